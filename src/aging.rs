@@ -0,0 +1,334 @@
+// Aging module - re-evaluating tasks that have sat in one horizon too long
+// This module demonstrates reading a task's history to answer "since when?",
+// and separating deciding from applying so a dry run shares the same logic
+//
+// A task is planned for the short, mid or long term, but plans go stale: a
+// mid-term task untouched for months either needs doing soon or needs
+// dropping. Aging rules give each horizon a maximum age. A task that has been
+// in its horizon for longer is either:
+//
+// - flagged: listed in the aging report for the user to decide, or
+// - promoted: moved one horizon closer (long → mid → short), recorded in the
+//   task's history like any other change
+//
+// Short-term tasks can't be promoted any further, so they are only flagged.
+//
+// # When the rules run
+//
+// `todo age` applies them and prints the report (`--dry-run` only shows it).
+// With `on_load` set, they also run whenever the data is loaded, before any
+// command; promotions are then reported on stderr.
+//
+// # Configuration
+//
+// The `aging` section of config.json (see the config module); missing fields
+// keep their defaults, and `null` turns off the rule for a horizon:
+//
+// ```json
+// {
+//   "aging": {
+//     "short_term_days": 14,
+//     "mid_term_days": 60,
+//     "long_term_days": 180,
+//     "promote": false,
+//     "on_load": false
+//   }
+// }
+// ```
+//
+// A task's age in its horizon counts from its last horizon change in the
+// history, or from its creation if it never moved. Promoting a task is a
+// horizon change, so the clock starts again in the new horizon.
+
+use crate::context::ContextManager;
+use crate::task::{Task, TimeHorizon};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Maximum ages per horizon and what to do about older tasks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgingRules {
+    /// Days a task may stay short-term before it is flagged
+    pub short_term_days: Option<u32>,
+
+    /// Days a task may stay mid-term before it is flagged or promoted
+    pub mid_term_days: Option<u32>,
+
+    /// Days a task may stay long-term before it is flagged or promoted
+    pub long_term_days: Option<u32>,
+
+    /// Promote old mid- and long-term tasks instead of only flagging them
+    pub promote: bool,
+
+    /// Also apply the rules every time the data is loaded
+    pub on_load: bool,
+}
+
+impl Default for AgingRules {
+    fn default() -> Self {
+        AgingRules {
+            short_term_days: Some(14),
+            mid_term_days: Some(60),
+            long_term_days: Some(180),
+            promote: false,
+            on_load: false,
+        }
+    }
+}
+
+impl AgingRules {
+    /// The maximum age for tasks of `horizon`, if there is one
+    fn limit(&self, horizon: TimeHorizon) -> Option<u32> {
+        match horizon {
+            TimeHorizon::ShortTerm => self.short_term_days,
+            TimeHorizon::MidTerm => self.mid_term_days,
+            TimeHorizon::LongTerm => self.long_term_days,
+        }
+    }
+}
+
+/// What happened to an old task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgingAction {
+    /// Left where it is, for the user to look at
+    Flagged,
+
+    /// Moved to this horizon
+    Promoted(TimeHorizon),
+}
+
+/// One task the rules found too old
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgedTask {
+    /// Name of the context the task is in
+    pub context: String,
+    pub id: String,
+    pub description: String,
+
+    /// The horizon the task was in
+    pub horizon: TimeHorizon,
+
+    /// Whole days the task had been in that horizon
+    pub days: i64,
+
+    /// The maximum age it went over
+    pub limit: u32,
+
+    pub action: AgingAction,
+}
+
+/// Everything the rules found, by context name and then manual order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgingReport {
+    pub tasks: Vec<AgedTask>,
+}
+
+impl AgingReport {
+    /// Number of tasks that were (or would be) promoted
+    pub fn promoted(&self) -> usize {
+        self.tasks
+            .iter()
+            .filter(|task| matches!(task.action, AgingAction::Promoted(_)))
+            .count()
+    }
+}
+
+/// Applies the aging rules to the open tasks of every context
+///
+/// # Arguments
+///
+/// * `manager` - The data; promoted tasks are changed in place
+/// * `rules` - The maximum ages and whether to promote
+/// * `now` - The instant ages are measured to
+/// * `dry_run` - Only report; change nothing
+///
+/// # Returns
+///
+/// The report of flagged and promoted tasks. With `dry_run`, promotions are
+/// reported as they would happen.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Duration, Utc};
+/// use rust_todo::aging::{apply, AgingAction, AgingRules};
+/// use rust_todo::context::ContextManager;
+/// use rust_todo::task::{Priority, Task, TimeHorizon};
+///
+/// let mut manager = ContextManager::new();
+/// let mut task = Task::new("Learn Rust".to_string(), TimeHorizon::LongTerm, Priority::Medium);
+/// task.created_at = (Utc::now() - Duration::days(200)).to_rfc3339();
+/// manager.active_context_mut().add_task(task);
+///
+/// let rules = AgingRules { promote: true, ..AgingRules::default() };
+/// let report = apply(&mut manager, &rules, Utc::now(), false);
+///
+/// assert_eq!(report.tasks[0].action, AgingAction::Promoted(TimeHorizon::MidTerm));
+/// assert_eq!(manager.active_context().tasks[0].time_horizon, TimeHorizon::MidTerm);
+/// ```
+pub fn apply(
+    manager: &mut ContextManager,
+    rules: &AgingRules,
+    now: DateTime<Utc>,
+    dry_run: bool,
+) -> AgingReport {
+    let mut names: Vec<String> = manager.contexts.keys().cloned().collect();
+    names.sort();
+
+    let mut report = AgingReport::default();
+    for name in names {
+        let context = manager
+            .contexts
+            .get_mut(&name)
+            .expect("names come from the map");
+
+        for task in context.tasks.iter_mut().filter(|task| !task.completed) {
+            let Some(limit) = rules.limit(task.time_horizon) else {
+                continue;
+            };
+            let Some(days) = days_in_horizon(task, now) else {
+                continue;
+            };
+            if days <= i64::from(limit) {
+                continue;
+            }
+
+            let action = match promotion(task.time_horizon) {
+                Some(to) if rules.promote => AgingAction::Promoted(to),
+                _ => AgingAction::Flagged,
+            };
+            report.tasks.push(AgedTask {
+                context: name.clone(),
+                id: task.id.clone(),
+                description: task.description.clone(),
+                horizon: task.time_horizon,
+                days,
+                limit,
+                action,
+            });
+
+            if let (AgingAction::Promoted(to), false) = (action, dry_run) {
+                let before = task.clone();
+                task.time_horizon = to;
+                task.record_changes(&before);
+            }
+        }
+    }
+    report
+}
+
+/// The next horizon closer to now, or None for short-term
+fn promotion(horizon: TimeHorizon) -> Option<TimeHorizon> {
+    match horizon {
+        TimeHorizon::LongTerm => Some(TimeHorizon::MidTerm),
+        TimeHorizon::MidTerm => Some(TimeHorizon::ShortTerm),
+        TimeHorizon::ShortTerm => None,
+    }
+}
+
+/// Whole days since the task entered its current horizon
+///
+/// That is the last horizon change in its history, or its creation. None if
+/// the timestamp can't be parsed.
+fn days_in_horizon(task: &Task, now: DateTime<Utc>) -> Option<i64> {
+    let since = task
+        .history
+        .iter()
+        .rev()
+        .find(|entry| entry.change.field == "time_horizon")
+        .map(|entry| entry.at.as_str())
+        .unwrap_or(&task.created_at);
+    let since = DateTime::parse_from_rfc3339(since).ok()?;
+    Some((now - since.with_timezone(&Utc)).num_days())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Priority;
+    use chrono::Duration;
+
+    fn manager_with(tasks: &[(&str, TimeHorizon, i64)], now: DateTime<Utc>) -> ContextManager {
+        let mut manager = ContextManager::new();
+        for (description, horizon, days_ago) in tasks {
+            let mut task = Task::new(description.to_string(), *horizon, Priority::Medium);
+            task.created_at = (now - Duration::days(*days_ago)).to_rfc3339();
+            manager.active_context_mut().add_task(task);
+        }
+        manager
+    }
+
+    #[test]
+    fn test_flags_without_changing_anything() {
+        let now = Utc::now();
+        let mut manager = manager_with(
+            &[
+                ("fresh mid", TimeHorizon::MidTerm, 10),
+                ("old mid", TimeHorizon::MidTerm, 61),
+                ("old short", TimeHorizon::ShortTerm, 15),
+                ("old long", TimeHorizon::LongTerm, 400),
+            ],
+            now,
+        );
+        let before = serde_json::to_string(&manager).unwrap();
+
+        let report = apply(&mut manager, &AgingRules::default(), now, false);
+        let flagged: Vec<(&str, i64)> = report
+            .tasks
+            .iter()
+            .map(|task| (task.description.as_str(), task.days))
+            .collect();
+        assert_eq!(
+            flagged,
+            [("old mid", 61), ("old short", 15), ("old long", 400)]
+        );
+        assert!(report
+            .tasks
+            .iter()
+            .all(|task| task.action == AgingAction::Flagged));
+        assert_eq!(serde_json::to_string(&manager).unwrap(), before);
+    }
+
+    #[test]
+    fn test_promotes_one_step_and_restarts_the_clock() {
+        let now = Utc::now();
+        let mut manager = manager_with(
+            &[
+                ("old long", TimeHorizon::LongTerm, 400),
+                ("old short", TimeHorizon::ShortTerm, 30),
+            ],
+            now,
+        );
+        let rules = AgingRules {
+            promote: true,
+            short_term_days: None,
+            ..AgingRules::default()
+        };
+
+        // A dry run reports the promotion but changes nothing
+        let preview = apply(&mut manager.clone(), &rules, now, true);
+        let report = apply(&mut manager, &rules, now, false);
+        assert_eq!(preview, report);
+        assert_eq!(report.promoted(), 1);
+        assert_eq!(report.tasks.len(), 1);
+
+        let task = &manager.active_context().tasks[0];
+        assert_eq!(task.time_horizon, TimeHorizon::MidTerm);
+        assert_eq!(task.history[0].change.field, "time_horizon");
+
+        // Just moved to mid-term, so it isn't old there yet
+        assert!(apply(&mut manager, &rules, now, false).tasks.is_empty());
+    }
+
+    #[test]
+    fn test_completed_tasks_are_left_alone() {
+        let now = Utc::now();
+        let mut manager = manager_with(&[("done", TimeHorizon::MidTerm, 100)], now);
+        manager.active_context_mut().tasks[0].mark_complete();
+
+        assert!(apply(&mut manager, &AgingRules::default(), now, false)
+            .tasks
+            .is_empty());
+    }
+}
@@ -0,0 +1,379 @@
+// Chart module - burndown and throughput charts drawn with Unicode blocks
+// This module demonstrates rebuilding a time series from timestamps, and
+// rendering it at sub-character resolution with the eighth-block characters
+//
+// `todo chart burndown` shows how many tasks of a context were open at the
+// end of each day since a date, and `todo chart throughput` how many were
+// completed each week (weeks start on Monday, dates are UTC).
+//
+// Tasks don't store daily snapshots, so the series are rebuilt from each
+// task's creation and completion times. The completion time comes from the
+// task's history (Task::completed_at()). Tasks completed before history was
+// recorded, or imported without it, are left out of both charts, as they are
+// of the statistics, since when they were completed isn't known. Deleted
+// tasks are gone from the data, so they don't appear either.
+//
+// Rendering returns plain lines for a given width, so the charts fit the
+// terminal (see terminal_width()) and can be tested without one.
+
+use crate::stats::Period;
+use crate::task::Task;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use std::io::IsTerminal;
+
+/// Block characters from one to eight eighths high, bottom-aligned
+const VERTICAL_EIGHTHS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Block characters from one to eight eighths wide, left-aligned
+const HORIZONTAL_EIGHTHS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// Rows of the burndown chart, not counting the axis
+const BURNDOWN_HEIGHT: usize = 10;
+
+/// Width used when output doesn't go to a terminal and COLUMNS isn't set
+const DEFAULT_WIDTH: usize = 80;
+
+/// One value of a series
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Point {
+    /// The day, or the first day of the week
+    pub date: NaiveDate,
+    pub count: usize,
+}
+
+/// The UTC day an RFC 3339 timestamp falls on
+fn day_of(timestamp: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|at| at.with_timezone(&Utc).date_naive())
+}
+
+/// The day a completed task was completed
+///
+/// None for open tasks, and for completed tasks whose completion time isn't
+/// known.
+fn completion_day(task: &Task) -> Option<NaiveDate> {
+    if !task.completed {
+        return None;
+    }
+    day_of(task.completed_at()?)
+}
+
+/// Open tasks at the end of each day from `since` to `until`, inclusive
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use rust_todo::chart::burndown;
+/// use rust_todo::task::{Priority, Task, TimeHorizon};
+///
+/// let mut task = Task::new("Ship it".to_string(), TimeHorizon::ShortTerm, Priority::High);
+/// task.created_at = "2026-10-01T09:00:00+00:00".to_string();
+///
+/// let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+/// let points = burndown(&[&task], day(1), day(3));
+/// assert_eq!(points.len(), 3);
+/// assert!(points.iter().all(|point| point.count == 1));
+/// ```
+pub fn burndown(tasks: &[&Task], since: NaiveDate, until: NaiveDate) -> Vec<Point> {
+    let spans: Vec<(NaiveDate, Option<NaiveDate>)> = tasks
+        .iter()
+        .filter_map(|task| {
+            let completed = completion_day(task);
+            // Completed at an unknown time: there's no day it stopped being open
+            if task.completed && completed.is_none() {
+                return None;
+            }
+            Some((day_of(&task.created_at)?, completed))
+        })
+        .collect();
+
+    since
+        .iter_days()
+        .take_while(|date| *date <= until)
+        .map(|date| Point {
+            date,
+            count: spans
+                .iter()
+                .filter(|(created, completed)| {
+                    *created <= date && completed.is_none_or(|done| done > date)
+                })
+                .count(),
+        })
+        .collect()
+}
+
+/// Tasks completed in each of the last `weeks` weeks, ending with the week of `today`
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use rust_todo::chart::throughput;
+/// use rust_todo::task::{Priority, Task, TimeHorizon};
+///
+/// let mut task = Task::new("Ship it".to_string(), TimeHorizon::ShortTerm, Priority::High);
+/// task.mark_complete();
+///
+/// let today = chrono::Utc::now().date_naive();
+/// let points = throughput(&[&task], 4, today);
+/// assert_eq!(points.len(), 4);
+/// assert_eq!(points[3].count, 1);
+/// ```
+pub fn throughput(tasks: &[&Task], weeks: usize, today: NaiveDate) -> Vec<Point> {
+    let current = Period::Week.start(today);
+    let completions: Vec<NaiveDate> = tasks
+        .iter()
+        .filter_map(|task| completion_day(task))
+        .map(|day| Period::Week.start(day))
+        .collect();
+
+    (0..weeks as i64)
+        .rev()
+        .map(|back| current - Duration::weeks(back))
+        .map(|date| Point {
+            date,
+            count: completions.iter().filter(|week| **week == date).count(),
+        })
+        .collect()
+}
+
+/// Draws a series as vertical bars, one column per day, within `width` characters
+///
+/// When there are more days than columns, each column shows the last day it
+/// covers; with few days, columns get wider. The dates of the first and last
+/// day are printed under the axis.
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use rust_todo::chart::{render_columns, Point};
+///
+/// let date = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+/// let lines = render_columns(&[Point { date, count: 4 }, Point { date, count: 2 }], 40);
+/// assert!(lines[0].starts_with(" 4 ┤"));
+/// ```
+pub fn render_columns(points: &[Point], width: usize) -> Vec<String> {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    let max = points.iter().map(|point| point.count).max().unwrap_or(0);
+    let label_width = max.to_string().len() + 1;
+    let available = width.saturating_sub(label_width + 2).max(1);
+
+    // Several days per column when they don't fit, several columns per day
+    // when there's room to spare
+    let per_column = points.len().div_ceil(available);
+    let values: Vec<usize> = points
+        .chunks(per_column)
+        .map(|chunk| chunk[chunk.len() - 1].count)
+        .collect();
+    let column_width = (available / values.len()).clamp(1, 4);
+
+    let mut lines = Vec::new();
+    for row in (0..BURNDOWN_HEIGHT).rev() {
+        let label = if row == BURNDOWN_HEIGHT - 1 {
+            max.to_string()
+        } else {
+            String::new()
+        };
+        let cells: String = values
+            .iter()
+            .map(|value| {
+                let cell = match eighths(*value, max, BURNDOWN_HEIGHT).saturating_sub(row * 8) {
+                    0 => ' ',
+                    filled => VERTICAL_EIGHTHS[filled.min(8) - 1],
+                };
+                cell.to_string().repeat(column_width)
+            })
+            .collect();
+        lines.push(format!("{:>label_width$} ┤{}", label, cells));
+    }
+    let plotted = values.len() * column_width;
+    lines.push(format!("{:>label_width$} └{}", 0, "─".repeat(plotted)));
+
+    // Dates under the axis, the last one right-aligned if it fits
+    let start = first.date.format("%Y-%m-%d").to_string();
+    let end = last.date.format("%Y-%m-%d").to_string();
+    let indent = " ".repeat(label_width + 2);
+    if first.date != last.date && plotted > start.len() + end.len() {
+        let gap = plotted - start.len() - end.len();
+        lines.push(format!("{}{}{}{}", indent, start, " ".repeat(gap), end));
+    } else {
+        lines.push(format!("{}{}", indent, start));
+    }
+    lines
+}
+
+/// Draws a series as horizontal bars, one line per point, within `width` characters
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use rust_todo::chart::{render_bars, Point};
+///
+/// let date = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+/// let lines = render_bars(&[Point { date, count: 3 }], 40);
+/// assert!(lines[0].starts_with("2026-10-12 │█"));
+/// assert!(lines[0].ends_with(" 3"));
+/// ```
+pub fn render_bars(points: &[Point], width: usize) -> Vec<String> {
+    let max = points.iter().map(|point| point.count).max().unwrap_or(0);
+    let count_width = max.to_string().len();
+    // "YYYY-MM-DD │" before the bar, " N" after it
+    let bar_width = width.saturating_sub(12 + 1 + count_width).max(1);
+
+    points
+        .iter()
+        .map(|point| {
+            let filled = eighths(point.count, max, bar_width);
+            let mut bar = HORIZONTAL_EIGHTHS[7].to_string().repeat(filled / 8);
+            if let Some(partial) = (filled % 8).checked_sub(1) {
+                bar.push(HORIZONTAL_EIGHTHS[partial]);
+            }
+            format!("{} │{} {}", point.date.format("%Y-%m-%d"), bar, point.count)
+        })
+        .collect()
+}
+
+/// `value` out of `max`, in eighths of `cells` characters; at least one
+/// eighth unless `value` is zero
+fn eighths(value: usize, max: usize, cells: usize) -> usize {
+    if value == 0 || max == 0 {
+        return 0;
+    }
+    let scaled = (value as f64 / max as f64 * (cells * 8) as f64).round() as usize;
+    scaled.max(1)
+}
+
+/// The width charts are drawn at
+///
+/// The terminal's width when stdout is one, otherwise the COLUMNS
+/// environment variable, otherwise 80 characters.
+pub fn terminal_width() -> usize {
+    if std::io::stdout().is_terminal() {
+        if let Ok((columns, _)) = ratatui::crossterm::terminal::size() {
+            return usize::from(columns);
+        }
+    }
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{FieldChange, HistoryEntry, Priority, TimeHorizon};
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    /// A task created on October `created`, completed on October `completed`
+    fn task(created: u32, completed: Option<u32>) -> Task {
+        let mut task = Task::new("Task".to_string(), TimeHorizon::ShortTerm, Priority::Medium);
+        task.created_at = format!("2026-10-{:02}T09:00:00+00:00", created);
+        if let Some(d) = completed {
+            task.completed = true;
+            task.history.push(HistoryEntry {
+                at: format!("2026-10-{:02}T17:00:00+00:00", d),
+                change: FieldChange {
+                    field: "completed".to_string(),
+                    from: "false".to_string(),
+                    to: "true".to_string(),
+                },
+            });
+        }
+        task
+    }
+
+    #[test]
+    fn test_burndown_counts_open_at_end_of_day() {
+        let tasks = [
+            task(1, Some(2)),
+            task(1, None),
+            task(3, Some(3)),
+            task(2, None),
+        ];
+        let refs: Vec<&Task> = tasks.iter().collect();
+
+        let counts: Vec<usize> = burndown(&refs, day(1), day(4))
+            .iter()
+            .map(|point| point.count)
+            .collect();
+        assert_eq!(counts, [2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn test_unknown_completion_times_are_left_out() {
+        // Completed before history was recorded: when isn't known, even
+        // though the task was modified later
+        let mut old = task(1, None);
+        old.completed = true;
+        old.updated_at = Some("2026-10-02T10:00:00+00:00".to_string());
+        let tasks = [old, task(1, Some(2))];
+        let refs: Vec<&Task> = tasks.iter().collect();
+
+        let counts: Vec<usize> = burndown(&refs, day(1), day(3))
+            .iter()
+            .map(|point| point.count)
+            .collect();
+        assert_eq!(counts, [1, 0, 0]);
+
+        let counts: Vec<usize> = throughput(&refs, 1, day(2))
+            .iter()
+            .map(|point| point.count)
+            .collect();
+        assert_eq!(counts, [1]);
+    }
+
+    #[test]
+    fn test_throughput_per_week() {
+        // The 5th and 12th are Mondays
+        let tasks = [
+            task(1, Some(6)),
+            task(1, Some(11)),
+            task(1, Some(12)),
+            task(1, None),
+        ];
+        let refs: Vec<&Task> = tasks.iter().collect();
+
+        let points = throughput(&refs, 3, day(14));
+        let weeks: Vec<(NaiveDate, usize)> = points
+            .iter()
+            .map(|point| (point.date, point.count))
+            .collect();
+        assert_eq!(
+            weeks,
+            [(day(5) - Duration::weeks(1), 0), (day(5), 2), (day(12), 1)]
+        );
+    }
+
+    #[test]
+    fn test_render_adapts_to_width() {
+        let points: Vec<Point> = (1..=31)
+            .map(|d| Point {
+                date: day(d),
+                count: 31 - d as usize,
+            })
+            .collect();
+
+        let narrow = render_columns(&points, 20);
+        assert!(narrow.iter().all(|line| line.chars().count() <= 20));
+        assert_eq!(narrow.len(), BURNDOWN_HEIGHT + 2);
+
+        let wide = render_columns(&points, 200);
+        assert!(wide[0].chars().count() > 60);
+        assert!(wide[BURNDOWN_HEIGHT + 1].ends_with("2026-10-31"));
+
+        let bars = render_bars(&points[..3], 30);
+        assert!(bars.iter().all(|line| line.chars().count() <= 30));
+        assert!(bars[0].contains('█'));
+    }
+}
@@ -59,6 +59,17 @@ use std::path::PathBuf;
 #[command(version)]
 #[command(author)]
 pub struct Cli {
+    /// Output format: text, json, or ndjson
+    ///
+    /// - text: Coloured output for humans (default)
+    /// - json: One JSON document per command, for scripts
+    /// - ndjson: One compact JSON object per line, for streaming
+    ///
+    /// This is a global flag, so it can appear before or after the subcommand.
+    /// See the output module for the documented JSON shapes.
+    #[arg(long = "output", global = true, default_value = "text")]
+    pub output: String,
+
     /// The subcommand to execute
     ///
     /// This field uses the Subcommand derive to parse subcommands.
@@ -118,6 +129,9 @@ pub enum Commands {
     ///
     /// # Using full flag names
     /// todo add "Fix bug" --horizon mid --priority high
+    ///
+    /// # With a due date
+    /// todo add "File taxes" --due 2027-04-15
     /// ```
     Add {
         /// Task description
@@ -144,12 +158,19 @@ pub enum Commands {
         /// Default: medium
         #[arg(short = 'p', long = "priority", default_value = "medium")]
         priority: String,
+
+        /// Due date: YYYY-MM-DD, today or tomorrow
+        ///
+        /// Tasks get more urgent as their due date comes closer (see `todo next`).
+        #[arg(long = "due", value_name = "DATE")]
+        due: Option<String>,
     },
 
-    /// List tasks in the active context
+    /// List tasks in the active context, or in several contexts
     ///
-    /// Displays tasks grouped by time horizon and sorted by priority within each horizon.
-    /// By default, shows all incomplete tasks. Use flags to customize the view.
+    /// Displays tasks grouped by time horizon and sorted by priority within each horizon,
+    /// or by the keys given with --sort. By default, shows all incomplete tasks of the
+    /// active context. Use flags to customize the view.
     ///
     /// # Examples
     ///
@@ -165,6 +186,21 @@ pub enum Commands {
     ///
     /// # List mid-term tasks including completed ones
     /// todo list -t mid --all
+    ///
+    /// # List tasks matching a filter expression
+    /// todo list --where 'priority>=medium and (desc~"api" or tag:backend)'
+    ///
+    /// # Newest first among tasks of the same priority
+    /// todo list --sort priority,-created
+    ///
+    /// # In the order set with `todo move`
+    /// todo list --sort manual
+    ///
+    /// # Every context, grouped by context and then by horizon
+    /// todo list --all-contexts
+    ///
+    /// # The high priority tasks of two contexts in one list
+    /// todo list --context work,home --where priority:high --flat
     /// ```
     List {
         /// Show all tasks including completed ones
@@ -179,12 +215,223 @@ pub enum Commands {
         /// If not specified, shows tasks from all time horizons.
         #[arg(short = 't', long = "horizon")]
         horizon: Option<String>,
+
+        /// Only show tasks matching a filter expression
+        ///
+        /// For example `priority>=medium and not completed and created>2026-01-01`.
+        /// Completed tasks stay hidden without --all, unless the filter itself
+        /// mentions completion (`status:done`, `completed`, ...).
+        #[arg(long = "where", value_name = "FILTER")]
+        filter: Option<String>,
+
+        /// Sort keys within each horizon, or for the whole --flat list
+        /// (default: priority)
+        ///
+        /// Keys are priority, horizon, created, updated, description, urgency,
+        /// due and manual. Separate several keys with commas; prefix a key with -
+        /// to reverse it. Tasks that tie on every key keep their manual order.
+        #[arg(short = 's', long = "sort", allow_hyphen_values = true)]
+        sort: Option<String>,
+
+        /// List the tasks of every context
+        #[arg(long = "all-contexts", conflicts_with = "contexts")]
+        all_contexts: bool,
+
+        /// List the tasks of these contexts (comma-separated or repeated)
+        ///
+        /// Contexts are shown in the order given. Without this flag or
+        /// --all-contexts, only the active context is listed.
+        #[arg(short = 'c', long = "context", value_delimiter = ',')]
+        contexts: Vec<String>,
+
+        /// Show one list instead of grouping by context and horizon
+        ///
+        /// Tasks are sorted by the --sort keys alone, and each line shows the
+        /// task's context.
+        #[arg(long = "flat")]
+        flat: bool,
     },
 
-    /// Mark a task as complete
+    /// Show all details of a single task
     ///
-    /// Marks the specified task as completed. The task ID can be a partial match
-    /// (e.g., the first 6 characters shown in the list view).
+    /// Displays the full ID, description, time horizon, priority, status,
+    /// creation time and tags of a task in the active context.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// # Show a task using a partial ID
+    /// todo show 123e45
+    ///
+    /// # Get the task as JSON
+    /// todo show 123e45 --output json
+    /// ```
+    Show {
+        /// Task ID (can be partial, will match prefix)
+        id: String,
+    },
+
+    /// Show the change history of a task
+    ///
+    /// Lists when the task was created and every change since: edits to the
+    /// description, horizon moves, priority changes, completion and reopening.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// # Show the timeline of a task using a partial ID
+    /// todo history 123e45
+    /// ```
+    History {
+        /// Task ID (can be partial, will match prefix)
+        id: String,
+    },
+
+    /// Search tasks in every context
+    ///
+    /// Searches task descriptions and tags across all contexts, tolerating
+    /// small typos. Results are ranked by relevance, with the matched words
+    /// highlighted and each task's context shown.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// # Which context was the deploy task in?
+    /// todo search deploy
+    ///
+    /// # Several terms must all match; typos are forgiven
+    /// todo search relase notes
+    /// ```
+    Search {
+        /// Words to search for
+        #[arg(required = true)]
+        terms: Vec<String>,
+
+        /// Show at most this many results
+        #[arg(short = 'n', long = "limit", default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Show the most urgent open tasks of every context
+    ///
+    /// Tasks are ranked by an urgency score made up of their priority,
+    /// horizon, age and whether they are blocked (tagged `blocked`). Each
+    /// task is shown with the breakdown of its score. The weights can be
+    /// changed in the `urgency` section of config.json, next to the data file.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// # The five most urgent tasks
+    /// todo next
+    ///
+    /// # Just the top one
+    /// todo next -n 1
+    /// ```
+    Next {
+        /// Show this many tasks
+        #[arg(short = 'n', long = "limit", default_value_t = 5)]
+        limit: usize,
+    },
+
+    /// Flag or promote tasks that have been in their horizon too long
+    ///
+    /// Applies the aging rules from the `aging` section of config.json to the
+    /// open tasks of every context. By default, short-term tasks older than
+    /// 14 days, mid-term ones older than 60 and long-term ones older than 180
+    /// are flagged; with `"promote": true`, mid- and long-term tasks move one
+    /// horizon closer instead. Prints what was found and why.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// # Apply the rules
+    /// todo age
+    ///
+    /// # Only show what would happen
+    /// todo age --dry-run
+    /// ```
+    Age {
+        /// Show the report without promoting anything
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
+
+    /// Show productivity statistics for all contexts
+    ///
+    /// Shows tasks created and completed per day or week, the average time
+    /// from creation to completion per horizon and priority, open tasks per
+    /// context and the oldest open tasks, as ASCII bar charts (or as one
+    /// JSON object with `--output json`).
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// # The last 8 weeks
+    /// todo stats
+    ///
+    /// # The last 14 days, and the 10 oldest open tasks
+    /// todo stats --by day --periods 14 --oldest 10
+    /// ```
+    Stats {
+        /// Count activity per day or per week
+        #[arg(long = "by", default_value = "week")]
+        by: String,
+
+        /// Number of days or weeks of activity to show
+        #[arg(long, default_value_t = 8)]
+        periods: usize,
+
+        /// Number of oldest open tasks to list
+        #[arg(long, default_value_t = 5)]
+        oldest: usize,
+    },
+
+    /// Draw burndown and throughput charts
+    ///
+    /// The charts are rebuilt from when tasks were created and completed, and
+    /// fit the width of the terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// todo chart burndown --context work --since 2026-10-01
+    /// todo chart throughput --weeks 26
+    /// ```
+    Chart {
+        /// Which chart to draw
+        #[command(subcommand)]
+        chart: ChartKind,
+    },
+
+    /// Walk through every open task of the active context
+    ///
+    /// Shows the open tasks one at a time, short-term first and the oldest
+    /// first within each horizon, and asks what to do with each:
+    ///
+    /// k keep, c complete, d delete, h <horizon> change horizon,
+    /// p <priority> change priority, f defer (one horizon later),
+    /// s skip, q quit
+    ///
+    /// Tasks the aging rules consider too old are marked. The time of each
+    /// answer is saved as the task's last review, and a summary is printed
+    /// at the end.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// todo review
+    /// ```
+    Review,
+
+    /// Mark tasks as complete
+    ///
+    /// Marks the specified tasks as completed. Task IDs can be partial matches
+    /// (e.g., the first 6 characters shown in the list view). Instead of IDs,
+    /// `--where` selects every task matching a filter expression.
+    ///
+    /// When more than one task may be affected, the tasks are listed and you
+    /// are asked to confirm; `--yes` skips the question.
     ///
     /// # Examples
     ///
@@ -194,19 +441,39 @@ pub enum Commands {
     ///
     /// # Complete a task using partial ID
     /// todo complete 123e45
+    ///
+    /// # Complete several tasks
+    /// todo complete 123e45 9f8e7d
+    ///
+    /// # Complete every low-priority short-term task, without asking
+    /// todo complete --where "horizon:short priority:low" --yes
     /// ```
     Complete {
-        /// Task ID (can be partial, will match prefix)
+        /// Task IDs (can be partial, will match prefix)
         ///
-        /// The unique identifier of the task to complete. You can use the full UUID
+        /// The unique identifiers of the tasks to complete. You can use the full UUID
         /// or just the first few characters (as shown in the list view).
-        id: String,
+        #[arg(required_unless_present = "filter", conflicts_with = "filter")]
+        ids: Vec<String>,
+
+        /// Filter selecting the tasks, e.g. "horizon:short priority:low"
+        ///
+        /// The same filter expressions as `list --where`, e.g.
+        /// `priority<=low and (tag:chore or created<2026-01-01)`.
+        #[arg(long = "where", value_name = "FILTER")]
+        filter: Option<String>,
+
+        /// Don't ask for confirmation
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
     },
 
-    /// Edit a task's properties
+    /// Edit task properties
     ///
-    /// Modifies one or more properties of an existing task. You can change the
+    /// Modifies one or more properties of existing tasks. You can change the
     /// description, time horizon, and/or priority. Only specified properties are changed.
+    /// Like `complete`, it accepts several IDs or a `--where` filter, and asks
+    /// for confirmation when more than one task may be affected.
     ///
     /// # Examples
     ///
@@ -219,12 +486,24 @@ pub enum Commands {
     ///
     /// # Change multiple properties
     /// todo edit abc123 -d "New description" -t long -p low
+    ///
+    /// # Move every task tagged sprint12 to the mid term
+    /// todo edit --where tag:sprint12 -t mid
     /// ```
     Edit {
-        /// Task ID to edit
+        /// Task IDs to edit
         ///
-        /// The unique identifier of the task to modify. Can be a partial ID.
-        id: String,
+        /// The unique identifiers of the tasks to modify. Can be partial IDs.
+        #[arg(required_unless_present = "filter", conflicts_with = "filter")]
+        ids: Vec<String>,
+
+        /// Filter selecting the tasks, e.g. "tag:sprint12"
+        #[arg(long = "where", value_name = "FILTER")]
+        filter: Option<String>,
+
+        /// Don't ask for confirmation
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
 
         /// New description
         ///
@@ -243,12 +522,18 @@ pub enum Commands {
         /// If specified, changes the task's priority to this value.
         #[arg(short = 'p', long = "priority")]
         priority: Option<String>,
+
+        /// New due date: YYYY-MM-DD, today, tomorrow, or none to remove it
+        #[arg(long = "due", value_name = "DATE")]
+        due: Option<String>,
     },
 
-    /// Delete a task
+    /// Delete tasks
     ///
-    /// Permanently removes the specified task from the active context.
-    /// This action cannot be undone.
+    /// Permanently removes the specified tasks from the active context.
+    /// This action cannot be undone. Like `complete`, it accepts several IDs
+    /// or a `--where` filter, and asks for confirmation when more than one
+    /// task may be affected.
     ///
     /// # Examples
     ///
@@ -258,12 +543,49 @@ pub enum Commands {
     ///
     /// # Delete a task using partial ID
     /// todo delete 123e45
+    ///
+    /// # Delete every completed task
+    /// todo delete --where status:done
     /// ```
     Delete {
-        /// Task ID to delete
+        /// Task IDs to delete
         ///
-        /// The unique identifier of the task to remove. Can be a partial ID.
+        /// The unique identifiers of the tasks to remove. Can be partial IDs.
+        #[arg(required_unless_present = "filter", conflicts_with = "filter")]
+        ids: Vec<String>,
+
+        /// Filter selecting the tasks, e.g. "status:done"
+        #[arg(long = "where", value_name = "FILTER")]
+        filter: Option<String>,
+
+        /// Don't ask for confirmation
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
+    },
+
+    /// Move a task within the manual order of its horizon and priority
+    ///
+    /// The manual order starts as the order tasks were added in. It is used by
+    /// `list --sort manual`, and breaks ties in every other sort order, so it
+    /// decides the order of tasks with the same priority in a plain
+    /// `todo list`. A task only moves among those tasks, so it never passes a
+    /// task of higher or lower priority.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// # Move a task one place up
+    /// todo move 123e45 up
+    ///
+    /// # Move it before every other task of its horizon and priority
+    /// todo move 123e45 top
+    /// ```
+    Move {
+        /// Task ID (can be partial, will match prefix)
         id: String,
+
+        /// Where to move it: up, down, top or bottom
+        direction: String,
     },
 
     /// Manage project contexts
@@ -294,6 +616,40 @@ pub enum Commands {
         action: ContextAction,
     },
 
+    /// Save and run named filters (views)
+    ///
+    /// A view is a filter expression plus optional sort keys, saved under a
+    /// name. Running a view lists the matching tasks of the active context in
+    /// the view's order. Views are global unless saved with --context, and
+    /// can also select what `export` and `report` include.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// # Save a view
+    /// todo view save triage "priority:high not completed" --sort created
+    ///
+    /// # Run it
+    /// todo view triage
+    ///
+    /// # A view that only exists in the work context
+    /// todo view save standup "tag:standup" --context work
+    ///
+    /// # List the views available here
+    /// todo view list
+    /// ```
+    #[command(args_conflicts_with_subcommands = true)]
+    View {
+        /// View action to perform
+        #[command(subcommand)]
+        action: Option<ViewAction>,
+
+        /// Name of the view to run (without an action)
+        ///
+        /// Without a name or action, the available views are listed.
+        name: Option<String>,
+    },
+
     /// Export tasks to a file
     ///
     /// Creates a JSON file containing all contexts and their tasks. This is useful
@@ -307,6 +663,12 @@ pub enum Commands {
     ///
     /// # Export to a file with path
     /// todo export ~/backups/todos-2024-01-15.json
+    ///
+    /// # Export for Taskwarrior (load with `task import`)
+    /// todo export tasks.json --format taskwarrior
+    ///
+    /// # Export only the open short-term tasks of the work context
+    /// todo export work.json --context work --horizon short --open-only
     /// ```
     Export {
         /// Output file path
@@ -314,6 +676,42 @@ pub enum Commands {
         /// The path where the export file should be created. The file will contain
         /// all contexts and tasks in JSON format.
         path: PathBuf,
+
+        /// File format: native or taskwarrior
+        ///
+        /// - native: This application's own JSON format
+        /// - taskwarrior: The JSON array format used by `task export` / `task import`
+        ///
+        /// Default: native
+        #[arg(short = 'f', long = "format", default_value = "native")]
+        format: String,
+
+        /// Only export these contexts (comma-separated or repeated)
+        ///
+        /// If not specified, all contexts are exported. The active context in the
+        /// exported file is the current one if selected, otherwise the first
+        /// selected context by name.
+        #[arg(short = 'c', long = "context", value_delimiter = ',')]
+        contexts: Vec<String>,
+
+        /// Only export tasks in this time horizon: short, mid, or long
+        #[arg(short = 't', long = "horizon")]
+        horizon: Option<String>,
+
+        /// Only export tasks that are not completed
+        #[arg(long = "open-only")]
+        open_only: bool,
+
+        /// Only export tasks matching a filter expression, e.g. "tag:backend"
+        #[arg(long = "where", value_name = "FILTER")]
+        filter: Option<String>,
+
+        /// Only export tasks matching a saved view's filter
+        ///
+        /// A view saved for one context also limits the export to that
+        /// context, unless --context is given.
+        #[arg(long = "view", conflicts_with = "filter")]
+        view: Option<String>,
     },
 
     /// Import tasks from a file
@@ -329,6 +727,13 @@ pub enum Commands {
     ///
     /// # Import and merge with existing data
     /// todo import backup.json --merge
+    ///
+    /// # Re-import a backup without duplicating tasks, previewing first
+    /// todo import backup.json --strategy union --dry-run
+    /// todo import backup.json --strategy union
+    ///
+    /// # Import the output of Taskwarrior's `task export`
+    /// todo import tasks.json --format taskwarrior
     /// ```
     Import {
         /// Input file path
@@ -337,12 +742,221 @@ pub enum Commands {
         /// (as created by the export command).
         path: PathBuf,
 
+        /// File format: native or taskwarrior
+        ///
+        /// With taskwarrior, each project becomes a context and tasks without a
+        /// project go to the "default" context. Deleted tasks are skipped.
+        ///
+        /// Default: native
+        #[arg(short = 'f', long = "format", default_value = "native")]
+        format: String,
+
         /// Merge with existing data instead of replacing
         ///
         /// If specified, imported contexts and tasks are added to the existing data.
-        /// If a context name conflicts, the imported context is renamed with a suffix.
+        /// How name and task clashes are handled depends on --strategy.
         #[arg(short = 'm', long = "merge")]
         merge: bool,
+
+        /// Merge strategy: rename, union, theirs, ours, or newest (implies --merge)
+        ///
+        /// - rename: a clashing context is added as `<name>-imported-N`
+        /// - union: tasks are merged by ID; completion and tags are combined
+        /// - theirs: tasks are merged by ID; imported values win conflicts
+        /// - ours: tasks are merged by ID; local values win conflicts
+        /// - newest: tasks are merged by ID; the last modified copy wins
+        ///
+        /// Default: rename
+        #[arg(short = 's', long = "strategy")]
+        strategy: Option<String>,
+
+        /// Show what the import would change without changing anything
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
+
+    /// Generate a report of all contexts
+    ///
+    /// Writes a self-contained HTML page with one section per context, a column
+    /// per time horizon, priority colour coding and completion percentages.
+    /// The page has no external assets, so it can be emailed as a single file.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// # Write the weekly report
+    /// todo report --html weekly.html
+    ///
+    /// # Report on a saved view only
+    /// todo report --html triage.html --view triage
+    /// ```
+    Report {
+        /// Output HTML file path
+        ///
+        /// The path where the HTML report should be written. Parent directories
+        /// are created if needed and an existing file is overwritten.
+        #[arg(long = "html")]
+        html: PathBuf,
+
+        /// Only include tasks matching a saved view's filter
+        ///
+        /// A view saved for one context limits the report to that context.
+        #[arg(long = "view")]
+        view: Option<String>,
+    },
+
+    /// Sync with another data file in both directions
+    ///
+    /// Merges this store with another data file (e.g., a copy kept in a shared
+    /// folder) and writes the result to both. Additions, edits, completions and
+    /// deletions made on either side since the last sync are carried over.
+    /// The first sync with a file has no common base, so differing fields are
+    /// treated as conflicts.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// # Sync with the copy in a shared folder, asking about conflicts
+    /// todo sync ~/Dropbox/todo/data.json
+    ///
+    /// # Sync from a script: the most recent edit wins every conflict
+    /// todo sync ~/Dropbox/todo/data.json --resolve newest
+    /// ```
+    Sync {
+        /// Path of the other data file
+        ///
+        /// Created if it doesn't exist yet.
+        path: PathBuf,
+
+        /// How to resolve conflicts: ask, ours, theirs, or newest
+        ///
+        /// - ask: prompt for each conflict (fails if there is no terminal)
+        /// - ours: keep the local value
+        /// - theirs: take the other file's value
+        /// - newest: keep the copy that was modified last
+        ///
+        /// Default: ask
+        #[arg(short = 'r', long = "resolve", default_value = "ask")]
+        resolve: String,
+    },
+
+    /// Print a shell completion script
+    ///
+    /// Completes commands and flags, and also task IDs (with descriptions in
+    /// zsh and fish) and context names, which the script asks `todo` for as
+    /// you type.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// todo completions bash > ~/.local/share/bash-completion/completions/todo
+    /// todo completions zsh > "${fpath[1]}/_todo"
+    /// todo completions fish > ~/.config/fish/completions/todo.fish
+    /// ```
+    Completions {
+        /// Shell to generate the script for: bash, zsh, or fish
+        shell: String,
+    },
+
+    /// Start an interactive shell
+    ///
+    /// Reads commands line by line in the usual syntax, without the leading
+    /// `todo`, keeping the data loaded between them. Tab completes command
+    /// names, task IDs and context names, and history is kept between
+    /// sessions. The data is saved by `save` and on exit (`exit`, `quit` or
+    /// Ctrl-D).
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// todo shell
+    /// default> add "Write docs" -p high
+    /// default> complete 5f0e
+    /// default> exit
+    /// ```
+    Shell,
+
+    /// Open the full-screen terminal interface
+    ///
+    /// Shows the active context as a board with one column per time horizon,
+    /// with a context switcher and search box. Tasks are completed, edited,
+    /// deleted and re-prioritised with single keys; every change is saved
+    /// immediately. Press q to quit.
+    ///
+    /// See the tui module for the full list of keys.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// todo tui
+    /// ```
+    Tui,
+
+    /// Serve a local HTTP REST API
+    ///
+    /// Exposes contexts and tasks as JSON endpoints so other programs can read
+    /// and change them. Runs until interrupted. Every request reads the data
+    /// file afresh, so CLI commands run alongside the server are seen at once.
+    /// Changes made through the API are not committed in git-backed mode.
+    ///
+    /// See the server module for the list of endpoints.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// # Serve on the default address
+    /// todo serve
+    ///
+    /// # Then, from another terminal
+    /// curl http://127.0.0.1:8080/contexts/default/tasks?horizon=short
+    /// ```
+    Serve {
+        /// Address to listen on
+        ///
+        /// Binding to anything other than a loopback address exposes the
+        /// data, unauthenticated, to the network.
+        ///
+        /// Default: 127.0.0.1:8080
+        #[arg(long = "bind", default_value = "127.0.0.1:8080")]
+        bind: String,
+    },
+
+    /// Speak JSON-RPC 2.0 on stdin/stdout
+    ///
+    /// For editor plugins: one long-lived process answers requests (one JSON
+    /// message per line) instead of running `todo` for every action, and
+    /// sends a `data.changed` notification when the data file is changed by
+    /// something else. Runs until stdin is closed.
+    ///
+    /// See the rpc module for the methods and message format.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// echo '{"jsonrpc":"2.0","id":1,"method":"list","params":{}}' | todo rpc
+    /// ```
+    Rpc,
+
+    /// Manage git-backed storage
+    ///
+    /// Once the data directory is a git repository, every command that changes
+    /// data creates a commit describing the change. The repository can be
+    /// pushed to a remote by hand with the usual git commands.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// # Turn on git-backed storage
+    /// todo git init
+    ///
+    /// # See recent changes, then go back to an earlier state
+    /// todo git log
+    /// todo git restore 3f2a1bc
+    /// ```
+    Git {
+        /// Git action to perform
+        #[command(subcommand)]
+        action: GitAction,
     },
 }
 
@@ -442,3 +1056,132 @@ pub enum ContextAction {
         name: String,
     },
 }
+
+/// Saved view subcommands
+///
+/// This enum defines the operations on saved views:
+/// - todo view save <name> <filter> [--sort <key>] [--context <name>]
+/// - todo view list
+/// - todo view delete <name>
+///
+/// Running a view is `todo view <name>`, handled by the View command itself.
+#[derive(Subcommand)]
+pub enum ViewAction {
+    /// Save a view, replacing one with the same name
+    ///
+    /// # Example
+    ///
+    /// ```bash
+    /// todo view save triage "priority:high not completed" --sort created
+    /// ```
+    Save {
+        /// Name to run the view by
+        name: String,
+
+        /// Filter expression, as accepted by `list --where`
+        filter: String,
+
+        /// Sort keys: priority, horizon, created, updated, description,
+        /// urgency, due, manual
+        ///
+        /// Separate several keys with commas; prefix a key with - to reverse
+        /// it, e.g. priority,-created. Without sort keys, tasks are shown in
+        /// their manual order.
+        #[arg(short = 's', long = "sort", allow_hyphen_values = true)]
+        sort: Option<String>,
+
+        /// Save the view for this context only (default: global)
+        #[arg(short = 'c', long = "context")]
+        context: Option<String>,
+    },
+
+    /// List the views available in the active context
+    List,
+
+    /// Delete a view
+    ///
+    /// Deletes the view of the active context with this name if there is
+    /// one, otherwise the global view.
+    Delete {
+        /// Name of the view to delete
+        name: String,
+    },
+}
+
+/// Git storage subcommands
+///
+/// This enum defines the operations available for git-backed storage:
+/// - todo git init
+/// - todo git log [-n <count>]
+/// - todo git restore <rev>
+#[derive(Subcommand)]
+pub enum GitAction {
+    /// Turn the data directory into a git repository
+    ///
+    /// Commits the current data as the first commit. Safe to run again.
+    Init,
+
+    /// Show the most recent changes to the data
+    Log {
+        /// Number of commits to show
+        #[arg(short = 'n', long = "limit", default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Restore the data as it was at an earlier commit
+    ///
+    /// The restore is committed like any other change, so it can be undone
+    /// by restoring the commit before it.
+    ///
+    /// # Example
+    ///
+    /// ```bash
+    /// todo git restore 3f2a1bc
+    /// todo git restore HEAD~2
+    /// ```
+    Restore {
+        /// Commit to restore (hash from `todo git log`, or any git revision)
+        rev: String,
+    },
+}
+
+/// Charts drawn by `todo chart`
+///
+/// - todo chart burndown [--context <name>] [--since <date>]
+/// - todo chart throughput [--context <name>] [--weeks <n>]
+#[derive(Subcommand)]
+pub enum ChartKind {
+    /// Open tasks at the end of each day
+    ///
+    /// # Example
+    ///
+    /// ```bash
+    /// todo chart burndown --context work --since 2026-10-01
+    /// ```
+    Burndown {
+        /// Context to chart (default: the active context)
+        #[arg(short = 'c', long = "context")]
+        context: Option<String>,
+
+        /// First day of the chart, as YYYY-MM-DD (default: four weeks ago)
+        #[arg(long)]
+        since: Option<String>,
+    },
+
+    /// Tasks completed each week
+    ///
+    /// # Example
+    ///
+    /// ```bash
+    /// todo chart throughput --weeks 26
+    /// ```
+    Throughput {
+        /// Context to chart (default: the active context)
+        #[arg(short = 'c', long = "context")]
+        context: Option<String>,
+
+        /// Number of weeks to show, ending with the current one
+        #[arg(long, default_value_t = 12)]
+        weeks: usize,
+    },
+}
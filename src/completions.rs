@@ -0,0 +1,278 @@
+// Completions module - shell completion scripts with dynamic candidates
+// This module demonstrates generating code from the clap definition, and post-processing it
+//
+// `todo completions <bash|zsh|fish>` prints a completion script. The static
+// part (commands, flags, their help text) is generated by clap_complete from
+// the Cli definition, so it never goes out of date. On top of that, the
+// script asks the binary itself for the things only the data knows:
+//
+// - task IDs, for `show`, `history`, `move`, `complete`, `edit` and `delete`
+//   (zsh and fish show each task's description next to its ID); the last
+//   three take several, so IDs keep being offered
+// - context names, for `context switch` and `context delete`
+//
+// It does so by running `todo` with TODO_COMPLETE set to `ids` or `contexts`,
+// which prints one candidate per line instead of running a command. Task
+// lines are `<short id>\t<description>`. (An environment variable rather than
+// a hidden subcommand, because clap_complete would offer a hidden subcommand
+// as a completion.)
+//
+// # Installing
+//
+// ```bash
+// todo completions bash > ~/.local/share/bash-completion/completions/todo
+// todo completions zsh > "${fpath[1]}/_todo"
+// todo completions fish > ~/.config/fish/completions/todo.fish
+// ```
+//
+// # How the scripts are extended
+//
+// For bash and zsh the generated `_todo` function is renamed `_todo_static`,
+// and a new `_todo` handles the dynamic cases before falling back to it. That
+// keeps the function name the shells expect (zsh autoloads `_todo` from
+// fpath). Fish completions are independent lines, so two are appended.
+
+use crate::cli::Cli;
+use crate::context::ContextManager;
+use crate::error::{AppError, Result};
+use clap::CommandFactory;
+use std::str::FromStr;
+
+/// Name of the binary the scripts complete
+const BIN_NAME: &str = "todo";
+
+/// Environment variable that asks `todo` for completion candidates
+pub const CANDIDATES_ENV: &str = "TODO_COMPLETE";
+
+/// Shells a completion script can be generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for CompletionShell {
+    type Err = AppError;
+
+    /// Parses a shell name: bash, zsh or fish (case-insensitive)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use rust_todo::completions::CompletionShell;
+    ///
+    /// assert_eq!(CompletionShell::from_str("ZSH").unwrap(), CompletionShell::Zsh);
+    /// assert!(CompletionShell::from_str("powershell").is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "bash" => Ok(CompletionShell::Bash),
+            "zsh" => Ok(CompletionShell::Zsh),
+            "fish" => Ok(CompletionShell::Fish),
+            _ => Err(AppError::InvalidShell(s.to_string())),
+        }
+    }
+}
+
+/// Bash: handle IDs and context names, otherwise use the generated function
+///
+/// The global `--output` flag and its value are skipped when working out
+/// which command is being completed. After a flag, the flag's value is being
+/// typed, so the generated function handles it.
+const BASH_DYNAMIC: &str = r#"
+_todo() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}" args=() i
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        case "${COMP_WORDS[i]}" in
+            --output) ((i++)) ;;
+            -*) ;;
+            *) args+=("${COMP_WORDS[i]}") ;;
+        esac
+    done
+    if [[ "${cur}" != -* && "${prev}" != -* ]]; then
+        case "${args[*]}" in
+            show|history|move|complete|edit|delete|"complete "*|"edit "*|"delete "*)
+                COMPREPLY=($(compgen -W "$(env TODO_COMPLETE=ids todo 2>/dev/null | cut -f1)" -- "${cur}"))
+                return 0 ;;
+            "context switch"|"context delete")
+                COMPREPLY=($(compgen -W "$(env TODO_COMPLETE=contexts todo 2>/dev/null)" -- "${cur}"))
+                return 0 ;;
+        esac
+    fi
+    _todo_static "$@"
+}
+"#;
+
+/// Zsh: the same, with descriptions via _describe ("id:description")
+const ZSH_DYNAMIC: &str = r#"
+_todo() {
+    local -a args candidates
+    local i
+    for ((i = 2; i < CURRENT; i++)); do
+        case "${words[i]}" in
+            --output) ((i++)) ;;
+            -*) ;;
+            *) args+=("${words[i]}") ;;
+        esac
+    done
+    if [[ "${words[CURRENT]}" != -* && "${words[CURRENT-1]}" != -* ]]; then
+        case "${args[*]}" in
+            show|history|move|complete|edit|delete|"complete "*|"edit "*|"delete "*)
+                candidates=(${(f)"$(env TODO_COMPLETE=ids todo 2>/dev/null)"})
+                candidates=("${(@)candidates//$'\t'/:}")
+                _describe -t tasks 'task' candidates && return ;;
+            "context switch"|"context delete")
+                candidates=(${(f)"$(env TODO_COMPLETE=contexts todo 2>/dev/null)"})
+                _describe -t contexts 'context' candidates && return ;;
+        esac
+    fi
+    _todo_static "$@"
+}
+"#;
+
+/// Fish: extra completion lines; fish shows the text after a tab as the description
+const FISH_DYNAMIC: &str = r#"
+complete -c todo -n "__fish_todo_using_subcommand show history move complete edit delete" -f -a "(env TODO_COMPLETE=ids todo 2>/dev/null)"
+complete -c todo -n "__fish_todo_using_subcommand context; and __fish_seen_subcommand_from switch delete" -f -a "(env TODO_COMPLETE=contexts todo 2>/dev/null)"
+"#;
+
+/// Generates the completion script for `shell`
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::completions::{script, CompletionShell};
+///
+/// let script = script(CompletionShell::Fish);
+/// assert!(script.contains("env TODO_COMPLETE=ids todo"));
+/// ```
+pub fn script(shell: CompletionShell) -> String {
+    let generator = match shell {
+        CompletionShell::Bash => clap_complete::Shell::Bash,
+        CompletionShell::Zsh => clap_complete::Shell::Zsh,
+        CompletionShell::Fish => clap_complete::Shell::Fish,
+    };
+
+    let mut generated = Vec::new();
+    clap_complete::generate(generator, &mut Cli::command(), BIN_NAME, &mut generated);
+    let generated = String::from_utf8_lossy(&generated);
+
+    match shell {
+        CompletionShell::Bash => wrap(&generated, BASH_DYNAMIC),
+        CompletionShell::Zsh => wrap(&generated, ZSH_DYNAMIC),
+        CompletionShell::Fish => format!("{}{}", generated, FISH_DYNAMIC),
+    }
+}
+
+/// Renames the generated `_todo` to `_todo_static` and adds the dynamic `_todo`
+fn wrap(generated: &str, dynamic: &str) -> String {
+    let renamed = generated.replacen("\n_todo() {", "\n_todo_static() {", 1);
+    let renamed = if generated.starts_with("_todo() {") {
+        renamed.replacen("_todo() {", "_todo_static() {", 1)
+    } else {
+        renamed
+    };
+    format!("{}{}", renamed, dynamic)
+}
+
+/// Lists completion candidates, as requested through CANDIDATES_ENV
+///
+/// # Arguments
+///
+/// * `manager` - The loaded data
+/// * `kind` - "ids" for task IDs in the active context, "contexts" for context names
+///
+/// # Returns
+///
+/// One line per candidate. Task lines are `<short id>\t<description>`, open
+/// tasks first; context names are sorted.
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::completions::candidates;
+/// use rust_todo::context::ContextManager;
+///
+/// let manager = ContextManager::new();
+/// assert_eq!(candidates(&manager, "contexts").unwrap(), ["default"]);
+/// ```
+pub fn candidates(manager: &ContextManager, kind: &str) -> Result<Vec<String>> {
+    match kind {
+        "ids" => {
+            let mut tasks: Vec<_> = manager.active_context().tasks.iter().collect();
+            // Completed tasks are rarely what is being looked for
+            tasks.sort_by_key(|task| task.completed);
+            Ok(tasks
+                .into_iter()
+                .map(|task| {
+                    // Tabs and newlines would break the line format
+                    let description = task.description.replace(['\t', '\n'], " ");
+                    format!("{}\t{}", task.id.get(..6).unwrap_or(&task.id), description)
+                })
+                .collect())
+        }
+        "contexts" => {
+            let mut names: Vec<String> = manager.contexts.keys().cloned().collect();
+            names.sort();
+            Ok(names)
+        }
+        _ => Err(AppError::InvalidRequest(format!(
+            "unknown completion kind '{}' (expected ids or contexts)",
+            kind
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Priority, Task, TimeHorizon};
+
+    #[test]
+    fn test_scripts_wrap_generated_completion() {
+        for shell in [CompletionShell::Bash, CompletionShell::Zsh] {
+            let script = script(shell);
+            // The generated function was renamed, and the wrapper calls it
+            assert!(script.contains("_todo_static() {"), "{:?}", shell);
+            assert!(script.contains("_todo_static \"$@\""), "{:?}", shell);
+            assert_eq!(script.matches("\n_todo() {").count(), 1, "{:?}", shell);
+            assert!(
+                script.contains("env TODO_COMPLETE=contexts todo"),
+                "{:?}",
+                shell
+            );
+        }
+
+        let fish = script(CompletionShell::Fish);
+        assert!(fish.contains("function __fish_todo_using_subcommand"));
+        assert!(fish.contains("env TODO_COMPLETE=ids todo"));
+    }
+
+    #[test]
+    fn test_candidates() {
+        let mut manager = ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+        let mut done = Task::new("Done".to_string(), TimeHorizon::ShortTerm, Priority::Low);
+        done.mark_complete();
+        let open = Task::new(
+            "Open\tnow".to_string(),
+            TimeHorizon::ShortTerm,
+            Priority::Low,
+        );
+        let open_id = open.id[..6].to_string();
+        manager.active_context_mut().add_task(done);
+        manager.active_context_mut().add_task(open);
+
+        let ids = candidates(&manager, "ids").unwrap();
+        assert_eq!(ids[0], format!("{}\tOpen now", open_id));
+        assert_eq!(ids.len(), 2);
+
+        assert_eq!(
+            candidates(&manager, "contexts").unwrap(),
+            ["default", "work"]
+        );
+        assert!(candidates(&manager, "flags").is_err());
+    }
+}
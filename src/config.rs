@@ -0,0 +1,36 @@
+// Config module - user settings read from config.json
+// This module demonstrates optional, partially written configuration with
+// #[serde(default)]
+//
+// Settings live in `config.json` next to the data file. The file is optional
+// and is never written by the application; every section and field in it is
+// optional too, falling back to its default:
+//
+// ```json
+// {
+//   "urgency": { "priority_high": 8.0, "blocked_tag": "waiting" }
+// }
+// ```
+//
+// Sections:
+// - `urgency`: weights of the urgency score (see the urgency module)
+// - `aging`: maximum ages per horizon, and whether to promote older tasks
+//   (see the aging module)
+
+use crate::aging::AgingRules;
+use crate::urgency::UrgencyWeights;
+use serde::{Deserialize, Serialize};
+
+/// Name of the config file, kept next to the data file
+pub const CONFIG_FILE: &str = "config.json";
+
+/// All user settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Weights of the urgency score used by `todo next` and `--sort urgency`
+    pub urgency: UrgencyWeights,
+
+    /// Rules for tasks that have been in one horizon too long
+    pub aging: AgingRules,
+}
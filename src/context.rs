@@ -1,7 +1,9 @@
 // Context module - manages project contexts and their associated tasks
 // This module demonstrates Rust's HashMap usage, borrowing patterns, and error handling
 
-use crate::task::Task;
+use crate::filter::Filter;
+use crate::sort::{MoveDirection, SortOrder};
+use crate::task::{Task, TimeHorizon};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -30,6 +32,7 @@ use std::collections::HashMap;
 ///
 /// - `name`: The context's name (e.g., "work", "personal", "learning")
 /// - `tasks`: A vector of tasks belonging to this context
+/// - `tombstones`: Records of tasks deleted from this context, used by sync
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Context {
     /// The name of this context
@@ -37,6 +40,24 @@ pub struct Context {
 
     /// The tasks belonging to this context
     pub tasks: Vec<Task>,
+
+    /// Tasks deleted from this context
+    ///
+    /// Without these, sync could not tell a task deleted here from a task
+    /// newly added on the other side. Skipped on disk when empty, so files
+    /// without deletions keep their old shape.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tombstones: Vec<Tombstone>,
+}
+
+/// Marker left behind when a task is deleted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tombstone {
+    /// ID of the deleted task
+    pub id: String,
+
+    /// ISO 8601 timestamp of the deletion
+    pub deleted_at: String,
 }
 
 impl Context {
@@ -68,6 +89,7 @@ impl Context {
         Self {
             name,
             tasks: Vec::new(), // Create an empty vector for tasks
+            tombstones: Vec::new(),
         }
     }
 
@@ -200,7 +222,7 @@ impl Context {
     /// }
     ///
     /// let found = context.find_task(&task_id);
-    /// assert_eq!(found.unwrap().completed, true);
+    /// assert!(found.unwrap().completed);
     /// ```
     pub fn find_task_mut(&mut self, id: &str) -> Option<&mut Task> {
         // Use iter_mut() instead of iter() to get mutable references
@@ -208,6 +230,55 @@ impl Context {
         self.tasks.iter_mut().find(|task| task.id == id)
     }
 
+    /// Resolves a partial task ID (a prefix, e.g. the 6-character short ID)
+    /// to the full ID
+    ///
+    /// This is how every command that takes an ID finds its task, so users
+    /// never have to type a whole UUID.
+    ///
+    /// # Arguments
+    ///
+    /// * `partial_id` - The ID prefix to match
+    ///
+    /// # Returns
+    ///
+    /// The full task ID, or Err(AppError::TaskNotFound) if no task or more
+    /// than one task matches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::context::Context;
+    /// use rust_todo::task::{Task, TimeHorizon, Priority};
+    ///
+    /// let mut context = Context::new("work".to_string());
+    /// let task = Task::new("Test".to_string(), TimeHorizon::ShortTerm, Priority::Medium);
+    /// let task_id = task.id.clone();
+    /// context.add_task(task);
+    ///
+    /// assert_eq!(context.resolve_id(&task_id[..6]).unwrap(), task_id);
+    /// assert!(context.resolve_id("zzz").is_err());
+    /// ```
+    pub fn resolve_id(&self, partial_id: &str) -> crate::error::Result<String> {
+        // Find all tasks whose ID starts with the partial ID
+        let matches: Vec<&str> = self
+            .tasks
+            .iter()
+            .filter(|task| task.id.starts_with(partial_id))
+            .map(|task| task.id.as_str())
+            .collect();
+
+        match matches.as_slice() {
+            [] => Err(crate::error::AppError::TaskNotFound(partial_id.to_string())),
+            [id] => Ok(id.to_string()),
+            _ => Err(crate::error::AppError::TaskNotFound(format!(
+                "Ambiguous ID '{}' matches multiple tasks: {}",
+                partial_id,
+                matches.join(", ")
+            ))),
+        }
+    }
+
     /// Removes a task by ID and returns it
     ///
     /// This method demonstrates:
@@ -273,6 +344,35 @@ impl Context {
         }
     }
 
+    /// Deletes a task by ID, leaving a tombstone so sync can propagate it
+    ///
+    /// Use this for deletions the user asked for. remove_task() is for moving a
+    /// task elsewhere, where no tombstone should be left behind.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::context::Context;
+    /// use rust_todo::task::{Task, TimeHorizon, Priority};
+    ///
+    /// let mut context = Context::new("work".to_string());
+    /// let task = Task::new("Test".to_string(), TimeHorizon::ShortTerm, Priority::Medium);
+    /// let task_id = task.id.clone();
+    /// context.add_task(task);
+    ///
+    /// context.delete_task(&task_id).unwrap();
+    /// assert!(context.tasks.is_empty());
+    /// assert_eq!(context.tombstones[0].id, task_id);
+    /// ```
+    pub fn delete_task(&mut self, id: &str) -> crate::error::Result<Task> {
+        let task = self.remove_task(id)?;
+        self.tombstones.push(Tombstone {
+            id: task.id.clone(),
+            deleted_at: chrono::Utc::now().to_rfc3339(),
+        });
+        Ok(task)
+    }
+
     /// Gets tasks filtered by time horizon
     ///
     /// This method demonstrates:
@@ -335,9 +435,9 @@ impl Context {
     /// Tasks are sorted by:
     /// 1. Time horizon (ShortTerm < MidTerm < LongTerm)
     /// 2. Priority within each horizon (High > Medium > Low, so we reverse)
+    /// 3. Manual order (see move_task()) for tasks of the same priority
     ///
-    /// We use tuple comparison: (horizon, -priority) where we reverse the priority
-    /// order by using Reverse wrapper or by comparing in reverse order.
+    /// This is sorted_tasks_by() with the default SortOrder.
     ///
     /// # Performance Note
     ///
@@ -368,22 +468,168 @@ impl Context {
     /// assert_eq!(sorted[2].description, "Long High");
     /// ```
     pub fn sorted_tasks(&self) -> Vec<&Task> {
-        // Create a Vec of references to all tasks
-        let mut tasks: Vec<&Task> = self.tasks.iter().collect();
+        self.sorted_tasks_by(&SortOrder::default())
+    }
 
-        // Sort by time horizon first, then by priority (high to low) within each horizon
-        // We use sort_by_key with a tuple for multi-level sorting
-        // For priority, we want high to low, so we reverse the comparison
-        tasks.sort_by_key(|task| {
-            // Create a tuple for comparison
-            // TimeHorizon variants are ordered: ShortTerm < MidTerm < LongTerm
-            // Priority is ordered: Low < Medium < High
-            // We want high priority first, so we use Reverse
-            (task.time_horizon, std::cmp::Reverse(task.priority))
-        });
+    /// Gets all tasks grouped by time horizon, sorted by `order` within each
+    ///
+    /// The stored order of the tasks is the manual order, so it breaks any
+    /// ties `order` leaves.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use rust_todo::context::Context;
+    /// use rust_todo::sort::SortOrder;
+    /// use rust_todo::task::{Task, TimeHorizon, Priority};
+    ///
+    /// let mut context = Context::new("work".to_string());
+    /// context.add_task(Task::new("b".to_string(), TimeHorizon::LongTerm, Priority::High));
+    /// context.add_task(Task::new("c".to_string(), TimeHorizon::ShortTerm, Priority::Low));
+    /// context.add_task(Task::new("a".to_string(), TimeHorizon::ShortTerm, Priority::High));
+    ///
+    /// let order = SortOrder::from_str("description").unwrap();
+    /// let sorted = context.sorted_tasks_by(&order);
+    /// // Short-term tasks still come first
+    /// assert_eq!(sorted[0].description, "a");
+    /// assert_eq!(sorted[1].description, "c");
+    /// assert_eq!(sorted[2].description, "b");
+    /// ```
+    pub fn sorted_tasks_by(&self, order: &SortOrder) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self.tasks.iter().collect();
+        order.sort(&mut tasks);
 
+        // Group by horizon; sort_by_key is stable, so each group keeps `order`
+        tasks.sort_by_key(|task| task.time_horizon);
         tasks
     }
+
+    /// Moves a task within the manual order of its horizon and priority
+    ///
+    /// The task only changes places with tasks of the same horizon, priority
+    /// and completion state, since those are the tasks `todo list` shows it
+    /// among (it sorts each horizon by priority, then by manual order): moving
+    /// an open task up passes the open task of the same priority before it,
+    /// not a hidden completed one or a higher priority one. All other tasks
+    /// keep their places.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The full ID of the task to move
+    /// * `direction` - Where to move it
+    ///
+    /// # Returns
+    ///
+    /// The task's new position among those tasks (0 is the top) and their
+    /// count. The position is unchanged when the task is already at the top
+    /// or bottom. Err(AppError::TaskNotFound) if there is no such task.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::context::Context;
+    /// use rust_todo::sort::MoveDirection;
+    /// use rust_todo::task::{Task, TimeHorizon, Priority};
+    ///
+    /// let mut context = Context::new("work".to_string());
+    /// for description in ["a", "b", "c"] {
+    ///     context.add_task(Task::new(description.to_string(), TimeHorizon::ShortTerm, Priority::Medium));
+    /// }
+    /// let id = context.tasks[2].id.clone();
+    ///
+    /// assert_eq!(context.move_task(&id, MoveDirection::Up).unwrap(), (1, 3));
+    /// assert_eq!(context.move_task(&id, MoveDirection::Top).unwrap(), (0, 3));
+    /// assert_eq!(context.tasks[0].description, "c");
+    /// ```
+    pub fn move_task(
+        &mut self,
+        id: &str,
+        direction: MoveDirection,
+    ) -> crate::error::Result<(usize, usize)> {
+        let task = self
+            .find_task(id)
+            .ok_or_else(|| crate::error::AppError::TaskNotFound(id.to_string()))?;
+        let (horizon, priority, completed) = (task.time_horizon, task.priority, task.completed);
+
+        // Indexes into self.tasks of the tasks this one is listed with
+        let slots: Vec<usize> = self
+            .tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, task)| {
+                task.time_horizon == horizon
+                    && task.priority == priority
+                    && task.completed == completed
+            })
+            .map(|(index, _)| index)
+            .collect();
+        let from = slots
+            .iter()
+            .position(|&index| self.tasks[index].id == id)
+            .expect("the task is one of its own slots");
+
+        let to = match direction {
+            MoveDirection::Up => from.saturating_sub(1),
+            MoveDirection::Down => (from + 1).min(slots.len() - 1),
+            MoveDirection::Top => 0,
+            MoveDirection::Bottom => slots.len() - 1,
+        };
+
+        // Rotate the task into its new slot; the tasks in between shift by
+        // one slot, and tasks outside the slots don't move at all
+        let mut group: Vec<Task> = slots
+            .iter()
+            .map(|&index| self.tasks[index].clone())
+            .collect();
+        let moved = group.remove(from);
+        group.insert(to, moved);
+        for (&index, task) in slots.iter().zip(group) {
+            self.tasks[index] = task;
+        }
+
+        Ok((to, slots.len()))
+    }
+}
+
+/// What `todo list` shows, parsed from its flags
+///
+/// The RPC `list` method selects tasks with this too, so both list the same
+/// tasks in the same order.
+pub struct ListOptions {
+    /// Whether to show completed tasks
+    pub show_all: bool,
+
+    /// Only show tasks of this time horizon
+    pub horizon: Option<TimeHorizon>,
+
+    /// Only show tasks matching this filter expression (`--where`)
+    pub filter: Option<Filter>,
+
+    /// Sort keys (`--sort`), used within each horizon unless `flat`
+    pub order: SortOrder,
+
+    /// One list for all contexts instead of groups by context and horizon
+    pub flat: bool,
+}
+
+impl ListOptions {
+    /// Selects the tasks of one context to list, grouped by horizon
+    pub fn select<'a>(&self, context: &'a Context) -> Vec<&'a Task> {
+        context
+            .sorted_tasks_by(&self.order)
+            .into_iter()
+            .filter(|task| self.show_all || !task.completed)
+            .filter(|task| match self.horizon {
+                Some(horizon) => task.time_horizon == horizon,
+                None => true,
+            })
+            .filter(|task| match &self.filter {
+                Some(filter) => filter.matches(task),
+                None => true,
+            })
+            .collect()
+    }
 }
 
 /// Manages all contexts and tracks the active one
@@ -421,7 +667,7 @@ impl Context {
 /// 1. There is always at least one context (the default context)
 /// 2. The active_context always refers to an existing context in the HashMap
 /// 3. Context names are unique (enforced by HashMap)
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextManager {
     /// All contexts, indexed by name
     pub contexts: HashMap<String, Context>,
@@ -1082,7 +1328,7 @@ mod tests {
         // Verify the task was modified
         let found = context.find_task(&task_id);
         assert!(found.is_some());
-        assert_eq!(found.unwrap().completed, true);
+        assert!(found.unwrap().completed);
     }
 
     #[test]
@@ -1294,6 +1540,90 @@ mod tests {
         assert_eq!(sorted[0].description, "Only task");
     }
 
+    #[test]
+    fn test_move_task_within_horizon() {
+        let mut context = Context::new("work".to_string());
+        for (description, horizon, priority) in [
+            ("short 1", TimeHorizon::ShortTerm, Priority::Medium),
+            ("long 1", TimeHorizon::LongTerm, Priority::Medium),
+            ("short 2", TimeHorizon::ShortTerm, Priority::Medium),
+            ("short done", TimeHorizon::ShortTerm, Priority::Medium),
+            ("short 3", TimeHorizon::ShortTerm, Priority::Medium),
+            ("short high", TimeHorizon::ShortTerm, Priority::High),
+        ] {
+            context.add_task(Task::new(description.to_string(), horizon, priority));
+        }
+        context.tasks[3].mark_complete();
+        let descriptions = |context: &Context| -> Vec<String> {
+            context
+                .tasks
+                .iter()
+                .map(|task| task.description.clone())
+                .collect()
+        };
+
+        // Up passes the open short-term task, not the completed one
+        let id = context.tasks[4].id.clone();
+        assert_eq!(context.move_task(&id, MoveDirection::Up).unwrap(), (1, 3));
+        assert_eq!(
+            descriptions(&context),
+            [
+                "short 1",
+                "long 1",
+                "short 3",
+                "short done",
+                "short 2",
+                "short high"
+            ]
+        );
+
+        // Already at the bottom: nothing changes
+        let id = context.tasks[4].id.clone();
+        assert_eq!(
+            context.move_task(&id, MoveDirection::Bottom).unwrap(),
+            (2, 3)
+        );
+
+        // To the top of its priority; the long-term and high priority
+        // tasks keep their places
+        assert_eq!(context.move_task(&id, MoveDirection::Top).unwrap(), (0, 3));
+        assert_eq!(
+            descriptions(&context),
+            [
+                "short 2",
+                "long 1",
+                "short 1",
+                "short done",
+                "short 3",
+                "short high"
+            ]
+        );
+
+        // The default listing shows it first among its priority, which is
+        // where the move said it would be
+        let sorted: Vec<&str> = context
+            .sorted_tasks()
+            .iter()
+            .map(|task| task.description.as_str())
+            .collect();
+        assert_eq!(
+            sorted,
+            [
+                "short high",
+                "short 2",
+                "short 1",
+                "short done",
+                "short 3",
+                "long 1"
+            ]
+        );
+
+        assert!(matches!(
+            context.move_task("missing", MoveDirection::Up),
+            Err(crate::error::AppError::TaskNotFound(_))
+        ));
+    }
+
     // ContextManager tests
 
     #[test]
@@ -1647,7 +1977,7 @@ mod tests {
         // Verify the task was modified
         let found = manager.active_context().find_task(&task_id);
         assert!(found.is_some());
-        assert_eq!(found.unwrap().completed, true);
+        assert!(found.unwrap().completed);
     }
 
     #[test]
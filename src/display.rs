@@ -14,7 +14,15 @@
 // - **Borrowing**: Working with references to avoid unnecessary cloning
 // - **Trait Usage**: Using Display-like patterns for formatting
 
-use crate::task::{Priority, Task, TimeHorizon};
+use crate::aging::{AgingAction, AgingReport};
+use crate::merge::{MergeChange, MergeReport};
+use crate::review::ReviewSummary;
+use crate::search::SearchHit;
+use crate::stats::{CompletionTime, Period, Stats};
+use crate::sync::{Side, SideChanges, SyncReport};
+use crate::task::{HistoryEntry, Priority, Task, TimeHorizon};
+use crate::urgency::Ranked;
+use crate::views::View;
 use colored::*;
 
 /// Formats a single task for compact display
@@ -62,6 +70,14 @@ use colored::*;
 /// // Output: "[ ] abc123 [HIGH] Write tests"
 /// ```
 pub fn format_task_line(task: &Task) -> String {
+    task_line(task, &task.description)
+}
+
+/// Formats a task line with an already formatted description
+///
+/// Shared by format_task_line() and format_search_hit(), which highlights
+/// parts of the description.
+fn task_line(task: &Task, description: &str) -> String {
     // Determine the checkbox symbol based on completion status
     // Pattern matching is Rust's way of handling different cases
     let checkbox = if task.completed {
@@ -85,12 +101,19 @@ pub fn format_task_line(task: &Task) -> String {
 
     // Combine all parts into a single formatted line
     // The format!() macro creates a new String with the interpolated values
+    // The due date, when there is one, goes after the description
+    let due = match task.due {
+        Some(due) => format!(" {}", format!("(due {})", due).yellow()),
+        None => String::new(),
+    };
+
     format!(
-        "{} {} {} {}",
+        "{} {} {} {}{}",
         checkbox,
         short_id.dimmed(),
         priority_str,
-        task.description
+        description,
+        due
     )
 }
 
@@ -98,7 +121,8 @@ pub fn format_task_line(task: &Task) -> String {
 ///
 /// This function organizes and displays tasks in a structured format:
 /// - Tasks are grouped by time horizon (short-term, mid-term, long-term)
-/// - Within each horizon, tasks are sorted by priority (high to low)
+/// - Within each horizon, tasks keep the order they are given in, so callers
+///   decide it (see Context::sorted_tasks_by())
 /// - Each group has a header with the horizon name
 /// - Completed tasks can be optionally filtered out
 ///
@@ -156,7 +180,7 @@ pub fn display_tasks(tasks: &[&Task], show_completed: bool) {
         // Filter tasks by this time horizon
         // The filter() method creates a new iterator that only yields matching elements
         // We use a closure |task| to define the filtering logic
-        let mut horizon_tasks: Vec<&Task> = tasks
+        let horizon_tasks: Vec<&Task> = tasks
             .iter()
             .filter(|task| task.time_horizon == *horizon)
             .filter(|task| show_completed || !task.completed)
@@ -168,11 +192,6 @@ pub fn display_tasks(tasks: &[&Task], show_completed: bool) {
             continue;
         }
 
-        // Sort tasks by priority (high to low) within this horizon
-        // sort_by_key() takes a closure that extracts the sort key from each element
-        // We use Reverse to sort in descending order (high priority first)
-        horizon_tasks.sort_by_key(|task| std::cmp::Reverse(task.priority));
-
         // Add spacing between groups (except before the first group)
         if displayed_any {
             println!(); // Print a blank line
@@ -197,6 +216,48 @@ pub fn display_tasks(tasks: &[&Task], show_completed: bool) {
     }
 }
 
+/// Displays the tasks of several contexts, grouped by context
+///
+/// Each context gets a header with its name, followed by its tasks grouped
+/// by time horizon as display_tasks() shows them.
+///
+/// # Arguments
+///
+/// * `groups` - Context names with their tasks, in display order
+/// * `show_completed` - Whether to include completed tasks in the display
+pub fn display_context_groups(groups: &[(&str, Vec<&Task>)], show_completed: bool) {
+    for (index, (name, tasks)) in groups.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        println!("{}", format!("@{}", name).cyan().bold().underline());
+        display_tasks(tasks, show_completed);
+    }
+}
+
+/// Displays tasks of one or more contexts as a single list
+///
+/// Unlike display_tasks(), nothing is grouped: tasks are shown in the order
+/// given, each with the name of its context.
+///
+/// # Arguments
+///
+/// * `tasks` - Context names with a task each, in display order
+pub fn display_flat_tasks(tasks: &[(&str, &Task)]) {
+    if tasks.is_empty() {
+        println!("{}", "No tasks to display.".dimmed());
+        return;
+    }
+
+    for (context, task) in tasks {
+        println!(
+            "  {}  {}",
+            format_task_line(task),
+            format!("@{}", context).cyan()
+        );
+    }
+}
+
 /// Displays detailed information about a single task
 ///
 /// This function shows all available information about a task:
@@ -266,6 +327,21 @@ pub fn display_task_detail(task: &Task) {
 
     // Print the creation timestamp
     println!("  {}: {}", "Created".bold(), task.created_at.dimmed());
+    if let Some(updated_at) = &task.updated_at {
+        println!("  {}: {}", "Updated".bold(), updated_at.dimmed());
+    }
+    if let Some(reviewed_at) = &task.reviewed_at {
+        println!("  {}: {}", "Reviewed".bold(), reviewed_at.dimmed());
+    }
+
+    if let Some(due) = task.due {
+        println!("  {}: {}", "Due".bold(), due.to_string().yellow());
+    }
+
+    // Print tags only when the task has some, to keep the common case compact
+    if !task.tags.is_empty() {
+        println!("  {}: {}", "Tags".bold(), task.tags.join(", ").cyan());
+    }
 }
 
 /// Displays a list of contexts with an indicator for the active one
@@ -325,6 +401,657 @@ pub fn display_contexts(contexts: &[&str], active: &str) {
     println!("{}", "  ● = active context".dimmed());
 }
 
+/// Displays the full timeline of a task, from creation to its latest change
+///
+/// The first line is always the creation, followed by each recorded change
+/// in order, described in words (e.g., "moved horizon", "completed").
+///
+/// # Arguments
+///
+/// * `task` - The task whose history to show
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::task::{Task, TimeHorizon, Priority};
+/// use rust_todo::display::display_task_history;
+///
+/// let mut task = Task::new("Write tests".to_string(), TimeHorizon::ShortTerm, Priority::High);
+/// task.mark_complete();
+/// display_task_history(&task);
+/// ```
+pub fn display_task_history(task: &Task) {
+    println!(
+        "{} [{}] {}",
+        "History:".bold().underline(),
+        short_id(&task.id).dimmed(),
+        task.description
+    );
+    println!();
+
+    // The values the task was created with are the `from` of the first
+    // change to each field, or the current value if it never changed
+    let original = |field: &str, current: String| {
+        task.history
+            .iter()
+            .find(|entry| entry.change.field == field)
+            .map_or(current, |entry| entry.change.from.clone())
+    };
+    println!(
+        "  {}  {} ({}, {})",
+        format_timestamp(&task.created_at).dimmed(),
+        "created".green(),
+        original("time_horizon", format!("{:?}", task.time_horizon)),
+        original("priority", format!("{:?}", task.priority))
+    );
+
+    for entry in &task.history {
+        println!(
+            "  {}  {}",
+            format_timestamp(&entry.at).dimmed(),
+            describe_history_entry(entry)
+        );
+    }
+}
+
+/// Describes a history entry in words, e.g. `moved horizon: ShortTerm → MidTerm`
+pub fn describe_history_entry(entry: &HistoryEntry) -> String {
+    let change = &entry.change;
+    match change.field.as_str() {
+        "completed" if change.to == "true" => "completed".green().to_string(),
+        "completed" => "reopened".yellow().to_string(),
+        field => {
+            let action = match field {
+                "description" => "edited description",
+                "time_horizon" => "moved horizon",
+                "priority" => "changed priority",
+                "tags" => "changed tags",
+                "due" => "changed due date",
+                other => other,
+            };
+            format!("{}: {} → {}", action, change.from, change.to)
+        }
+    }
+}
+
+/// Formats an RFC 3339 timestamp as local "YYYY-MM-DD HH:MM", or returns it unchanged
+fn format_timestamp(timestamp: &str) -> String {
+    match chrono::DateTime::parse_from_rfc3339(timestamp) {
+        Ok(time) => time
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string(),
+        Err(_) => timestamp.to_string(),
+    }
+}
+
+/// Displays saved views: name, filter, sort key and scope
+///
+/// # Arguments
+///
+/// * `views` - The views to show, as returned by Views::visible()
+pub fn display_views(views: &[&View]) {
+    if views.is_empty() {
+        println!("{}", "No saved views.".dimmed());
+        return;
+    }
+
+    // Pad names so the filters line up
+    let width = views.iter().map(|view| view.name.len()).max().unwrap_or(0);
+    for view in views {
+        let sort = match &view.sort {
+            Some(sort) => format!("  sort: {}", sort),
+            None => String::new(),
+        };
+        let scope = match &view.context {
+            Some(context) => format!("  @{}", context).cyan().to_string(),
+            None => String::new(),
+        };
+        println!(
+            "  {:<width$}  {}{}{}",
+            view.name.bold(),
+            view.filter,
+            sort.dimmed(),
+            scope,
+            width = width
+        );
+    }
+}
+
+/// Formats one search result: the task line with the matched parts of the
+/// description highlighted, followed by the task's context
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::context::ContextManager;
+/// use rust_todo::display::format_search_hit;
+/// use rust_todo::search::search;
+/// use rust_todo::task::{Priority, Task, TimeHorizon};
+///
+/// let mut manager = ContextManager::new();
+/// manager.active_context_mut().add_task(Task::new(
+///     "Write API docs".to_string(),
+///     TimeHorizon::ShortTerm,
+///     Priority::High,
+/// ));
+///
+/// let hits = search(&manager, "api");
+/// let line = format_search_hit(&hits[0]);
+/// assert!(line.contains("API"));
+/// assert!(line.contains("default"));
+/// ```
+pub fn format_search_hit(hit: &SearchHit) -> String {
+    let description = &hit.task.description;
+    let mut highlighted = String::new();
+    let mut end = 0;
+
+    // Copy the text between matches as is, and the matches in colour
+    for range in &hit.highlights {
+        highlighted.push_str(&description[end..range.start]);
+        highlighted.push_str(&description[range.clone()].yellow().bold().to_string());
+        end = range.end;
+    }
+    highlighted.push_str(&description[end..]);
+
+    format!(
+        "{}  {}",
+        task_line(hit.task, &highlighted),
+        format!("@{}", hit.context).cyan()
+    )
+}
+
+/// Displays search results, best first
+///
+/// # Arguments
+///
+/// * `hits` - The results, as returned by search()
+/// * `query` - What was searched for, for the summary line
+pub fn display_search_results(hits: &[SearchHit], query: &str) {
+    if hits.is_empty() {
+        println!("{}", format!("No tasks match \"{}\".", query).dimmed());
+        return;
+    }
+
+    for hit in hits {
+        println!("  {}", format_search_hit(hit));
+    }
+
+    println!();
+    let noun = if hits.len() == 1 { "result" } else { "results" };
+    println!("{} {} {} for \"{}\"", "ℹ".cyan(), hits.len(), noun, query);
+}
+
+/// Displays the tasks ranked by `todo next`, each with its score breakdown
+///
+/// # Arguments
+///
+/// * `ranked` - The tasks to show, most urgent first
+/// * `open_count` - How many open tasks were ranked, for the summary line
+pub fn display_ranked(ranked: &[Ranked], open_count: usize) {
+    if ranked.is_empty() {
+        println!("{}", "No open tasks.".dimmed());
+        return;
+    }
+
+    for (index, entry) in ranked.iter().enumerate() {
+        let urgency = entry.urgency;
+        println!(
+            "{:>3}. {}  {}  {}",
+            index + 1,
+            format_task_line(entry.task),
+            format!("@{}", entry.context).cyan(),
+            format!("{:.1}", urgency.total()).bold()
+        );
+
+        // Only the factors that contribute anything
+        let factors: Vec<String> = [
+            ("priority", urgency.priority),
+            ("horizon", urgency.horizon),
+            ("age", urgency.age),
+            ("due", urgency.due),
+            ("blocked", urgency.blocked),
+        ]
+        .iter()
+        .filter(|(_, value)| *value != 0.0)
+        .map(|(name, value)| format!("{} {:+.1}", name, value))
+        .collect();
+        println!("     {}", factors.join("  ").dimmed());
+    }
+
+    println!();
+    println!(
+        "{} {} of {} open tasks, most urgent first",
+        "ℹ".cyan(),
+        ranked.len(),
+        open_count
+    );
+}
+
+/// Displays the tasks found by the aging rules and what happened to them
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::aging::AgingReport;
+/// use rust_todo::display::display_aging_report;
+///
+/// display_aging_report(&AgingReport::default(), false);
+/// ```
+pub fn display_aging_report(report: &AgingReport, dry_run: bool) {
+    if report.tasks.is_empty() {
+        println!(
+            "{}",
+            "No tasks have been in their horizon longer than allowed.".dimmed()
+        );
+        return;
+    }
+
+    let horizon_name = |horizon: TimeHorizon| match horizon {
+        TimeHorizon::ShortTerm => "short-term",
+        TimeHorizon::MidTerm => "mid-term",
+        TimeHorizon::LongTerm => "long-term",
+    };
+
+    for aged in &report.tasks {
+        let outcome = match aged.action {
+            AgingAction::Flagged => "flagged".yellow().to_string(),
+            AgingAction::Promoted(to) if dry_run => format!("would move to {}", horizon_name(to))
+                .cyan()
+                .to_string(),
+            AgingAction::Promoted(to) => {
+                format!("moved to {}", horizon_name(to)).green().to_string()
+            }
+        };
+        println!(
+            "  [{}] {}  {}",
+            short_id(&aged.id).dimmed(),
+            aged.description,
+            format!("@{}", aged.context).cyan()
+        );
+        println!(
+            "     {} days in {} (limit {}) → {}",
+            aged.days,
+            horizon_name(aged.horizon),
+            aged.limit,
+            outcome
+        );
+    }
+
+    println!();
+    let flagged = report.tasks.len() - report.promoted();
+    let verb = if dry_run {
+        "would be promoted"
+    } else {
+        "promoted"
+    };
+    println!(
+        "{} {} flagged, {} {}{}",
+        "ℹ".cyan(),
+        flagged,
+        report.promoted(),
+        verb,
+        if dry_run {
+            " (dry run, nothing changed)"
+        } else {
+            ""
+        }
+    );
+}
+
+/// Width of the longest bar drawn by display_stats()
+const STATS_BAR_WIDTH: usize = 30;
+
+/// An ASCII bar of `value` out of `max`, at least one character unless zero
+fn ascii_bar(value: f64, max: f64) -> String {
+    if value <= 0.0 || max <= 0.0 {
+        return String::new();
+    }
+    let length = ((value / max) * STATS_BAR_WIDTH as f64).round().max(1.0) as usize;
+    "#".repeat(length)
+}
+
+/// Displays the statistics computed by `todo stats` as ASCII bar charts
+///
+/// # Example
+///
+/// ```
+/// use chrono::Utc;
+/// use rust_todo::context::ContextManager;
+/// use rust_todo::display::display_stats;
+/// use rust_todo::stats::{compute, Period};
+///
+/// display_stats(&compute(&ContextManager::new(), Period::Week, 4, 5, Utc::now()));
+/// ```
+pub fn display_stats(stats: &Stats) {
+    let heading = |text: &str| println!("{}", text.bold().underline());
+
+    heading(&format!("Created vs completed per {}", stats.period));
+    let busiest = stats
+        .activity
+        .iter()
+        .map(|activity| activity.created.max(activity.completed))
+        .max()
+        .unwrap_or(0) as f64;
+    for activity in &stats.activity {
+        let start = match stats.period {
+            Period::Day => activity.start.format("%a %Y-%m-%d").to_string(),
+            Period::Week => activity.start.format("%Y-%m-%d").to_string(),
+        };
+        println!(
+            "  {}  {:<9} {:<width$} {}",
+            start.dimmed(),
+            "created",
+            ascii_bar(activity.created as f64, busiest).cyan(),
+            activity.created,
+            width = STATS_BAR_WIDTH
+        );
+        println!(
+            "  {}  {:<9} {:<width$} {}",
+            " ".repeat(start.chars().count()),
+            "completed",
+            ascii_bar(activity.completed as f64, busiest).green(),
+            activity.completed,
+            width = STATS_BAR_WIDTH
+        );
+    }
+
+    println!();
+    heading("Average time to completion");
+    let slowest = stats
+        .by_horizon
+        .iter()
+        .filter_map(|group| group.average_days)
+        .fold(0.0, f64::max);
+    let horizon_names = ["Short-term", "Mid-term", "Long-term"];
+    for (group, name) in stats.by_horizon.iter().zip(horizon_names) {
+        completion_line(name, group, slowest);
+    }
+    let slowest = stats
+        .by_priority
+        .iter()
+        .filter_map(|group| group.average_days)
+        .fold(0.0, f64::max);
+    for (group, name) in stats.by_priority.iter().zip(["High", "Medium", "Low"]) {
+        completion_line(name, group, slowest);
+    }
+
+    println!();
+    heading("Tasks per context");
+    let largest = stats
+        .contexts
+        .iter()
+        .map(|counts| counts.open)
+        .max()
+        .unwrap_or(0) as f64;
+    let name_width = stats
+        .contexts
+        .iter()
+        .map(|counts| counts.context.chars().count())
+        .max()
+        .unwrap_or(0);
+    for counts in &stats.contexts {
+        println!(
+            "  {:<name_width$}  {:<width$} {} open, {} completed",
+            counts.context.cyan(),
+            ascii_bar(counts.open as f64, largest).yellow(),
+            counts.open,
+            counts.completed,
+            width = STATS_BAR_WIDTH
+        );
+    }
+
+    println!();
+    heading("Oldest open tasks");
+    if stats.oldest.is_empty() {
+        println!("  {}", "No open tasks.".dimmed());
+    }
+    for task in &stats.oldest {
+        println!(
+            "  [{}] {}  {}  {}",
+            short_id(&task.id).dimmed(),
+            task.description,
+            format!("@{}", task.context).cyan(),
+            format!("{} days", task.age_days).dimmed()
+        );
+    }
+}
+
+/// One line of the completion time chart
+fn completion_line<G>(name: &str, group: &CompletionTime<G>, slowest: f64) {
+    let Some(days) = group.average_days else {
+        println!("  {:<10}  {}", name, "no completed tasks".dimmed());
+        return;
+    };
+    let noun = if group.tasks == 1 { "task" } else { "tasks" };
+    println!(
+        "  {:<10}  {:<width$} {:.1} days {}",
+        name,
+        ascii_bar(days, slowest).magenta(),
+        days,
+        format!("({} {})", group.tasks, noun).dimmed(),
+        width = STATS_BAR_WIDTH
+    );
+}
+
+/// Displays a chart drawn by the chart module under a heading
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::display::display_chart;
+///
+/// display_chart("Completed tasks per week", &["2026-10-12 │█ 1".to_string()]);
+/// ```
+pub fn display_chart(title: &str, lines: &[String]) {
+    println!("{}", title.bold().underline());
+    for line in lines {
+        println!("{}", line.cyan());
+    }
+}
+
+/// Displays the summary printed at the end of `todo review`
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::display::display_review_summary;
+/// use rust_todo::review::ReviewSummary;
+///
+/// display_review_summary("work", &ReviewSummary::default());
+/// ```
+pub fn display_review_summary(context: &str, summary: &ReviewSummary) {
+    if summary.reviewed() == 0 && summary.remaining == 0 {
+        println!("{}", "No open tasks to review.".dimmed());
+        return;
+    }
+
+    println!(
+        "{} Reviewed {} of {} open tasks in {}",
+        "✓".green().bold(),
+        summary.reviewed(),
+        summary.reviewed() + summary.remaining,
+        context.cyan()
+    );
+
+    // Only the answers that were given
+    let counts: Vec<String> = [
+        ("kept", summary.kept),
+        ("completed", summary.completed),
+        ("deleted", summary.deleted),
+        ("rescheduled", summary.rescheduled),
+        ("reprioritized", summary.reprioritized),
+        ("deferred", summary.deferred),
+        ("skipped", summary.skipped),
+    ]
+    .iter()
+    .filter(|(_, count)| *count > 0)
+    .map(|(name, count)| format!("{} {}", count, name))
+    .collect();
+    if !counts.is_empty() {
+        println!("  {}", counts.join(" · "));
+    }
+    if summary.remaining > 0 {
+        println!(
+            "  {}",
+            format!("{} left for next time", summary.remaining).dimmed()
+        );
+    }
+}
+
+/// Displays the changes made by an import merge as a diff
+///
+/// Each line starts with a marker, similar to a unified diff:
+/// - `+` a context or task that is added
+/// - `>` a clashing context that is added under a new name
+/// - `~` a task that takes imported values (local → imported)
+/// - `!` a conflict where the local value is kept (local ≠ imported)
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::display::display_merge_report;
+/// use rust_todo::merge::{MergeChange, MergeReport};
+///
+/// let report = MergeReport {
+///     changes: vec![MergeChange::AddContext { context: "work".to_string() }],
+/// };
+/// display_merge_report(&report);
+/// ```
+pub fn display_merge_report(report: &MergeReport) {
+    if report.is_empty() {
+        println!("{}", "  No changes.".dimmed());
+        return;
+    }
+
+    for change in &report.changes {
+        match change {
+            MergeChange::AddContext { context } => {
+                println!("{} context {}", "+".green().bold(), context.cyan());
+            }
+            MergeChange::RenameContext {
+                context,
+                renamed_to,
+                tasks,
+            } => {
+                println!(
+                    "{} context {} → {} ({} tasks, name conflict)",
+                    ">".yellow().bold(),
+                    context.dimmed(),
+                    renamed_to.cyan(),
+                    tasks
+                );
+            }
+            MergeChange::AddTask {
+                context,
+                id,
+                description,
+            } => {
+                println!(
+                    "{} [{}] {}: {}",
+                    "+".green().bold(),
+                    short_id(id).dimmed(),
+                    context.cyan(),
+                    description
+                );
+            }
+            MergeChange::UpdateTask {
+                context,
+                id,
+                fields,
+            } => {
+                println!(
+                    "{} [{}] {}",
+                    "~".yellow().bold(),
+                    short_id(id).dimmed(),
+                    context.cyan()
+                );
+                for field in fields {
+                    println!(
+                        "    {}: {} → {}",
+                        field.field,
+                        field.from.red(),
+                        field.to.green()
+                    );
+                }
+            }
+            MergeChange::Conflict {
+                context,
+                id,
+                fields,
+            } => {
+                println!(
+                    "{} [{}] {} (kept local)",
+                    "!".red().bold(),
+                    short_id(id).dimmed(),
+                    context.cyan()
+                );
+                for field in fields {
+                    println!(
+                        "    {}: {} ≠ {}",
+                        field.field,
+                        field.from.green(),
+                        field.to.dimmed()
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Displays what a sync changed on each side and how conflicts were resolved
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::display::display_sync_report;
+/// use rust_todo::sync::SyncReport;
+///
+/// display_sync_report(&SyncReport::default());
+/// ```
+pub fn display_sync_report(report: &SyncReport) {
+    let describe = |changes: &SideChanges| {
+        if changes.is_empty() {
+            "up to date".dimmed().to_string()
+        } else {
+            format!(
+                "{} added, {} updated, {} deleted",
+                changes.added, changes.updated, changes.deleted
+            )
+        }
+    };
+    println!("  {}:  {}", "Local".bold(), describe(&report.local));
+    println!("  {}: {}", "Remote".bold(), describe(&report.remote));
+
+    if report.conflicts.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("{}", "Conflicts:".bold().underline());
+    for resolved in &report.conflicts {
+        let conflict = &resolved.conflict;
+        let (kept, dropped, side) = match resolved.kept {
+            Side::Local => (&conflict.local, &conflict.remote, "local"),
+            Side::Remote => (&conflict.remote, &conflict.local, "remote"),
+        };
+        println!(
+            "  {} [{}] {}: kept {} {} over {}",
+            "!".red().bold(),
+            short_id(&conflict.id).dimmed(),
+            conflict.field,
+            side,
+            kept.green(),
+            dropped.dimmed()
+        );
+    }
+}
+
+/// Returns the first 6 characters of a task ID, as shown in task lists
+fn short_id(id: &str) -> &str {
+    id.get(..6).unwrap_or(id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -519,4 +1246,19 @@ mod tests {
         // This should not panic and should highlight "work" as active
         display_contexts(&contexts, "work");
     }
+
+    #[test]
+    fn test_describe_history_entry() {
+        let mut task = Task::new("Plan".to_string(), TimeHorizon::ShortTerm, Priority::Low);
+        task.update(None, Some(TimeHorizon::LongTerm), None, None);
+        task.mark_complete();
+
+        let moved = describe_history_entry(&task.history[0]);
+        assert!(moved.contains("moved horizon: ShortTerm → LongTerm"));
+        assert!(describe_history_entry(&task.history[1]).contains("completed"));
+
+        let mut reopened = task.history[1].clone();
+        reopened.change.to = "false".to_string();
+        assert!(describe_history_entry(&reopened).contains("reopened"));
+    }
 }
@@ -29,6 +29,11 @@ pub enum AppError {
     #[error("Context not found: {0}")]
     ContextNotFound(String),
 
+    /// Error when no saved view with the specified name is visible
+    /// (neither global nor saved for the active context)
+    #[error("View not found: {0}")]
+    ViewNotFound(String),
+
     /// Error when attempting to create a context that already exists
     #[error("Context already exists: {0}")]
     ContextAlreadyExists(String),
@@ -43,6 +48,80 @@ pub enum AppError {
     #[error("Invalid priority: {0}")]
     InvalidPriority(String),
 
+    /// Error when an invalid import/export file format is provided
+    /// Valid values are: native, taskwarrior
+    #[error("Invalid file format: {0}")]
+    InvalidFileFormat(String),
+
+    /// Error when an invalid --output format is provided
+    /// Valid values are: text, json, ndjson
+    #[error("Invalid output format: {0}")]
+    InvalidOutputFormat(String),
+
+    /// Error when an invalid import --strategy is provided
+    /// Valid values are: rename, union, theirs, ours, newest
+    #[error("Invalid merge strategy: {0}")]
+    InvalidMergeStrategy(String),
+
+    /// Error when an invalid sync --resolve policy is provided
+    /// Valid values are: ask, ours, theirs, newest
+    #[error("Invalid sync policy: {0}")]
+    InvalidSyncPolicy(String),
+
+    /// Error when an unsupported shell is given to `completions`
+    /// Valid values are: bash, zsh, fish
+    #[error("Invalid shell: {0}")]
+    InvalidShell(String),
+
+    /// Error when a `--where` filter expression can't be parsed
+    /// The message says what is wrong and at which column (see the filter module)
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(String),
+
+    /// Error when an unknown sort key is given
+    /// Valid keys are: priority, horizon, created, updated, description,
+    /// urgency, due, manual, each optionally prefixed with `-` (see SortKey's FromStr)
+    #[error("Invalid sort key: {0}")]
+    InvalidSortKey(String),
+
+    /// Error when an invalid `move` direction is given
+    /// Valid values are: up, down, top, bottom
+    #[error("Invalid move direction: {0}")]
+    InvalidMoveDirection(String),
+
+    /// Error when an answer during `todo review` isn't understood
+    /// Valid answers are: k, c, d, h <horizon>, p <priority>, f, s
+    #[error("Invalid review answer: {0}")]
+    InvalidReviewAction(String),
+
+    /// Error when an unknown statistics period is given
+    /// Valid values are: day, week
+    #[error("Invalid period: {0}")]
+    InvalidPeriod(String),
+
+    /// Error when a date isn't a YYYY-MM-DD date in the past
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+
+    /// Error when a due date can't be parsed
+    /// Valid values are: YYYY-MM-DD, today, tomorrow (and none, to remove it)
+    #[error("Invalid due date: {0}")]
+    InvalidDueDate(String),
+
+    /// Error when a sync conflict could not be resolved (e.g., nobody to ask)
+    /// Nothing is written when this happens
+    #[error("Unresolved sync conflict: {0}")]
+    SyncConflict(String),
+
+    /// Error from a git command in git-backed storage mode
+    /// Contains git's own error output
+    #[error("Git error: {0}")]
+    GitError(String),
+
+    /// Error when an API request is malformed (bad JSON body, unknown route)
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
     /// Error when attempting to delete the last remaining context
     /// At least one context must always exist
     #[error("Cannot delete the last context")]
@@ -64,6 +143,52 @@ pub enum AppError {
     InvalidDataFormat(String),
 }
 
+impl AppError {
+    /// Returns a stable, machine-readable code for this error
+    ///
+    /// The codes are part of the JSON output interface (see the output module),
+    /// so scripts can branch on them instead of parsing messages. Once
+    /// published, a code must never change meaning.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::error::AppError;
+    ///
+    /// let error = AppError::ContextNotFound("work".to_string());
+    /// assert_eq!(error.code(), "context_not_found");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::TaskNotFound(_) => "task_not_found",
+            AppError::ContextNotFound(_) => "context_not_found",
+            AppError::ViewNotFound(_) => "view_not_found",
+            AppError::ContextAlreadyExists(_) => "context_already_exists",
+            AppError::InvalidTimeHorizon(_) => "invalid_time_horizon",
+            AppError::InvalidPriority(_) => "invalid_priority",
+            AppError::InvalidFileFormat(_) => "invalid_file_format",
+            AppError::InvalidOutputFormat(_) => "invalid_output_format",
+            AppError::InvalidMergeStrategy(_) => "invalid_merge_strategy",
+            AppError::InvalidSyncPolicy(_) => "invalid_sync_policy",
+            AppError::InvalidShell(_) => "invalid_shell",
+            AppError::InvalidFilter(_) => "invalid_filter",
+            AppError::InvalidSortKey(_) => "invalid_sort_key",
+            AppError::InvalidMoveDirection(_) => "invalid_move_direction",
+            AppError::InvalidReviewAction(_) => "invalid_review_action",
+            AppError::InvalidPeriod(_) => "invalid_period",
+            AppError::InvalidDate(_) => "invalid_date",
+            AppError::InvalidDueDate(_) => "invalid_due_date",
+            AppError::SyncConflict(_) => "sync_conflict",
+            AppError::GitError(_) => "git_error",
+            AppError::InvalidRequest(_) => "invalid_request",
+            AppError::CannotDeleteLastContext => "cannot_delete_last_context",
+            AppError::IoError(_) => "io_error",
+            AppError::JsonError(_) => "json_error",
+            AppError::InvalidDataFormat(_) => "invalid_data_format",
+        }
+    }
+}
+
 /// Type alias for Result with our custom error type
 ///
 /// This is a common Rust pattern that makes function signatures more concise.
@@ -0,0 +1,853 @@
+// Filter module - a small query language for selecting tasks
+// This module demonstrates a hand-written tokenizer and recursive descent parser,
+// and evaluating a typed syntax tree
+//
+// `list`, `export` and the bulk commands (`complete`, `edit`, `delete`) take
+// a filter expression with `--where`:
+//
+// ```bash
+// todo list --where 'priority>=medium and not completed and (desc~"api" or horizon:short) and created>2026-01-01'
+// todo complete --where "horizon:short priority:low"
+// todo edit --where tag:sprint12 -t mid
+// ```
+//
+// # Syntax
+//
+// A filter is made of conditions `field op value`, combined with `and`, `or`,
+// `not` and parentheses. `not` binds tightest, then `and`, then `or`.
+// Conditions written next to each other are joined with `and`, so
+// `horizon:short priority:low` means `horizon:short and priority:low`.
+//
+// | Field                      | Operators                  | Values                         |
+// |----------------------------|----------------------------|--------------------------------|
+// | `priority`                 | `: = != < <= > >=`         | low, medium, high              |
+// | `horizon`                  | `: = != < <= > >=`         | short, mid, long               |
+// | `created`, `updated`       | `: = != < <= > >=`         | 2026-01-01, today, yesterday   |
+// | `desc` (`description`)     | `: = != ~ !~`              | text                           |
+// | `tag`                      | `: = != ~ !~`              | text                           |
+// | `completed`                | `: = !=`                   | true, false                    |
+// | `status`                   | `: = !=`                   | open, done                     |
+// | `id`                       | `: = !=`                   | an ID prefix                   |
+//
+// - `:` and `=` mean equal; `~` means "contains" and `!~` "doesn't contain".
+//   Text comparisons ignore case.
+// - `tag:x` matches tasks that have the tag x; `tag!=x` tasks that don't.
+// - Priorities and horizons are ordered: `low < medium < high` and
+//   `short < mid < long`. Values accept the same spellings as the flags
+//   (`priority:h`, `horizon:short-term`).
+// - Dates compare by day, in local time. `updated` is the time of the last
+//   change, or the creation time for tasks that never changed.
+// - `completed`, `done` and `open` on their own are shorthands for
+//   `completed:true`, `completed:true` and `completed:false`.
+// - Values containing spaces or parentheses are quoted: `desc~"write docs"`.
+//
+// Parsing checks that each field exists, that the operator makes sense for
+// it and that the value has the right type, so a filter that parses can't
+// fail while tasks are being matched. Errors point at the column where the
+// problem is.
+
+use crate::error::{AppError, Result};
+use crate::task::{Priority, Task, TimeHorizon};
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use std::str::FromStr;
+
+/// Field names, for error messages
+const FIELDS: [&str; 9] = [
+    "priority",
+    "horizon",
+    "created",
+    "updated",
+    "desc",
+    "tag",
+    "completed",
+    "status",
+    "id",
+];
+
+/// A comparison operator as written in the filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    NotContains,
+}
+
+impl Op {
+    fn symbol(self) -> &'static str {
+        match self {
+            Op::Eq => ":",
+            Op::Ne => "!=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Contains => "~",
+            Op::NotContains => "!~",
+        }
+    }
+}
+
+/// Operators for ordered values: priorities, horizons, dates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrdOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl OrdOp {
+    fn compare<T: Ord>(self, left: T, right: T) -> bool {
+        match self {
+            OrdOp::Eq => left == right,
+            OrdOp::Ne => left != right,
+            OrdOp::Lt => left < right,
+            OrdOp::Le => left <= right,
+            OrdOp::Gt => left > right,
+            OrdOp::Ge => left >= right,
+        }
+    }
+}
+
+/// Operators for text: descriptions and tags
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextOp {
+    Eq,
+    Ne,
+    Contains,
+    NotContains,
+}
+
+/// One typed condition on a task field
+///
+/// Text values are stored lowercased, since text comparisons ignore case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    Priority(OrdOp, Priority),
+    Horizon(OrdOp, TimeHorizon),
+    Created(OrdOp, NaiveDate),
+    Updated(OrdOp, NaiveDate),
+    Description(TextOp, String),
+    Tag(TextOp, String),
+    Completed(bool),
+    IdPrefix(String),
+}
+
+impl Condition {
+    /// Returns true if `task` meets this condition
+    pub fn matches(&self, task: &Task) -> bool {
+        match self {
+            Condition::Priority(op, priority) => op.compare(task.priority, *priority),
+            Condition::Horizon(op, horizon) => op.compare(task.time_horizon, *horizon),
+            Condition::Created(op, date) => match local_date(&task.created_at) {
+                Some(created) => op.compare(created, *date),
+                None => false,
+            },
+            Condition::Updated(op, date) => match local_date(task.modified_at()) {
+                Some(updated) => op.compare(updated, *date),
+                None => false,
+            },
+            Condition::Description(op, text) => {
+                let description = task.description.to_lowercase();
+                match op {
+                    TextOp::Eq => description == *text,
+                    TextOp::Ne => description != *text,
+                    TextOp::Contains => description.contains(text.as_str()),
+                    TextOp::NotContains => !description.contains(text.as_str()),
+                }
+            }
+            Condition::Tag(op, text) => {
+                let mut tags = task.tags.iter().map(|tag| tag.to_lowercase());
+                match op {
+                    TextOp::Eq => tags.any(|tag| tag == *text),
+                    TextOp::Ne => !tags.any(|tag| tag == *text),
+                    TextOp::Contains => tags.any(|tag| tag.contains(text.as_str())),
+                    TextOp::NotContains => !tags.any(|tag| tag.contains(text.as_str())),
+                }
+            }
+            Condition::Completed(completed) => task.completed == *completed,
+            Condition::IdPrefix(prefix) => task.id.starts_with(prefix.as_str()),
+        }
+    }
+}
+
+/// The day an RFC 3339 timestamp falls on, in local time
+fn local_date(timestamp: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|time| time.with_timezone(&Local).date_naive())
+}
+
+/// A filter expression tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Condition(Condition),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Returns true if `task` satisfies the expression
+    pub fn matches(&self, task: &Task) -> bool {
+        match self {
+            Expr::Condition(condition) => condition.matches(task),
+            Expr::Not(expr) => !expr.matches(task),
+            Expr::And(left, right) => left.matches(task) && right.matches(task),
+            Expr::Or(left, right) => left.matches(task) || right.matches(task),
+        }
+    }
+
+    /// Returns true if any condition is about completion
+    fn mentions_completion(&self) -> bool {
+        match self {
+            Expr::Condition(condition) => matches!(condition, Condition::Completed(_)),
+            Expr::Not(expr) => expr.mentions_completion(),
+            Expr::And(left, right) | Expr::Or(left, right) => {
+                left.mentions_completion() || right.mentions_completion()
+            }
+        }
+    }
+}
+
+/// A parsed filter expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    expr: Expr,
+}
+
+impl Filter {
+    /// Returns true if `task` matches the filter
+    pub fn matches(&self, task: &Task) -> bool {
+        self.expr.matches(task)
+    }
+
+    /// Returns true if the filter says anything about completion
+    ///
+    /// `list` hides completed tasks unless `--all` is given; a filter that
+    /// asks for them (`status:done`) shows them anyway.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use rust_todo::filter::Filter;
+    ///
+    /// assert!(Filter::from_str("tag:x or done").unwrap().mentions_completion());
+    /// assert!(!Filter::from_str("tag:x").unwrap().mentions_completion());
+    /// ```
+    pub fn mentions_completion(&self) -> bool {
+        self.expr.mentions_completion()
+    }
+}
+
+impl FromStr for Filter {
+    type Err = AppError;
+
+    /// Parses a filter expression
+    ///
+    /// # Errors
+    ///
+    /// Returns AppError::InvalidFilter describing the first problem and its
+    /// column.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use rust_todo::filter::Filter;
+    /// use rust_todo::task::{Priority, Task, TimeHorizon};
+    ///
+    /// let filter = Filter::from_str("priority>=medium and not completed").unwrap();
+    ///
+    /// let task = Task::new("Ship".to_string(), TimeHorizon::ShortTerm, Priority::High);
+    /// assert!(filter.matches(&task));
+    ///
+    /// let task = Task::new("Tidy".to_string(), TimeHorizon::ShortTerm, Priority::Low);
+    /// assert!(!filter.matches(&task));
+    ///
+    /// let error = Filter::from_str("prio:high").unwrap_err();
+    /// assert!(error.to_string().contains("did you mean 'priority'"));
+    /// ```
+    fn from_str(s: &str) -> Result<Self> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser { tokens, pos: 0 };
+
+        if parser.tokens.is_empty() {
+            return Err(AppError::InvalidFilter("the filter is empty".to_string()));
+        }
+        let expr = parser.parse_or()?;
+
+        // Everything must have been consumed; a stray ')' is the usual culprit
+        if let Some(token) = parser.peek() {
+            return Err(error_at(
+                format!("unexpected {}", token.kind.describe()),
+                token.column,
+            ));
+        }
+        Ok(Filter { expr })
+    }
+}
+
+/// Builds an InvalidFilter error pointing at a 1-based column
+fn error_at(message: String, column: usize) -> AppError {
+    AppError::InvalidFilter(format!("{} at column {}", message, column))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    LParen,
+    RParen,
+    /// A field name, keyword or bare value
+    Word(String),
+    /// A double-quoted value
+    Quoted(String),
+    Op(Op),
+}
+
+impl TokenKind {
+    fn describe(&self) -> String {
+        match self {
+            TokenKind::LParen => "'('".to_string(),
+            TokenKind::RParen => "')'".to_string(),
+            TokenKind::Word(word) => format!("'{}'", word),
+            TokenKind::Quoted(text) => format!("\"{}\"", text),
+            TokenKind::Op(op) => format!("'{}'", op.symbol()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    /// 1-based column of the token's first character
+    column: usize,
+}
+
+fn is_op_char(c: char) -> bool {
+    matches!(c, ':' | '=' | '!' | '<' | '>' | '~')
+}
+
+/// Splits a filter into tokens
+///
+/// The word after an operator is read up to whitespace or a parenthesis,
+/// so values may contain operator characters (`created>2026-01-01`).
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+        let after_op = matches!(
+            tokens.last(),
+            Some(Token {
+                kind: TokenKind::Op(_),
+                ..
+            })
+        );
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let kind = match c {
+            '(' => {
+                i += 1;
+                TokenKind::LParen
+            }
+            ')' => {
+                i += 1;
+                TokenKind::RParen
+            }
+            '"' => {
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        Some('"') => break,
+                        Some('\\') if i + 1 < chars.len() => {
+                            text.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        Some(&c) => {
+                            text.push(c);
+                            i += 1;
+                        }
+                        None => {
+                            return Err(error_at("unterminated quote".to_string(), column));
+                        }
+                    }
+                }
+                i += 1;
+                TokenKind::Quoted(text)
+            }
+            c if is_op_char(c) && !after_op => {
+                let next = chars.get(i + 1).copied();
+                let (op, len) = match (c, next) {
+                    ('!', Some('=')) => (Op::Ne, 2),
+                    ('!', Some('~')) => (Op::NotContains, 2),
+                    ('<', Some('=')) => (Op::Le, 2),
+                    ('>', Some('=')) => (Op::Ge, 2),
+                    (':', _) | ('=', _) => (Op::Eq, 1),
+                    ('<', _) => (Op::Lt, 1),
+                    ('>', _) => (Op::Gt, 1),
+                    ('~', _) => (Op::Contains, 1),
+                    _ => {
+                        return Err(error_at(
+                            "unexpected '!' (use 'not' to negate, or != and !~)".to_string(),
+                            column,
+                        ))
+                    }
+                };
+                i += len;
+                TokenKind::Op(op)
+            }
+            _ => {
+                let start = i;
+                while i < chars.len()
+                    && !chars[i].is_whitespace()
+                    && !matches!(chars[i], '(' | ')' | '"')
+                    && (after_op || !is_op_char(chars[i]))
+                {
+                    i += 1;
+                }
+                TokenKind::Word(chars[start..i].iter().collect())
+            }
+        };
+        tokens.push(Token { kind, column });
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser over the tokens
+///
+/// ```text
+/// or      := and ("or" and)*
+/// and     := unary ("and"? unary)*
+/// unary   := "not" unary | primary
+/// primary := "(" or ")" | field op value | "completed" | "done" | "open"
+/// ```
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// Returns true if the next token is the given keyword
+    fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token { kind: TokenKind::Word(word), .. })
+            if word.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Column just past the end, for errors about missing input
+    fn end_column(&self) -> usize {
+        match self.tokens.last() {
+            Some(token) => token.column + token.kind.describe().chars().count() - 2,
+            None => 1,
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut left = self.parse_and()?;
+        while self.at_keyword("or") {
+            self.next();
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let starts_condition = matches!(
+                self.peek(),
+                Some(Token {
+                    kind: TokenKind::Word(_) | TokenKind::LParen,
+                    ..
+                })
+            );
+            if self.at_keyword("and") {
+                self.next();
+            } else if self.at_keyword("or") || !starts_condition {
+                break;
+            }
+            // Either an explicit "and", or another condition right after this one
+            let right = self.parse_unary()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr> {
+        if self.at_keyword("not") {
+            self.next();
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr> {
+        let end = self.end_column();
+        let token = self.next().ok_or_else(|| {
+            error_at(
+                "expected a condition (e.g. priority:high) but the filter ended".to_string(),
+                end,
+            )
+        })?;
+
+        match token.kind {
+            TokenKind::LParen => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token {
+                        kind: TokenKind::RParen,
+                        ..
+                    }) => Ok(expr),
+                    _ => Err(error_at(
+                        "missing ')' to close this '('".to_string(),
+                        token.column,
+                    )),
+                }
+            }
+            TokenKind::Word(word) => {
+                let op = match self.peek() {
+                    Some(Token {
+                        kind: TokenKind::Op(op),
+                        ..
+                    }) => Some(*op),
+                    _ => None,
+                };
+                match op {
+                    Some(op) => {
+                        let op_column = self.next().map(|t| t.column).unwrap_or(end);
+                        let value = match self.next() {
+                            Some(Token {
+                                kind: TokenKind::Word(value) | TokenKind::Quoted(value),
+                                column,
+                            }) => (value, column),
+                            _ => {
+                                return Err(error_at(
+                                    format!("expected a value after '{}{}'", word, op.symbol()),
+                                    op_column,
+                                ))
+                            }
+                        };
+                        condition(&word, token.column, op, &value.0, value.1)
+                    }
+                    None => bare_word(&word, token.column),
+                }
+            }
+            kind => Err(error_at(
+                format!(
+                    "expected a condition (e.g. priority:high) but found {}",
+                    kind.describe()
+                ),
+                token.column,
+            )),
+        }
+    }
+}
+
+/// A word on its own: one of the completion shorthands
+fn bare_word(word: &str, column: usize) -> Result<Expr> {
+    match word.to_lowercase().as_str() {
+        "completed" | "done" => Ok(Expr::Condition(Condition::Completed(true))),
+        "open" => Ok(Expr::Condition(Condition::Completed(false))),
+        "and" | "or" | "not" => Err(error_at(
+            format!("expected a condition before '{}'", word),
+            column,
+        )),
+        _ => Err(error_at(
+            format!(
+                "'{}' is not a condition; write field:value, e.g. priority:high{}",
+                word,
+                suggestion(word)
+            ),
+            column,
+        )),
+    }
+}
+
+/// " (did you mean 'priority'?)" for a misspelt field, or nothing
+fn suggestion(word: &str) -> String {
+    let word = word.to_lowercase();
+    if word.len() < 2 {
+        return String::new();
+    }
+    FIELDS
+        .iter()
+        .find(|field| field.starts_with(&word) || word.starts_with(*field))
+        .map(|field| format!(" (did you mean '{}'?)", field))
+        .unwrap_or_default()
+}
+
+/// Builds a typed condition from `field op value`
+fn condition(
+    field: &str,
+    field_column: usize,
+    op: Op,
+    value: &str,
+    value_column: usize,
+) -> Result<Expr> {
+    let field = field.to_lowercase();
+    let invalid_value = |expected: &str| {
+        error_at(
+            format!(
+                "invalid {} value '{}' (expected {})",
+                field, value, expected
+            ),
+            value_column,
+        )
+    };
+    let ordered = |op: Op| -> Result<OrdOp> {
+        match op {
+            Op::Eq => Ok(OrdOp::Eq),
+            Op::Ne => Ok(OrdOp::Ne),
+            Op::Lt => Ok(OrdOp::Lt),
+            Op::Le => Ok(OrdOp::Le),
+            Op::Gt => Ok(OrdOp::Gt),
+            Op::Ge => Ok(OrdOp::Ge),
+            Op::Contains | Op::NotContains => {
+                Err(bad_op(&field, op, ":, =, !=, <, <=, > or >=", field_column))
+            }
+        }
+    };
+    let text = |op: Op| -> Result<TextOp> {
+        match op {
+            Op::Eq => Ok(TextOp::Eq),
+            Op::Ne => Ok(TextOp::Ne),
+            Op::Contains => Ok(TextOp::Contains),
+            Op::NotContains => Ok(TextOp::NotContains),
+            _ => Err(bad_op(&field, op, ":, =, !=, ~ or !~", field_column)),
+        }
+    };
+    // Boolean-like fields: `!=` negates the condition
+    let equality = |op: Op, condition: Condition| -> Result<Expr> {
+        match op {
+            Op::Eq => Ok(Expr::Condition(condition)),
+            Op::Ne => Ok(Expr::Not(Box::new(Expr::Condition(condition)))),
+            _ => Err(bad_op(&field, op, ":, = or !=", field_column)),
+        }
+    };
+
+    let condition = match field.as_str() {
+        "priority" => Condition::Priority(
+            ordered(op)?,
+            Priority::from_str(value).map_err(|_| invalid_value("low, medium or high"))?,
+        ),
+        "horizon" => Condition::Horizon(
+            ordered(op)?,
+            TimeHorizon::from_str(value).map_err(|_| invalid_value("short, mid or long"))?,
+        ),
+        "created" | "updated" => {
+            let op = ordered(op)?;
+            let date = parse_date(value)
+                .ok_or_else(|| invalid_value("a date like 2026-01-01, today or yesterday"))?;
+            if field == "created" {
+                Condition::Created(op, date)
+            } else {
+                Condition::Updated(op, date)
+            }
+        }
+        "desc" | "description" => Condition::Description(text(op)?, value.to_lowercase()),
+        "tag" if value.is_empty() => return Err(invalid_value("a tag name")),
+        "tag" => Condition::Tag(text(op)?, value.to_lowercase()),
+        "completed" => {
+            let completed = match value.to_lowercase().as_str() {
+                "true" | "yes" => true,
+                "false" | "no" => false,
+                _ => return Err(invalid_value("true or false")),
+            };
+            return equality(op, Condition::Completed(completed));
+        }
+        "status" => {
+            let completed = match value.to_lowercase().as_str() {
+                "done" | "completed" => true,
+                "open" => false,
+                _ => return Err(invalid_value("open or done")),
+            };
+            return equality(op, Condition::Completed(completed));
+        }
+        "id" if value.is_empty() => return Err(invalid_value("an ID prefix")),
+        "id" => return equality(op, Condition::IdPrefix(value.to_lowercase())),
+        _ => {
+            let hint = match suggestion(&field) {
+                hint if hint.is_empty() => format!(" (expected {})", FIELDS.join(", ")),
+                hint => hint,
+            };
+            return Err(error_at(
+                format!("unknown field '{}'{}", field, hint),
+                field_column,
+            ));
+        }
+    };
+    Ok(Expr::Condition(condition))
+}
+
+fn bad_op(field: &str, op: Op, allowed: &str, column: usize) -> AppError {
+    error_at(
+        format!(
+            "operator '{}' can't be used with {} (use {})",
+            op.symbol(),
+            field,
+            allowed
+        ),
+        column,
+    )
+}
+
+/// Parses YYYY-MM-DD, `today` or `yesterday`
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    let today = Local::now().date_naive();
+    match value.to_lowercase().as_str() {
+        "today" => Some(today),
+        "yesterday" => Some(today - Duration::days(1)),
+        _ => NaiveDate::parse_from_str(value, "%Y-%m-%d").ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(horizon: TimeHorizon, priority: Priority, tags: &[&str]) -> Task {
+        let mut task = Task::new("Task".to_string(), horizon, priority);
+        task.tags = tags.iter().map(|t| t.to_string()).collect();
+        task
+    }
+
+    fn matches(filter: &str, task: &Task) -> bool {
+        Filter::from_str(filter).unwrap().matches(task)
+    }
+
+    #[test]
+    fn test_simple_terms() {
+        let sprint = task(TimeHorizon::MidTerm, Priority::High, &["Sprint12"]);
+        let mut done = task(TimeHorizon::ShortTerm, Priority::Low, &[]);
+        done.mark_complete();
+
+        assert!(matches("tag:sprint12", &sprint));
+        assert!(!matches("tag:sprint1", &sprint));
+        assert!(matches("tag~sprint", &sprint));
+        assert!(matches("tag!=other", &sprint));
+        assert!(matches("horizon:mid priority:h", &sprint));
+        assert!(matches("status:done", &done));
+        assert!(!matches("status:open", &done));
+        assert!(!matches("status:open horizon:short", &done));
+        assert!(matches("completed", &done));
+        assert!(matches("completed!=false", &done));
+        assert!(matches(&format!("id:{}", &sprint.id[..4]), &sprint));
+    }
+
+    #[test]
+    fn test_ordering_and_dates() {
+        let mut api = Task::new(
+            "Design the API".to_string(),
+            TimeHorizon::LongTerm,
+            Priority::Medium,
+        );
+        api.created_at = "2026-03-10T12:00:00+00:00".to_string();
+
+        assert!(matches("priority>=medium", &api));
+        assert!(!matches("priority>medium", &api));
+        assert!(matches("horizon>short", &api));
+        assert!(matches("created>2026-01-01", &api));
+        assert!(matches("created:2026-03-10", &api));
+        assert!(!matches("created<2026-03-10", &api));
+        // Never updated: `updated` falls back to the creation time
+        assert!(matches("updated<=2026-03-10", &api));
+        assert!(matches("created<=today", &api));
+    }
+
+    #[test]
+    fn test_boolean_structure() {
+        let mut api = Task::new(
+            "Document the API".to_string(),
+            TimeHorizon::LongTerm,
+            Priority::High,
+        );
+        api.created_at = "2026-03-10T12:00:00+00:00".to_string();
+        let filter = r#"priority>=medium and not completed and (desc~"api" or horizon:short) and created>2026-01-01"#;
+        assert!(matches(filter, &api));
+
+        api.description = "Something else".to_string();
+        assert!(!matches(filter, &api));
+
+        // `and` binds tighter than `or`; juxtaposition is `and`
+        let low = task(TimeHorizon::ShortTerm, Priority::Low, &[]);
+        assert!(matches("priority:high or priority:low horizon:short", &low));
+        assert!(!matches(
+            "(priority:high or priority:low) horizon:long",
+            &low
+        ));
+        assert!(matches("not not open", &low));
+        assert!(matches("desc:TASK", &low));
+        assert!(matches("desc!~\"some thing\"", &low));
+    }
+
+    #[test]
+    fn test_mentions_completion() {
+        assert!(Filter::from_str("not (tag:x or status:open)")
+            .unwrap()
+            .mentions_completion());
+        assert!(!Filter::from_str("priority:high")
+            .unwrap()
+            .mentions_completion());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let message = |filter: &str| match Filter::from_str(filter) {
+            Err(AppError::InvalidFilter(message)) => message,
+            other => panic!("{:?} parsed as {:?}", filter, other),
+        };
+
+        assert_eq!(message(""), "the filter is empty");
+        assert_eq!(message("   "), "the filter is empty");
+        assert!(message("short").contains("'short' is not a condition"));
+        assert!(message("colour:red").starts_with("unknown field 'colour'"));
+        assert!(message("prio:high").contains("did you mean 'priority'"));
+        assert!(message("ids:abc").contains("did you mean 'id'"));
+        assert_eq!(
+            message("priority>=urgent"),
+            "invalid priority value 'urgent' (expected low, medium or high) at column 11"
+        );
+        assert!(message("horizon:soon").contains("expected short, mid or long"));
+        assert!(message("created>last-week").contains("a date like 2026-01-01"));
+        assert!(message("priority~high").starts_with("operator '~' can't be used"));
+        assert!(message("tag<x").starts_with("operator '<' can't be used"));
+        assert!(message("status:maybe").contains("expected open or done"));
+        assert!(message("tag:").contains("expected a value after 'tag:'"));
+        assert_eq!(
+            message("(tag:x or tag:y"),
+            "missing ')' to close this '(' at column 1"
+        );
+        assert_eq!(message("tag:x)"), "unexpected ')' at column 6");
+        assert!(message("tag:x and").contains("but the filter ended"));
+        assert!(message("or tag:x").contains("before 'or'"));
+        assert!(message("desc~\"api").starts_with("unterminated quote"));
+        assert!(message("!done").starts_with("unexpected '!'"));
+    }
+}
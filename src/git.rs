@@ -0,0 +1,244 @@
+// Git module - keeps the data directory in a git repository
+// This module demonstrates running external programs with std::process::Command
+//
+// In git-backed storage mode, the data directory is a git repository and every
+// command that changes the data creates a commit, e.g. "complete: Write tests
+// [59ead9]". That gives a full history of the data for free, makes any earlier
+// state restorable, and lets the data be pushed to a shared remote by hand.
+//
+// # Enabling
+//
+// `todo git init` turns the data directory into a repository. From then on the
+// mode is detected by the presence of `.git` in the data directory; there is
+// no setting to keep in sync with it.
+//
+// # Implementation
+//
+// This module runs the `git` executable rather than linking a git library, so
+// commits behave exactly as if made by hand (hooks, config, signing) and no
+// native dependency is needed. Git must be on the PATH for this mode.
+
+use crate::error::{AppError, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Files git should not track in the data directory
+///
+/// `*.tmp` are left behind if an atomic save is interrupted; `sync/` holds
+/// the per-machine sync bases, which make no sense on another machine, and
+/// neither does the shell's command history.
+const GITIGNORE: &str = "*.tmp\nsync/\nshell_history\n";
+
+/// Identity used for commits when git has no user configured
+const FALLBACK_NAME: &str = "todo";
+const FALLBACK_EMAIL: &str = "todo@localhost";
+
+/// One commit in the data history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LogEntry {
+    /// Abbreviated commit hash, usable with `todo git restore`
+    pub rev: String,
+
+    /// Commit time, ISO 8601
+    pub date: String,
+
+    /// Commit subject, e.g. "add: Write tests [59ead9]"
+    pub message: String,
+}
+
+/// A data directory that is a git repository
+#[derive(Debug, Clone)]
+pub struct GitRepo {
+    dir: PathBuf,
+}
+
+impl GitRepo {
+    /// Opens the repository in `dir`, or returns None if it isn't one
+    ///
+    /// Only `dir` itself is checked, so a data directory that happens to be
+    /// inside some other repository is not mistaken for git-backed storage.
+    pub fn open(dir: &Path) -> Option<GitRepo> {
+        dir.join(".git").exists().then(|| GitRepo {
+            dir: dir.to_path_buf(),
+        })
+    }
+
+    /// Turns `dir` into a repository, creating it if needed
+    ///
+    /// Running this on a directory that already is a repository is safe:
+    /// git leaves existing history alone.
+    pub fn init(dir: &Path) -> Result<GitRepo> {
+        std::fs::create_dir_all(dir)?;
+        let repo = GitRepo {
+            dir: dir.to_path_buf(),
+        };
+        repo.git(&["init", "--quiet"])?;
+
+        let gitignore = dir.join(".gitignore");
+        if !gitignore.exists() {
+            std::fs::write(gitignore, GITIGNORE)?;
+        }
+        Ok(repo)
+    }
+
+    /// Returns the repository directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Commits every change in the directory
+    ///
+    /// # Returns
+    ///
+    /// Ok(true) if a commit was made, Ok(false) if there was nothing to commit.
+    pub fn commit_all(&self, message: &str) -> Result<bool> {
+        self.git(&["add", "--all"])?;
+
+        // `diff --cached --quiet` exits with 1 when something is staged
+        let staged = self
+            .command(&["diff", "--cached", "--quiet"])
+            .status()
+            .map_err(spawn_error)?;
+        if staged.success() {
+            return Ok(false);
+        }
+
+        // Don't fail on machines where git has never been configured
+        let mut commit = self.command(&["commit", "--quiet", "-m", message]);
+        if self.git(&["config", "user.email"]).is_err() {
+            commit
+                .env("GIT_AUTHOR_NAME", FALLBACK_NAME)
+                .env("GIT_AUTHOR_EMAIL", FALLBACK_EMAIL)
+                .env("GIT_COMMITTER_NAME", FALLBACK_NAME)
+                .env("GIT_COMMITTER_EMAIL", FALLBACK_EMAIL);
+        }
+        run(commit)?;
+        Ok(true)
+    }
+
+    /// Lists the most recent commits, newest first
+    pub fn log(&self, limit: usize) -> Result<Vec<LogEntry>> {
+        // An empty repository has no HEAD, and so no history yet
+        if self
+            .git(&["rev-parse", "--verify", "--quiet", "HEAD"])
+            .is_err()
+        {
+            return Ok(Vec::new());
+        }
+
+        // Fields are separated by the ASCII unit separator, which can't
+        // appear in a commit subject
+        let limit = format!("--max-count={}", limit);
+        let output = self.git(&["log", &limit, "--format=%h%x1f%aI%x1f%s"])?;
+
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\u{1f}');
+                Some(LogEntry {
+                    rev: fields.next()?.to_string(),
+                    date: fields.next()?.to_string(),
+                    message: fields.next()?.to_string(),
+                })
+            })
+            .collect())
+    }
+
+    /// Replaces `file` in the working directory with its content at `rev`
+    ///
+    /// The change is not committed; the caller commits it like any other
+    /// change, so restoring is itself recorded and can be undone.
+    pub fn restore(&self, rev: &str, file: &Path) -> Result<()> {
+        let commit = format!("{}^{{commit}}", rev);
+        self.git(&["rev-parse", "--verify", "--quiet", &commit])
+            .map_err(|_| AppError::GitError(format!("unknown revision: {}", rev)))?;
+
+        let file = file.to_string_lossy();
+        self.git(&["checkout", rev, "--", &file])?;
+        Ok(())
+    }
+
+    /// Builds a git command that runs in the repository directory
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new("git");
+        command.arg("-C").arg(&self.dir).args(args);
+        command
+    }
+
+    /// Runs a git command and returns its standard output
+    fn git(&self, args: &[&str]) -> Result<String> {
+        run(self.command(args))
+    }
+}
+
+/// Runs a command, turning a non-zero exit into a GitError with git's message
+fn run(mut command: Command) -> Result<String> {
+    let output = command.output().map_err(spawn_error)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(AppError::GitError(if stderr.is_empty() {
+            format!("git exited with {}", output.status)
+        } else {
+            stderr
+        }));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Explains a failure to start git, which usually means it isn't installed
+fn spawn_error(error: std::io::Error) -> AppError {
+    AppError::GitError(format!("could not run git: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Git-backed storage needs the git executable; skip where it's missing
+    fn git_available() -> bool {
+        Command::new("git").arg("--version").output().is_ok()
+    }
+
+    #[test]
+    fn test_open_requires_git_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(GitRepo::open(dir.path()).is_none());
+
+        if !git_available() {
+            return;
+        }
+        GitRepo::init(dir.path()).unwrap();
+        assert!(GitRepo::open(dir.path()).is_some());
+        assert!(dir.path().join(".gitignore").exists());
+    }
+
+    #[test]
+    fn test_commit_log_and_restore() {
+        if !git_available() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let repo = GitRepo::init(dir.path()).unwrap();
+        let data = dir.path().join("data.json");
+        assert!(repo.log(10).unwrap().is_empty());
+
+        std::fs::write(&data, "one").unwrap();
+        assert!(repo.commit_all("add: one [aaaaaa]").unwrap());
+        std::fs::write(&data, "two").unwrap();
+        assert!(repo.commit_all("edit: two [aaaaaa]").unwrap());
+
+        // Nothing changed, so nothing to commit
+        assert!(!repo.commit_all("noop").unwrap());
+
+        let log = repo.log(10).unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].message, "edit: two [aaaaaa]");
+        assert_eq!(log[1].message, "add: one [aaaaaa]");
+
+        repo.restore(&log[1].rev, Path::new("data.json")).unwrap();
+        assert_eq!(std::fs::read_to_string(&data).unwrap(), "one");
+
+        assert!(repo.restore("no-such-rev", Path::new("data.json")).is_err());
+    }
+}
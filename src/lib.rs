@@ -4,21 +4,65 @@
 //
 // The library is organized into several modules:
 // - error: Custom error types for the application
+// - aging: Flagging or promoting tasks that stay in one horizon too long
 // - task: Task data structure and operations
+// - completions: Shell completion scripts with dynamic ID and context completion
+// - config: User settings read from config.json
 // - context: Context management for organizing tasks by project
+// - shell: Line splitting and tab completion for the interactive shell
 // - store: Data persistence using JSON files
 // - display: Formatting and displaying tasks
+// - filter: key:value filter expressions for selecting tasks
+// - git: Git-backed storage with a commit per change
+// - merge: Merge strategies for importing into existing data
+// - output: Machine-readable JSON output for scripts
+// - report: Self-contained HTML reports
+// - review: The guided `todo review` walkthrough of open tasks
+// - rpc: JSON-RPC 2.0 over stdin/stdout for editor integrations
+// - search: Ranked, typo-tolerant search across all contexts
+// - server: Local HTTP REST API over the task data
+// - stats: Productivity statistics for `todo stats`
+// - sort: Ordering tasks by one or more chosen fields
+// - streaming: Incremental, validating import of large export files
+// - sync: Three-way merge between two copies of the data file
+// - taskwarrior: Conversion to and from Taskwarrior's JSON export format
+// - tui: Full-screen terminal interface
+// - urgency: Urgency scores for deciding what to do next
+// - views: Saved filters with a sort order
+// - chart: Burndown and throughput charts drawn with Unicode blocks
 // - cli: Command-line interface definitions
 //
 // This structure demonstrates Rust's module system and separation of concerns.
 
 // Public module declarations - these modules are accessible to external code
+pub mod aging;
+pub mod chart;
 pub mod cli;
+pub mod completions;
+pub mod config;
 pub mod context;
 pub mod display;
 pub mod error;
+pub mod filter;
+pub mod git;
+pub mod merge;
+pub mod output;
+pub mod report;
+pub mod review;
+pub mod rpc;
+pub mod search;
+pub mod server;
+pub mod shell;
+pub mod sort;
+pub mod stats;
 pub mod store;
+pub mod streaming;
+pub mod sync;
 pub mod task;
+pub mod taskwarrior;
+pub mod tui;
+pub mod urgency;
+pub mod views;
 
 // Re-export commonly used types for convenience
 // This allows users to write `use rust_todo::Task` instead of `use rust_todo::task::Task`
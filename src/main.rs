@@ -19,16 +19,48 @@
 
 use clap::Parser;
 use colored::*;
+use rustyline::error::ReadlineError;
+use rustyline::history::DefaultHistory;
+use rustyline::Editor;
+use std::io::IsTerminal;
 use std::path::PathBuf;
 use std::str::FromStr;
 
 // Import our modules
-use rust_todo::cli::{Cli, Commands, ContextAction};
-use rust_todo::context::ContextManager;
-use rust_todo::display::{display_contexts, display_tasks};
+use rust_todo::aging::{self, AgingAction};
+use rust_todo::chart;
+use rust_todo::cli::{ChartKind, Cli, Commands, ContextAction, GitAction, ViewAction};
+use rust_todo::completions::{self, CompletionShell};
+use rust_todo::context::{ContextManager, ListOptions};
+use rust_todo::display::{
+    display_aging_report, display_chart, display_context_groups, display_contexts,
+    display_flat_tasks, display_merge_report, display_ranked, display_review_summary,
+    display_search_results, display_stats, display_sync_report, display_task_detail,
+    display_task_history, display_tasks, display_views, format_task_line,
+};
 use rust_todo::error::{AppError, Result};
-use rust_todo::store::Store;
-use rust_todo::task::{Priority, Task, TimeHorizon};
+use rust_todo::filter::Filter;
+use rust_todo::git::GitRepo;
+use rust_todo::merge::{merge, MergeChange, MergeStrategy};
+use rust_todo::output::{
+    emit, emit_list, ActionOutput, AgedTaskOutput, ChartOutput, ContextOutput, ErrorOutput,
+    HistoryEventOutput, OutputFormat, RankedOutput, ReviewOutput, SearchResultOutput, SyncOutput,
+    TaskOutput,
+};
+use rust_todo::report::render_html;
+use rust_todo::review::{self, ReviewAction, ReviewSummary};
+use rust_todo::rpc;
+use rust_todo::search::search;
+use rust_todo::server;
+use rust_todo::shell::{self, ShellHelper};
+use rust_todo::sort::{MoveDirection, SortOrder};
+use rust_todo::stats::{self, Period};
+use rust_todo::store::{DataFormat, ExportFilter, Store};
+use rust_todo::sync::{sync, Side, SyncConflict, SyncPolicy};
+use rust_todo::task::{parse_due, Priority, Task, TimeHorizon};
+use rust_todo::tui;
+use rust_todo::urgency::rank;
+use rust_todo::views::View;
 
 /// Main function - the entry point for the application
 ///
@@ -56,11 +88,9 @@ use rust_todo::task::{Priority, Task, TimeHorizon};
 /// # Program Flow
 ///
 /// 1. Parse CLI arguments
-/// 2. Initialize storage
-/// 3. Load current state
-/// 4. Execute command
-/// 5. Save updated state
-/// 6. Display success message
+/// 2. Parse the output format
+/// 3. Run the command (see run())
+/// 4. Report errors as text or JSON, depending on the output format
 ///
 /// # Example
 ///
@@ -69,6 +99,17 @@ use rust_todo::task::{Priority, Task, TimeHorizon};
 /// cargo run -- add "Write tests" -t short -p high
 /// ```
 fn main() -> Result<()> {
+    // Dynamic completion hook: completion scripts ask for task IDs and
+    // context names through the environment (TODO_COMPLETE), so the request
+    // never shows up as a command in help or in the generated scripts
+    if let Ok(kind) = std::env::var(completions::CANDIDATES_ENV) {
+        let manager = get_store()?.load()?;
+        for candidate in completions::candidates(&manager, &kind)? {
+            println!("{}", candidate);
+        }
+        return Ok(());
+    }
+
     // Parse command-line arguments using clap
     // The parse() method is provided by the Parser derive macro
     // It will:
@@ -78,61 +119,311 @@ fn main() -> Result<()> {
     // - Exit with an error if arguments are invalid
     let cli = Cli::parse();
 
+    // Parse the global --output flag before doing anything else
+    // so that every later error can be reported in the requested format
+    let output = OutputFormat::from_str(&cli.output)?;
+
+    match run(cli.command, output) {
+        Ok(()) => Ok(()),
+        Err(error) if !output.is_text() => {
+            // Scripts get a JSON error object with a stable code instead of
+            // the text message; the exit status still signals failure
+            emit(output, &ErrorOutput::from(&error))?;
+            std::process::exit(1);
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Loads the data, runs one command and saves the result
+///
+/// This is the body of the program once arguments are parsed. Keeping it
+/// separate from main() lets main() decide how errors are presented.
+///
+/// # Arguments
+///
+/// * `command` - The parsed subcommand to execute
+/// * `output` - The output format selected with --output
+///
+/// # Returns
+///
+/// Ok(()) if the command succeeded and the data was saved, or the first error.
+fn run(command: Commands, output: OutputFormat) -> Result<()> {
     // Initialize the storage system
     // We use the directories crate to find the appropriate data directory for the OS
     let store = get_store()?;
 
+    // The RPC session loads and saves for itself on every request; saving
+    // data loaded now would undo changes made while it ran
+    if let Commands::Rpc = command {
+        return rpc::serve_stdio(store);
+    }
+
+    // Completion scripts don't depend on the data
+    if let Commands::Completions { shell } = &command {
+        print!("{}", completions::script(CompletionShell::from_str(shell)?));
+        return Ok(());
+    }
+
     // Load the current state from disk
     // If the file doesn't exist, this creates a new default ContextManager
     // The ? operator propagates any errors (e.g., corrupted file, permission denied)
     let mut manager = store.load()?;
+    let repo = store.file_path().parent().and_then(GitRepo::open);
+
+    // Aging rules set to run on load promote tasks before the command sees
+    // them. The promotions are saved (and committed) on their own, so they
+    // don't end up in the commit of an unrelated command.
+    let rules = store.load_config()?.aging;
+    if rules.on_load {
+        let before = manager.clone();
+        let report = aging::apply(&mut manager, &rules, chrono::Utc::now(), false);
+        if report.promoted() > 0 {
+            eprintln!(
+                "{} Aging rules moved {} old task(s) one horizon closer",
+                "ℹ".cyan(),
+                report.promoted()
+            );
+            store.save(&manager)?;
+            if let Some(repo) = &repo {
+                repo.commit_all(&CommitSubject::Bulk("age").message(&before, &manager))?;
+            }
+        }
+    }
+
+    // In git-backed mode, note what a changing command is about before it
+    // runs, so the commit made after saving can describe the change
+    let subject = repo.as_ref().and_then(|_| CommitSubject::of(&command));
+    let before = subject.as_ref().map(|_| manager.clone());
+
+    // Route the command to the appropriate handler
+    // The shell runs many commands against the same loaded data
+    match command {
+        Commands::Shell => handle_shell(&store, &mut manager, repo.as_ref())?,
+        command => dispatch(&store, &mut manager, repo.as_ref(), command, output)?,
+    }
+
+    // Save the updated state back to disk
+    // This is called after every command to ensure data persistence
+    // The ? operator propagates any errors (e.g., disk full, permission denied)
+    store.save(&manager)?;
+
+    // Record the change; commands that changed nothing produce no commit
+    if let (Some(repo), Some(subject), Some(before)) = (repo, subject, before) {
+        repo.commit_all(&subject.message(&before, &manager))?;
+    }
+
+    // Return success
+    // Ok(()) indicates the program completed successfully
+    Ok(())
+}
 
+/// Runs one command against loaded data
+///
+/// Shared by run() and the shell. Saving is left to the caller.
+///
+/// # Arguments
+///
+/// * `store` - The Store the data was loaded from
+/// * `manager` - The loaded data
+/// * `repo` - The data repository, in git-backed mode
+/// * `command` - The command to run
+/// * `output` - The output format selected with --output
+fn dispatch(
+    store: &Store,
+    manager: &mut ContextManager,
+    repo: Option<&GitRepo>,
+    command: Commands,
+    output: OutputFormat,
+) -> Result<()> {
     // Route the command to the appropriate handler
     // We use pattern matching to handle each command variant
     // Each handler modifies the manager and returns a Result
-    match cli.command {
+    match command {
         Commands::Add {
             description,
             horizon,
             priority,
+            due,
+        } => {
+            let due = due.map(|d| parse_due(&d)).transpose()?.flatten();
+            handle_add(manager, description, horizon, priority, due, output)?;
+        }
+        Commands::List {
+            all,
+            horizon,
+            filter,
+            sort,
+            all_contexts,
+            contexts,
+            flat,
         } => {
-            handle_add(&mut manager, description, horizon, priority)?;
+            // Parse every flag before touching any data
+            let filter = filter.map(|f| Filter::from_str(&f)).transpose()?;
+            let options = ListOptions {
+                // A filter that asks about completion decides for itself
+                // which tasks to show
+                show_all: all || filter.as_ref().is_some_and(Filter::mentions_completion),
+                horizon: horizon.map(|h| TimeHorizon::from_str(&h)).transpose()?,
+                filter,
+                order: sort
+                    .map(|s| SortOrder::from_str(&s))
+                    .transpose()?
+                    .unwrap_or_default()
+                    .with_weights(store.load_config()?.urgency),
+                flat,
+            };
+            let names = listed_contexts(manager, all_contexts, contexts)?;
+            handle_list(manager, &names, &options, output)?;
         }
-        Commands::List { all, horizon } => {
-            handle_list(&manager, all, horizon)?;
+        Commands::Show { id } => {
+            handle_show(manager, id, output)?;
         }
-        Commands::Complete { id } => {
-            handle_complete(&mut manager, id)?;
+        Commands::History { id } => {
+            handle_history(manager, id, output)?;
+        }
+        Commands::Search { terms, limit } => {
+            handle_search(manager, &terms.join(" "), limit, output)?;
+        }
+        Commands::Next { limit } => {
+            handle_next(store, manager, limit, output)?;
+        }
+        Commands::Age { dry_run } => {
+            handle_age(store, manager, dry_run, output)?;
+        }
+        Commands::Stats {
+            by,
+            periods,
+            oldest,
+        } => {
+            let period = Period::from_str(&by)?;
+            handle_stats(manager, period, periods, oldest, output)?;
+        }
+        Commands::Chart { chart } => {
+            handle_chart(manager, chart, output)?;
+        }
+        Commands::Review => {
+            handle_review(store, manager, output)?;
+        }
+        Commands::Complete { ids, filter, yes } => {
+            let selection = select_tasks(manager, &ids, filter.as_deref(), "Complete", yes)?;
+            handle_complete(manager, selection, output)?;
         }
         Commands::Edit {
-            id,
+            ids,
+            filter,
+            yes,
             description,
             horizon,
             priority,
+            due,
         } => {
-            handle_edit(&mut manager, id, description, horizon, priority)?;
+            // Parse the new values first: a typo shouldn't cost a confirmation
+            let horizon = horizon.map(|h| TimeHorizon::from_str(&h)).transpose()?;
+            let priority = priority.map(|p| Priority::from_str(&p)).transpose()?;
+            let due = due.map(|d| parse_due(&d)).transpose()?;
+            let selection = select_tasks(manager, &ids, filter.as_deref(), "Edit", yes)?;
+            handle_edit(
+                manager,
+                selection,
+                description,
+                horizon,
+                priority,
+                due,
+                output,
+            )?;
         }
-        Commands::Delete { id } => {
-            handle_delete(&mut manager, id)?;
+        Commands::Delete { ids, filter, yes } => {
+            let selection = select_tasks(manager, &ids, filter.as_deref(), "Delete", yes)?;
+            handle_delete(manager, selection, output)?;
+        }
+        Commands::Move { id, direction } => {
+            handle_move(manager, id, MoveDirection::from_str(&direction)?, output)?;
         }
         Commands::Context { action } => {
-            handle_context(&mut manager, action)?;
+            handle_context(manager, action, output)?;
+        }
+        Commands::View { action, name } => {
+            handle_view(store, manager, action, name, output)?;
+        }
+        Commands::Export {
+            path,
+            format,
+            contexts,
+            horizon,
+            open_only,
+            filter,
+            view,
+        } => {
+            // Build the selection from the filter flags
+            let mut filter = ExportFilter {
+                contexts: if contexts.is_empty() {
+                    None
+                } else {
+                    Some(contexts)
+                },
+                horizon: horizon.map(|h| TimeHorizon::from_str(&h)).transpose()?,
+                open_only,
+                matching: filter.map(|f| Filter::from_str(&f)).transpose()?,
+            };
+            if let Some(name) = view {
+                let view = find_view(store, manager, &name)?;
+                filter.matching = Some(view.parsed_filter()?);
+                if filter.contexts.is_none() {
+                    filter.contexts = view.context.map(|context| vec![context]);
+                }
+            }
+            handle_export(store, manager, path, format, &filter, output)?;
+        }
+        Commands::Import {
+            path,
+            format,
+            merge,
+            strategy,
+            dry_run,
+        } => {
+            // Choosing a strategy only makes sense when merging
+            let strategy = match strategy {
+                Some(strategy) => Some(MergeStrategy::from_str(&strategy)?),
+                None if merge => Some(MergeStrategy::default()),
+                None => None,
+            };
+            handle_import(store, manager, path, format, strategy, dry_run, output)?;
+        }
+        Commands::Report { html, view } => match view {
+            Some(name) => {
+                // Report on the part of the data the view selects
+                let view = find_view(store, manager, &name)?;
+                let filter = ExportFilter {
+                    contexts: view.context.clone().map(|context| vec![context]),
+                    matching: Some(view.parsed_filter()?),
+                    ..ExportFilter::default()
+                };
+                handle_report(&filter.apply(manager)?, html, output)?;
+            }
+            None => handle_report(manager, html, output)?,
+        },
+        Commands::Sync { path, resolve } => {
+            let policy = SyncPolicy::from_str(&resolve)?;
+            handle_sync(store, manager, path, policy, output)?;
+        }
+        Commands::Tui => {
+            tui::run(store, manager)?;
         }
-        Commands::Export { path } => {
-            handle_export(&store, &manager, path)?;
+        Commands::Serve { bind } => {
+            // Serving never returns normally, so the save below is not reached
+            handle_serve(store, &bind, output)?;
         }
-        Commands::Import { path, merge } => {
-            handle_import(&store, &mut manager, path, merge)?;
+        // These take over the whole process, or skip loading or saving the
+        // data, so run() starts them itself
+        Commands::Shell | Commands::Rpc | Commands::Completions { .. } => {
+            unreachable!("started by run()")
+        }
+        Commands::Git { action } => {
+            handle_git(store, manager, repo, action, output)?;
         }
     }
 
-    // Save the updated state back to disk
-    // This is called after every command to ensure data persistence
-    // The ? operator propagates any errors (e.g., disk full, permission denied)
-    store.save(&manager)?;
-
-    // Return success
-    // Ok(()) indicates the program completed successfully
     Ok(())
 }
 
@@ -191,6 +482,8 @@ fn get_store() -> Result<Store> {
 /// * `description` - The task description
 /// * `horizon` - Time horizon string (short, mid, long)
 /// * `priority` - Priority string (low, medium, high)
+/// * `due` - Optional due date
+/// * `output` - Output format; JSON formats print the created task
 ///
 /// # Returns
 ///
@@ -209,6 +502,8 @@ fn handle_add(
     description: String,
     horizon: String,
     priority: String,
+    due: Option<chrono::NaiveDate>,
+    output: OutputFormat,
 ) -> Result<()> {
     // Parse the time horizon string to a TimeHorizon enum
     // FromStr::from_str() returns Result<TimeHorizon, AppError>
@@ -221,7 +516,8 @@ fn handle_add(
 
     // Create a new task with the parsed values
     // Task::new() generates a UUID and timestamp automatically
-    let task = Task::new(description.clone(), time_horizon, priority_level);
+    let mut task = Task::new(description.clone(), time_horizon, priority_level);
+    task.due = due;
 
     // Get the task ID for display (first 6 characters)
     // Clone the ID to avoid borrowing issues
@@ -232,6 +528,13 @@ fn handle_add(
     // add_task() takes ownership of the task and adds it to the context's Vec
     manager.active_context_mut().add_task(task);
 
+    // Scripts get the created task, including its full ID
+    if !output.is_text() {
+        let context = manager.active_context();
+        let task = context.tasks.last().expect("task was just added");
+        return emit(output, &TaskOutput::new(task, &context.name));
+    }
+
     // Display success message with colored output
     // The colored crate provides methods like .green() and .bold()
     println!(
@@ -244,23 +547,58 @@ fn handle_add(
     Ok(())
 }
 
+/// Works out which contexts `todo list` shows
+///
+/// # Returns
+///
+/// Every context (sorted by name) for `--all-contexts`, the `--context`
+/// names in the order given, or else just the active context. Err with
+/// AppError::ContextNotFound for an unknown name.
+fn listed_contexts(
+    manager: &ContextManager,
+    all_contexts: bool,
+    requested: Vec<String>,
+) -> Result<Vec<String>> {
+    if all_contexts {
+        let mut names: Vec<String> = manager.contexts.keys().cloned().collect();
+        names.sort();
+        return Ok(names);
+    }
+    if requested.is_empty() {
+        return Ok(vec![manager.active_context.clone()]);
+    }
+
+    let mut names: Vec<String> = Vec::new();
+    for name in requested {
+        if !manager.contexts.contains_key(&name) {
+            return Err(AppError::ContextNotFound(name));
+        }
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    Ok(names)
+}
+
 /// Handles the List command - displays tasks
 ///
 /// This function demonstrates:
 /// - Filtering tasks by time horizon
-/// - Sorting tasks by priority
+/// - Sorting tasks by priority, or by the `--sort` keys
 /// - Using the display module for formatted output
 /// - Conditional logic based on flags
 ///
 /// # Arguments
 ///
 /// * `manager` - Reference to the ContextManager
-/// * `show_all` - Whether to show completed tasks
-/// * `horizon_filter` - Optional time horizon to filter by
+/// * `names` - The contexts to list, from listed_contexts()
+/// * `options` - Which tasks to show and how
+/// * `output` - Output format; JSON formats print an array of tasks, in the
+///   order the text output shows them
 ///
 /// # Returns
 ///
-/// Ok(()) if successful, or an error if the horizon filter is invalid.
+/// Ok(()) once the tasks are shown.
 ///
 /// # Requirements
 ///
@@ -272,267 +610,997 @@ fn handle_add(
 /// - Requirement 3.5: Visually distinguish completed tasks
 fn handle_list(
     manager: &ContextManager,
-    show_all: bool,
-    horizon_filter: Option<String>,
+    names: &[String],
+    options: &ListOptions,
+    output: OutputFormat,
 ) -> Result<()> {
-    // Get the active context
-    // active_context() returns an immutable reference
-    let context = manager.active_context();
+    // The tasks of each context, grouped by horizon and sorted within each
+    let groups: Vec<(&str, Vec<&Task>)> = names
+        .iter()
+        .map(|name| {
+            let context = &manager.contexts[name];
+            (context.name.as_str(), options.select(context))
+        })
+        .collect();
 
-    // Get the tasks to display based on the horizon filter
-    let tasks: Vec<&Task> = if let Some(horizon_str) = horizon_filter {
-        // Parse the horizon filter string
-        let horizon = TimeHorizon::from_str(&horizon_str)?;
+    // Flattened, the sort keys order the whole list; the order of the
+    // groups (contexts, then horizons) breaks ties
+    let mut flat: Vec<(&str, &Task)> = Vec::new();
+    if options.flat {
+        flat = groups
+            .iter()
+            .flat_map(|(name, tasks)| tasks.iter().map(move |task| (*name, *task)))
+            .collect();
+        options.order.sort_by_task(&mut flat, |(_, task)| task);
+    }
 
-        // Filter tasks by the specified horizon
-        context.tasks_by_horizon(horizon)
-    } else {
-        // No filter - get all tasks sorted by horizon and priority
-        context.sorted_tasks()
-    };
+    // Scripts get the tasks in the order the text output shows them
+    if !output.is_text() {
+        let items: Vec<TaskOutput> = if options.flat {
+            flat.iter()
+                .map(|(name, task)| TaskOutput::new(task, name))
+                .collect()
+        } else {
+            groups
+                .iter()
+                .flat_map(|(name, tasks)| tasks.iter().map(|task| TaskOutput::new(task, name)))
+                .collect()
+        };
+        return emit_list(output, &items);
+    }
 
     // Display the tasks using the display module
     // display_tasks() handles formatting, grouping, and coloring
-    display_tasks(&tasks, show_all);
+    match groups.as_slice() {
+        _ if options.flat => display_flat_tasks(&flat),
+        [(_, tasks)] => display_tasks(tasks, options.show_all),
+        groups => display_context_groups(groups, options.show_all),
+    }
 
     // Display context information
     println!();
-    println!(
-        "{} Context: {}",
-        "ℹ".cyan(),
-        manager.active_context.cyan().bold()
-    );
+    if names.len() == 1 {
+        println!("{} Context: {}", "ℹ".cyan(), names[0].cyan().bold());
+    } else {
+        println!(
+            "{} Contexts: {} · Active: {}",
+            "ℹ".cyan(),
+            names.join(", ").cyan(),
+            manager.active_context.cyan().bold()
+        );
+    }
 
     Ok(())
 }
 
-/// Handles the Complete command - marks a task as done
-///
-/// This function demonstrates:
-/// - Finding tasks by partial ID matching
-/// - Modifying task state
-/// - Error handling for missing tasks
-/// - User feedback
+/// Handles the Show command - displays all details of one task
 ///
 /// # Arguments
 ///
-/// * `manager` - Mutable reference to the ContextManager
+/// * `manager` - Reference to the ContextManager
 /// * `id` - Task ID (can be partial)
+/// * `output` - Output format; JSON formats print the task object
 ///
 /// # Returns
 ///
-/// Ok(()) if the task was marked complete, or an error if not found.
-///
-/// # Requirements
-///
-/// This function satisfies:
-/// - Requirement 2.3: Mark task as complete
-/// - Requirement 2.4: Return error if task not found
-fn handle_complete(manager: &mut ContextManager, id: String) -> Result<()> {
-    // Get the active context
-    let context = manager.active_context_mut();
+/// Ok(()) if the task was found, or an error if not found or ambiguous.
+fn handle_show(manager: &ContextManager, id: String, output: OutputFormat) -> Result<()> {
+    let context = manager.active_context();
 
-    // Find the task by ID (supports partial matching)
-    let task = find_task_by_partial_id(context, &id)?;
+    // Resolve the partial ID, then borrow the task immutably
+    let full_id = find_task_id_by_partial(context, &id)?;
+    let task = context
+        .find_task(&full_id)
+        .ok_or_else(|| AppError::TaskNotFound(id.clone()))?;
 
-    // Mark the task as complete
-    task.mark_complete();
+    if !output.is_text() {
+        return emit(output, &TaskOutput::new(task, &context.name));
+    }
 
-    // Display success message
-    println!(
-        "{} Task completed: {}",
-        "✓".green().bold(),
-        task.description.dimmed()
-    );
+    display_task_detail(task);
 
     Ok(())
 }
 
-/// Handles the Edit command - modifies task properties
-///
-/// This function demonstrates:
-/// - Optional parameter handling with Option<T>
-/// - Conditional updates based on provided values
-/// - String parsing for enum values
-/// - User feedback showing what changed
+/// Handles the History command - displays the timeline of one task
 ///
 /// # Arguments
 ///
-/// * `manager` - Mutable reference to the ContextManager
-/// * `id` - Task ID to edit
-/// * `description` - Optional new description
-/// * `horizon` - Optional new time horizon
-/// * `priority` - Optional new priority
+/// * `manager` - Reference to the ContextManager
+/// * `id` - Task ID (can be partial)
+/// * `output` - Output format; JSON formats print the list of history events
 ///
 /// # Returns
 ///
-/// Ok(()) if the task was edited, or an error if not found or parsing fails.
-///
-/// # Requirements
-///
-/// This function satisfies:
-/// - Requirement 2.1: Allow modification of task properties
-/// - Requirement 2.2: Update time horizon and persist
-/// - Requirement 2.4: Return error if task not found
-fn handle_edit(
-    manager: &mut ContextManager,
-    id: String,
-    description: Option<String>,
-    horizon: Option<String>,
-    priority: Option<String>,
-) -> Result<()> {
-    // Parse optional time horizon
-    let time_horizon = if let Some(h) = horizon {
-        Some(TimeHorizon::from_str(&h)?)
-    } else {
-        None
-    };
-
-    // Parse optional priority
-    let priority_level = if let Some(p) = priority {
-        Some(Priority::from_str(&p)?)
-    } else {
-        None
-    };
-
-    // Get the active context
-    let context = manager.active_context_mut();
+/// Ok(()) if the task was found, or an error if not found or ambiguous.
+fn handle_history(manager: &ContextManager, id: String, output: OutputFormat) -> Result<()> {
+    let context = manager.active_context();
 
-    // Find the task by ID
-    let task = find_task_by_partial_id(context, &id)?;
+    let full_id = find_task_id_by_partial(context, &id)?;
+    let task = context
+        .find_task(&full_id)
+        .ok_or_else(|| AppError::TaskNotFound(id.clone()))?;
 
-    // Update the task with the provided values
-    task.update(description.clone(), time_horizon, priority_level);
+    if !output.is_text() {
+        return emit_list(output, &HistoryEventOutput::timeline(task));
+    }
 
-    // Display success message
-    println!(
-        "{} Task updated: {}",
-        "✓".green().bold(),
-        task.description.dimmed()
-    );
+    display_task_history(task);
 
     Ok(())
 }
 
-/// Handles the Delete command - removes a task
-///
-/// This function demonstrates:
-/// - Removing items from collections
-/// - Confirmation messages
-/// - Error handling for missing tasks
+/// Handles the Search command - finds tasks in every context
 ///
 /// # Arguments
 ///
-/// * `manager` - Mutable reference to the ContextManager
-/// * `id` - Task ID to delete
+/// * `manager` - Reference to the ContextManager
+/// * `query` - The search terms, separated by spaces
+/// * `limit` - Maximum number of results to show
+/// * `output` - Output format; JSON formats print an array of search results
 ///
 /// # Returns
 ///
-/// Ok(()) if the task was deleted, or an error if not found.
-///
-/// # Requirements
-///
-/// This function satisfies:
-/// - Requirement 2.5: Delete task permanently
-/// - Requirement 2.4: Return error if task not found
-fn handle_delete(manager: &mut ContextManager, id: String) -> Result<()> {
-    // Get the active context
-    let context = manager.active_context_mut();
-
-    // Find the full task ID by partial match
-    let full_id = find_task_id_by_partial(context, &id)?;
+/// Ok(()); finding nothing is not an error.
+fn handle_search(
+    manager: &ContextManager,
+    query: &str,
+    limit: usize,
+    output: OutputFormat,
+) -> Result<()> {
+    let mut hits = search(manager, query);
+    hits.truncate(limit);
 
-    // Remove the task and get it back (for displaying confirmation)
-    let removed_task = context.remove_task(&full_id)?;
+    if !output.is_text() {
+        let results: Vec<SearchResultOutput> = hits.iter().map(SearchResultOutput::new).collect();
+        return emit_list(output, &results);
+    }
 
-    // Display success message
-    println!(
-        "{} Task deleted: {}",
-        "✓".green().bold(),
-        removed_task.description.dimmed()
-    );
+    display_search_results(&hits, query);
 
     Ok(())
 }
 
-/// Handles the Context command - manages contexts
-///
-/// This function demonstrates:
-/// - Nested command handling with pattern matching
-/// - Context operations (create, switch, list, delete)
-/// - User feedback for each operation
+/// Handles the Next command - shows the most urgent open tasks
 ///
 /// # Arguments
 ///
-/// * `manager` - Mutable reference to the ContextManager
-/// * `action` - The context action to perform
+/// * `store` - Reference to the Store, which knows where the config file is
+/// * `manager` - Reference to the ContextManager
+/// * `limit` - How many tasks to show
+/// * `output` - Output format; JSON formats print an array of ranked tasks
 ///
 /// # Returns
 ///
-/// Ok(()) if the operation succeeded, or an error if it failed.
+/// Ok(()), or an error if the config file can't be read.
+fn handle_next(
+    store: &Store,
+    manager: &ContextManager,
+    limit: usize,
+    output: OutputFormat,
+) -> Result<()> {
+    let config = store.load_config()?;
+    let ranked = rank(manager, &config.urgency, chrono::Utc::now());
+    let open_count = ranked.len();
+    let top = &ranked[..limit.min(open_count)];
+
+    if !output.is_text() {
+        let items: Vec<RankedOutput> = top.iter().map(RankedOutput::new).collect();
+        return emit_list(output, &items);
+    }
+
+    display_ranked(top, open_count);
+
+    Ok(())
+}
+
+/// Handles the age command: applies the aging rules and reports on them
 ///
-/// # Requirements
+/// # Arguments
 ///
-/// This function satisfies:
-/// - Requirement 5.1: Create new context
-/// - Requirement 5.2: Switch to different context
-/// - Requirement 5.3: List all contexts
-/// - Requirement 5.4: Reject duplicate context names
-/// - Requirement 5.5: Delete context and tasks
-fn handle_context(manager: &mut ContextManager, action: ContextAction) -> Result<()> {
-    match action {
-        ContextAction::New { name } => {
-            // Create a new context
-            manager.create_context(name.clone())?;
+/// * `store` - Where the config file is read from
+/// * `manager` - The data; promoted tasks are changed in place
+/// * `dry_run` - Only report what the rules would do
+/// * `output` - Text or JSON output
+fn handle_age(
+    store: &Store,
+    manager: &mut ContextManager,
+    dry_run: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    let rules = store.load_config()?.aging;
+    let report = aging::apply(manager, &rules, chrono::Utc::now(), dry_run);
 
-            println!(
-                "{} Context created: {}",
-                "✓".green().bold(),
-                name.cyan().bold()
-            );
-        }
-        ContextAction::Switch { name } => {
-            // Switch to a different context
-            manager.switch_context(&name)?;
+    if !output.is_text() {
+        let items: Vec<AgedTaskOutput> = report.tasks.iter().map(AgedTaskOutput::new).collect();
+        return emit_list(output, &items);
+    }
 
-            // Get task count for the new context
-            let task_count = manager.active_context().tasks.len();
+    display_aging_report(&report, dry_run);
 
-            println!(
-                "{} Switched to context: {} ({} tasks)",
-                "✓".green().bold(),
-                name.cyan().bold(),
-                task_count
-            );
-        }
-        ContextAction::List => {
-            // List all contexts
-            let context_names = manager.list_contexts();
-            display_contexts(&context_names, &manager.active_context);
-        }
-        ContextAction::Delete { name } => {
-            // Delete a context
-            manager.delete_context(&name)?;
+    Ok(())
+}
 
-            println!("{} Context deleted: {}", "✓".green().bold(), name.dimmed());
-        }
+/// Handles the stats command: computes and shows statistics of every context
+fn handle_stats(
+    manager: &ContextManager,
+    period: Period,
+    periods: usize,
+    oldest: usize,
+    output: OutputFormat,
+) -> Result<()> {
+    let stats = stats::compute(manager, period, periods, oldest, chrono::Utc::now());
+
+    if !output.is_text() {
+        return emit(output, &stats);
     }
 
+    display_stats(&stats);
+
     Ok(())
 }
 
-/// Handles the Export command - exports data to a file
+/// Handles the chart command: draws a burndown or throughput chart
 ///
-/// This function demonstrates:
-/// - File I/O operations
-/// - Using the store module for export
-/// - User feedback with file path
+/// # Arguments
+///
+/// * `manager` - The data
+/// * `chart` - Which chart, with its context and date range
+/// * `output` - Text draws the chart; JSON prints its points
+///
+/// # Returns
+///
+/// Ok(()) on success, Err(AppError::ContextNotFound) for an unknown context,
+/// or Err(AppError::InvalidDate) for a `--since` that isn't a past date.
+fn handle_chart(manager: &ContextManager, chart: ChartKind, output: OutputFormat) -> Result<()> {
+    let today = chrono::Utc::now().date_naive();
+    let context_name = match &chart {
+        ChartKind::Burndown { context, .. } | ChartKind::Throughput { context, .. } => context
+            .clone()
+            .unwrap_or_else(|| manager.active_context.clone()),
+    };
+    let context = manager
+        .contexts
+        .get(&context_name)
+        .ok_or_else(|| AppError::ContextNotFound(context_name.clone()))?;
+    let tasks: Vec<&Task> = context.tasks.iter().collect();
+
+    let (kind, points, title) = match chart {
+        ChartKind::Burndown { since, .. } => {
+            let since = match since {
+                Some(date) => chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                    .ok()
+                    .filter(|since| *since <= today)
+                    .ok_or(AppError::InvalidDate(date))?,
+                None => today - chrono::Duration::weeks(4),
+            };
+            let title = format!("Open tasks in {} since {}", context_name, since);
+            ("burndown", chart::burndown(&tasks, since, today), title)
+        }
+        ChartKind::Throughput { weeks, .. } => {
+            let title = format!("Tasks completed per week in {}", context_name);
+            ("throughput", chart::throughput(&tasks, weeks, today), title)
+        }
+    };
+
+    if !output.is_text() {
+        return emit(
+            output,
+            &ChartOutput {
+                chart: kind,
+                context: &context_name,
+                points: &points,
+            },
+        );
+    }
+
+    let width = chart::terminal_width();
+    let lines = match kind {
+        "burndown" => chart::render_columns(&points, width),
+        _ => chart::render_bars(&points, width),
+    };
+    display_chart(&title, &lines);
+
+    Ok(())
+}
+
+/// Handles the review command: asks about every open task of the active context
+///
+/// This function demonstrates:
+/// - Driving a testable workflow (the review module) from an input loop
+/// - Applying the aging rules first, so the review sees the horizons they
+///   promoted tasks to (in every context, as `todo age` does)
+///
+/// Prompts go to stderr, so JSON output on stdout is just the summary.
+/// Ending the review early (`q` or end of input) keeps the answers given.
+///
+/// # Arguments
+///
+/// * `store` - Where the config file with the aging rules is read from
+/// * `manager` - The data; aged and answered tasks are changed in place
+/// * `output` - Text or JSON output for the summary
+fn handle_review(store: &Store, manager: &mut ContextManager, output: OutputFormat) -> Result<()> {
+    let now = chrono::Utc::now();
+    let rules = store.load_config()?.aging;
+    let (aged, ids) = review::start(manager, &rules, now);
+
+    // The aging report comes first: the walkthrough below already shows the
+    // horizons it promoted tasks to
+    if output.is_text() && !aged.tasks.is_empty() {
+        display_aging_report(&aged, false);
+    }
+
+    let context = manager.active_context_mut();
+    let mut summary = ReviewSummary::default();
+
+    for (index, id) in ids.iter().enumerate() {
+        let task = context.find_task(id).expect("queued from this context");
+        eprintln!();
+        eprintln!(
+            "{} {}",
+            format!("({}/{})", index + 1, ids.len()).dimmed(),
+            format_task_line(task)
+        );
+
+        let created = days_ago(&task.created_at, now)
+            .map(|days| format!("created {} days ago", days))
+            .unwrap_or_else(|| format!("created {}", task.created_at));
+        let reviewed = match task.reviewed_at.as_deref().map(|at| days_ago(at, now)) {
+            Some(Some(days)) => format!("last reviewed {} days ago", days),
+            Some(None) | None => "never reviewed".to_string(),
+        };
+        eprintln!("      {}", format!("{} · {}", created, reviewed).dimmed());
+
+        let too_old = aged
+            .tasks
+            .iter()
+            .find(|old| old.id == *id && old.context == context.name);
+        if let Some(old) = too_old {
+            let note = match old.action {
+                AgingAction::Flagged => format!("{} days in its horizon", old.days),
+                AgingAction::Promoted(_) => {
+                    format!("moved here after {} days in its old horizon", old.days)
+                }
+            };
+            eprintln!(
+                "      {} {}, over the {}-day limit",
+                "!".yellow().bold(),
+                note,
+                old.limit
+            );
+        }
+
+        let Some(action) = ask_review_action()? else {
+            summary.remaining = ids.len() - index;
+            break;
+        };
+        review::apply(context, id, action, now)?;
+        summary.record(action);
+    }
+
+    if !output.is_text() {
+        return emit(
+            output,
+            &ReviewOutput {
+                context: &context.name,
+                aged: aged.tasks.iter().map(AgedTaskOutput::new).collect(),
+                summary: &summary,
+            },
+        );
+    }
+
+    eprintln!();
+    display_review_summary(&context.name, &summary);
+
+    Ok(())
+}
+
+/// Whole days from an RFC 3339 timestamp to `now`, None if it can't be parsed
+fn days_ago(timestamp: &str, now: chrono::DateTime<chrono::Utc>) -> Option<i64> {
+    let then = chrono::DateTime::parse_from_rfc3339(timestamp).ok()?;
+    Some((now - then.with_timezone(&chrono::Utc)).num_days())
+}
+
+/// Asks what to do with the task under review
+///
+/// Reads answers from stdin until one is valid. Returns None when the user
+/// quits or stdin is closed.
+fn ask_review_action() -> Result<Option<ReviewAction>> {
+    loop {
+        eprint!(
+            "  {} ",
+            "[k]eep [c]omplete [d]elete [h <horizon>] [p <priority>] de[f]er [s]kip [q]uit:".bold()
+        );
+        let mut answer = String::new();
+        if std::io::stdin().read_line(&mut answer)? == 0 {
+            eprintln!();
+            return Ok(None);
+        }
+        if matches!(answer.trim().to_lowercase().as_str(), "q" | "quit") {
+            return Ok(None);
+        }
+        match answer.parse() {
+            Ok(action) => return Ok(Some(action)),
+            Err(e) => eprintln!("  {}", e),
+        }
+    }
+}
+
+/// The tasks a complete, edit or delete command acts on
+///
+/// Built by select_tasks() before anything changes.
+struct Selection {
+    /// Full IDs of the tasks to change; empty when nothing matched or the
+    /// user said no
+    ids: Vec<String>,
+
+    /// Whether several tasks could be affected (several IDs or a filter).
+    /// Bulk commands print a JSON array; single-task ones keep printing the
+    /// task object on its own.
+    bulk: bool,
+}
+
+/// Resolves the IDs or `--where` filter of a task command, confirming bulk changes
+///
+/// This function demonstrates:
+/// - Reusing FromStr parsing for a small expression language
+/// - Interactive confirmation with an escape hatch for scripts
+///
+/// # Arguments
+///
+/// * `manager` - The ContextManager; tasks are selected from the active context
+/// * `ids` - Partial task IDs (empty when a filter is given)
+/// * `filter` - Filter expression from `--where`, e.g. "horizon:short priority:low"
+/// * `verb` - What will happen to the tasks, for the prompt ("Complete")
+/// * `yes` - Skip the confirmation
+///
+/// # Returns
+///
+/// The selected tasks, or an error if an ID is unknown or ambiguous or the
+/// filter doesn't parse. Nothing is selected if the user declines.
+///
+/// # Confirmation
+///
+/// When several tasks could be affected, they are listed on stderr and the
+/// user is asked `Complete these 3 tasks? [y/N]`. If stdin is closed before
+/// an answer, the command fails rather than guessing; scripts pass `--yes`.
+fn select_tasks(
+    manager: &ContextManager,
+    ids: &[String],
+    filter: Option<&str>,
+    verb: &str,
+    yes: bool,
+) -> Result<Selection> {
+    let context = manager.active_context();
+
+    let (selected, bulk) = match filter {
+        Some(filter) => {
+            let filter = Filter::from_str(filter)?;
+            let selected: Vec<String> = context
+                .tasks
+                .iter()
+                .filter(|task| filter.matches(task))
+                .map(|task| task.id.clone())
+                .collect();
+            (selected, true)
+        }
+        None => {
+            let mut selected: Vec<String> = Vec::new();
+            for id in ids {
+                let full_id = find_task_id_by_partial(context, id)?;
+                // The same task named twice (e.g. by full and short ID) counts once
+                if !selected.contains(&full_id) {
+                    selected.push(full_id);
+                }
+            }
+            (selected, ids.len() > 1)
+        }
+    };
+
+    if !bulk || yes {
+        return Ok(Selection {
+            ids: selected,
+            bulk,
+        });
+    }
+    if selected.is_empty() {
+        eprintln!("No tasks match.");
+        return Ok(Selection {
+            ids: selected,
+            bulk,
+        });
+    }
+
+    // Preview on stderr, so JSON output on stdout stays parseable
+    for id in &selected {
+        if let Some(task) = context.find_task(id) {
+            eprintln!("  {}", format_task_line(task));
+        }
+    }
+    let noun = if selected.len() == 1 { "task" } else { "tasks" };
+    eprint!("{} these {} {}? [y/N] ", verb, selected.len(), noun);
+
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer)? == 0 {
+        eprintln!();
+        return Err(AppError::InvalidRequest(
+            "no answer to the confirmation (pass --yes to skip it)".to_string(),
+        ));
+    }
+    if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+        eprintln!("Cancelled.");
+        return Ok(Selection {
+            ids: Vec::new(),
+            bulk,
+        });
+    }
+
+    Ok(Selection {
+        ids: selected,
+        bulk,
+    })
+}
+
+/// Prints the tasks changed by a complete, edit or delete command
+///
+/// Text output is one line per task; JSON output is the task object for a
+/// single-task command and an array for a bulk one.
+fn emit_changed(
+    output: OutputFormat,
+    selection: &Selection,
+    tasks: &[TaskOutput],
+    message: &str,
+) -> Result<()> {
+    if !output.is_text() {
+        return match tasks {
+            [task] if !selection.bulk => emit(output, task),
+            _ => emit_list(output, tasks),
+        };
+    }
+
+    for task in tasks {
+        println!(
+            "{} {}: {}",
+            "✓".green().bold(),
+            message,
+            task.task.description.dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+/// Handles the Complete command - marks tasks as done
+///
+/// This function demonstrates:
+/// - Working on a selection made up front
+/// - Modifying task state
+/// - User feedback
+///
+/// # Arguments
+///
+/// * `manager` - Mutable reference to the ContextManager
+/// * `selection` - The tasks to complete, from select_tasks()
+/// * `output` - Output format; JSON formats print the completed tasks
+///
+/// # Returns
+///
+/// Ok(()) once the tasks are marked complete.
+///
+/// # Requirements
+///
+/// This function satisfies:
+/// - Requirement 2.3: Mark task as complete
+/// - Requirement 2.4: Return error if task not found (in select_tasks)
+fn handle_complete(
+    manager: &mut ContextManager,
+    selection: Selection,
+    output: OutputFormat,
+) -> Result<()> {
+    // Get the active context
+    let context = manager.active_context_mut();
+
+    // Mark the tasks as complete
+    for id in &selection.ids {
+        if let Some(task) = context.find_task_mut(id) {
+            task.mark_complete();
+        }
+    }
+
+    let completed: Vec<TaskOutput> = selection
+        .ids
+        .iter()
+        .filter_map(|id| context.find_task(id))
+        .map(|task| TaskOutput::new(task, &context.name))
+        .collect();
+    emit_changed(output, &selection, &completed, "Task completed")
+}
+
+/// Handles the Edit command - modifies task properties
+///
+/// This function demonstrates:
+/// - Optional parameter handling with Option<T>
+/// - Conditional updates based on provided values
+/// - User feedback showing what changed
+///
+/// # Arguments
+///
+/// * `manager` - Mutable reference to the ContextManager
+/// * `selection` - The tasks to edit, from select_tasks()
+/// * `description` - Optional new description
+/// * `horizon` - Optional new time horizon
+/// * `priority` - Optional new priority
+/// * `due` - Optional new due date; Some(None) removes it
+/// * `output` - Output format; JSON formats print the updated tasks
+///
+/// # Returns
+///
+/// Ok(()) once the tasks are edited.
+///
+/// # Requirements
+///
+/// This function satisfies:
+/// - Requirement 2.1: Allow modification of task properties
+/// - Requirement 2.2: Update time horizon and persist
+/// - Requirement 2.4: Return error if task not found (in select_tasks)
+fn handle_edit(
+    manager: &mut ContextManager,
+    selection: Selection,
+    description: Option<String>,
+    horizon: Option<TimeHorizon>,
+    priority: Option<Priority>,
+    due: Option<Option<chrono::NaiveDate>>,
+    output: OutputFormat,
+) -> Result<()> {
+    // Get the active context
+    let context = manager.active_context_mut();
+
+    // Update the tasks with the provided values
+    for id in &selection.ids {
+        if let Some(task) = context.find_task_mut(id) {
+            task.update(description.clone(), horizon, priority, due);
+        }
+    }
+
+    let updated: Vec<TaskOutput> = selection
+        .ids
+        .iter()
+        .filter_map(|id| context.find_task(id))
+        .map(|task| TaskOutput::new(task, &context.name))
+        .collect();
+    emit_changed(output, &selection, &updated, "Task updated")
+}
+
+/// Handles the Delete command - removes tasks
+///
+/// This function demonstrates:
+/// - Removing items from collections
+/// - Confirmation messages
+///
+/// # Arguments
+///
+/// * `manager` - Mutable reference to the ContextManager
+/// * `selection` - The tasks to delete, from select_tasks()
+/// * `output` - Output format; JSON formats print the deleted tasks
+///
+/// # Returns
+///
+/// Ok(()) once the tasks are deleted.
+///
+/// # Requirements
+///
+/// This function satisfies:
+/// - Requirement 2.5: Delete task permanently
+/// - Requirement 2.4: Return error if task not found (in select_tasks)
+fn handle_delete(
+    manager: &mut ContextManager,
+    selection: Selection,
+    output: OutputFormat,
+) -> Result<()> {
+    // Get the active context
+    let context = manager.active_context_mut();
+
+    // Remove the tasks and keep them (for displaying confirmation)
+    let mut removed = Vec::new();
+    for id in &selection.ids {
+        removed.push(context.delete_task(id)?);
+    }
+
+    let deleted: Vec<TaskOutput> = removed
+        .iter()
+        .map(|task| TaskOutput::new(task, &context.name))
+        .collect();
+    emit_changed(output, &selection, &deleted, "Task deleted")
+}
+
+/// Handles the Move command - changes a task's place in the manual order
+///
+/// # Arguments
+///
+/// * `manager` - Mutable reference to the ContextManager
+/// * `id` - Task ID (can be partial)
+/// * `direction` - Where to move the task among the tasks of its horizon and priority
+/// * `output` - Output format; JSON formats print the moved task
+///
+/// # Returns
+///
+/// Ok(()) if the task was found, or an error if not found or ambiguous.
+/// Moving a task that is already at the top (or bottom) is not an error.
+fn handle_move(
+    manager: &mut ContextManager,
+    id: String,
+    direction: MoveDirection,
+    output: OutputFormat,
+) -> Result<()> {
+    let context = manager.active_context_mut();
+    let full_id = find_task_id_by_partial(context, &id)?;
+    let (position, count) = context.move_task(&full_id, direction)?;
+
+    let task = context
+        .find_task(&full_id)
+        .ok_or_else(|| AppError::TaskNotFound(id.clone()))?;
+
+    if !output.is_text() {
+        return emit(output, &TaskOutput::new(task, &context.name));
+    }
+
+    // The position among the tasks it is listed with: `todo list` sorts each
+    // horizon by priority first
+    println!(
+        "{} Task {} is now {} of {} {} priority tasks in its horizon",
+        "✓".green().bold(),
+        task.id[..6].cyan(),
+        (position + 1).to_string().bold(),
+        count,
+        format!("{:?}", task.priority).to_lowercase()
+    );
+    println!("  {}", task.description.dimmed());
+
+    Ok(())
+}
+
+/// Handles the Context command - manages contexts
+///
+/// This function demonstrates:
+/// - Nested command handling with pattern matching
+/// - Context operations (create, switch, list, delete)
+/// - User feedback for each operation
+///
+/// # Arguments
+///
+/// * `manager` - Mutable reference to the ContextManager
+/// * `action` - The context action to perform
+/// * `output` - Output format; JSON formats print context objects
+///
+/// # Returns
+///
+/// Ok(()) if the operation succeeded, or an error if it failed.
+///
+/// # Requirements
+///
+/// This function satisfies:
+/// - Requirement 5.1: Create new context
+/// - Requirement 5.2: Switch to different context
+/// - Requirement 5.3: List all contexts
+/// - Requirement 5.4: Reject duplicate context names
+/// - Requirement 5.5: Delete context and tasks
+fn handle_context(
+    manager: &mut ContextManager,
+    action: ContextAction,
+    output: OutputFormat,
+) -> Result<()> {
+    match action {
+        ContextAction::New { name } => {
+            // Create a new context
+            manager.create_context(name.clone())?;
+
+            if !output.is_text() {
+                let context = &manager.contexts[&name];
+                return emit(output, &ContextOutput::new(context, false));
+            }
+
+            println!(
+                "{} Context created: {}",
+                "✓".green().bold(),
+                name.cyan().bold()
+            );
+        }
+        ContextAction::Switch { name } => {
+            // Switch to a different context
+            manager.switch_context(&name)?;
+
+            if !output.is_text() {
+                return emit(output, &ContextOutput::new(manager.active_context(), true));
+            }
+
+            // Get task count for the new context
+            let task_count = manager.active_context().tasks.len();
+
+            println!(
+                "{} Switched to context: {} ({} tasks)",
+                "✓".green().bold(),
+                name.cyan().bold(),
+                task_count
+            );
+        }
+        ContextAction::List => {
+            if !output.is_text() {
+                // Sort by name so scripts get a stable order
+                let mut contexts: Vec<_> = manager.contexts.values().collect();
+                contexts.sort_by(|a, b| a.name.cmp(&b.name));
+
+                let items: Vec<ContextOutput> = contexts
+                    .into_iter()
+                    .map(|c| ContextOutput::new(c, c.name == manager.active_context))
+                    .collect();
+                return emit_list(output, &items);
+            }
+
+            // List all contexts
+            let context_names = manager.list_contexts();
+            display_contexts(&context_names, &manager.active_context);
+        }
+        ContextAction::Delete { name } => {
+            // Keep a copy of the context so JSON output can describe what was removed
+            let removed = manager.contexts.get(&name).cloned();
+
+            // Delete a context
+            manager.delete_context(&name)?;
+
+            if let (false, Some(context)) = (output.is_text(), removed) {
+                return emit(output, &ContextOutput::new(&context, false));
+            }
+
+            println!("{} Context deleted: {}", "✓".green().bold(), name.dimmed());
+        }
+    }
+
+    Ok(())
+}
+
+/// Handles the View command - saves, lists, deletes and runs views
+///
+/// This function demonstrates:
+/// - An optional nested subcommand next to a positional argument
+/// - Keeping data that isn't part of the main data file (views.json)
+///
+/// # Arguments
+///
+/// * `store` - Reference to the Store, which keeps the views file
+/// * `manager` - Reference to the ContextManager
+/// * `action` - The view action, or None to run or list views
+/// * `name` - Name of the view to run, when there is no action
+/// * `output` - Output format; JSON formats print view objects, or the
+///   view's tasks when running one
+///
+/// # Returns
+///
+/// Ok(()) if the operation succeeded, or an error if the view doesn't
+/// exist or can't be saved.
+fn handle_view(
+    store: &Store,
+    manager: &ContextManager,
+    action: Option<ViewAction>,
+    name: Option<String>,
+    output: OutputFormat,
+) -> Result<()> {
+    let mut views = store.load_views()?;
+
+    match (action, name) {
+        (
+            Some(ViewAction::Save {
+                name,
+                filter,
+                sort,
+                context,
+            }),
+            _,
+        ) => {
+            if let Some(context) = &context {
+                if !manager.contexts.contains_key(context) {
+                    return Err(AppError::ContextNotFound(context.clone()));
+                }
+            }
+            let view = View::new(&name, &filter, sort.as_deref(), context.as_deref())?;
+            let replaced = views.save(view.clone());
+            store.save_views(&views)?;
+
+            if !output.is_text() {
+                return emit(output, &view);
+            }
+
+            let verb = if replaced { "updated" } else { "saved" };
+            let scope = match &view.context {
+                Some(context) => format!("for context {}", context.cyan()),
+                None => "globally".to_string(),
+            };
+            println!(
+                "{} View {}: {} ({})",
+                "✓".green().bold(),
+                verb,
+                name.cyan().bold(),
+                scope
+            );
+        }
+        (Some(ViewAction::Delete { name }), _) => {
+            let removed = views.remove(&name, &manager.active_context)?;
+            store.save_views(&views)?;
+
+            if !output.is_text() {
+                return emit(output, &removed);
+            }
+
+            println!("{} View deleted: {}", "✓".green().bold(), name.dimmed());
+        }
+        (Some(ViewAction::List), _) | (None, None) => {
+            let visible = views.visible(&manager.active_context);
+
+            if !output.is_text() {
+                return emit_list(output, &visible);
+            }
+
+            display_views(&visible);
+        }
+        (None, Some(name)) => {
+            let view = views
+                .find(&name, &manager.active_context)
+                .ok_or_else(|| AppError::ViewNotFound(name.clone()))?;
+            let context = manager.active_context();
+            let tasks = view.select(context, &store.load_config()?.urgency)?;
+
+            if !output.is_text() {
+                let items: Vec<TaskOutput> = tasks
+                    .iter()
+                    .map(|task| TaskOutput::new(task, &context.name))
+                    .collect();
+                return emit_list(output, &items);
+            }
+
+            if tasks.is_empty() {
+                println!("{}", "No tasks match.".dimmed());
+            }
+            for task in &tasks {
+                println!("  {}", format_task_line(task));
+            }
+
+            println!();
+            println!(
+                "{} View: {} ({}) · Context: {}",
+                "ℹ".cyan(),
+                view.name.cyan().bold(),
+                view.filter.dimmed(),
+                context.name.cyan().bold()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds a saved view by name, as seen from the active context
+fn find_view(store: &Store, manager: &ContextManager, name: &str) -> Result<View> {
+    store
+        .load_views()?
+        .find(name, &manager.active_context)
+        .cloned()
+        .ok_or_else(|| AppError::ViewNotFound(name.to_string()))
+}
+
+/// Handles the Export command - exports data to a file
+///
+/// This function demonstrates:
+/// - File I/O operations
+/// - Using the store module for export
+/// - User feedback with file path
 ///
 /// # Arguments
 ///
 /// * `store` - Reference to the Store
 /// * `manager` - Reference to the ContextManager
 /// * `path` - Path where to export the data
+/// * `format` - File format string (native, taskwarrior)
+/// * `filter` - Which contexts and tasks to include
+/// * `output` - Output format; JSON formats print an action summary
 ///
 /// # Returns
 ///
@@ -543,9 +1611,43 @@ fn handle_context(manager: &mut ContextManager, action: ContextAction) -> Result
 /// This function satisfies:
 /// - Requirement 4.5: Export tasks to JSON file
 /// - Requirement 6.1: Create JSON file with all contexts and tasks
-fn handle_export(store: &Store, manager: &ContextManager, path: PathBuf) -> Result<()> {
-    // Export the data to the specified file
-    store.export(manager, &path)?;
+fn handle_export(
+    store: &Store,
+    manager: &ContextManager,
+    path: PathBuf,
+    format: String,
+    filter: &ExportFilter,
+    output: OutputFormat,
+) -> Result<()> {
+    let format = DataFormat::from_str(&format)?;
+
+    // Narrow the data down to the selected subset; the export functions
+    // then write it exactly as they would write the full data
+    let subset;
+    let manager = if filter.is_empty() {
+        manager
+    } else {
+        subset = filter.apply(manager)?;
+        &subset
+    };
+
+    // Export the data to the specified file in the requested format
+    match format {
+        DataFormat::Native => store.export(manager, &path)?,
+        DataFormat::Taskwarrior => store.export_taskwarrior(manager, &path)?,
+    }
+
+    if !output.is_text() {
+        return emit(
+            output,
+            &ActionOutput {
+                action: "export",
+                path: path.display().to_string(),
+                contexts: manager.contexts.len(),
+                tasks: manager.contexts.values().map(|c| c.tasks.len()).sum(),
+            },
+        );
+    }
 
     // Display success message with the file path
     println!(
@@ -570,7 +1672,10 @@ fn handle_export(store: &Store, manager: &ContextManager, path: PathBuf) -> Resu
 /// * `store` - Reference to the Store
 /// * `manager` - Mutable reference to the ContextManager
 /// * `path` - Path to the file to import
-/// * `merge` - Whether to merge with existing data
+/// * `format` - File format string (native, taskwarrior)
+/// * `strategy` - How to merge with existing data, or None to replace it
+/// * `dry_run` - Print what would change instead of changing it
+/// * `output` - Output format; JSON formats print an action summary
 ///
 /// # Returns
 ///
@@ -587,62 +1692,116 @@ fn handle_import(
     store: &Store,
     manager: &mut ContextManager,
     path: PathBuf,
-    merge: bool,
+    format: String,
+    strategy: Option<MergeStrategy>,
+    dry_run: bool,
+    output: OutputFormat,
 ) -> Result<()> {
     // Import the data from the specified file
     // This validates the JSON structure and returns a new ContextManager
-    let imported_manager = store.import(&path)?;
-
-    if merge {
-        // Merge the imported data with existing data
-        let mut added_contexts = 0;
-        let mut added_tasks = 0;
-
-        #[allow(clippy::map_entry)]
-        for (context_name, context) in imported_manager.contexts {
-            if manager.contexts.contains_key(&context_name) {
-                // Context already exists - rename the imported one
-                let mut new_name = format!("{}-imported", context_name);
-                let mut counter = 1;
-
-                // Find a unique name by adding a counter
-                while manager.contexts.contains_key(&new_name) {
-                    counter += 1;
-                    new_name = format!("{}-imported-{}", context_name, counter);
+    let imported_manager = match DataFormat::from_str(&format)? {
+        DataFormat::Native => {
+            // Stream the file so very large exports don't have to fit in
+            // memory twice; progress goes to stderr so it never mixes with
+            // JSON output, and only when someone is watching
+            let show_progress = output.is_text() && std::io::stderr().is_terminal();
+            let mut shown = false;
+
+            let imported = store.import_streaming(&path, |progress| {
+                if show_progress && progress.tasks > 0 && progress.tasks % 10_000 == 0 {
+                    shown = true;
+                    match progress.percent() {
+                        Some(percent) => eprint!(
+                            "\r{} Importing... {}% ({} tasks)",
+                            "⟳".cyan(),
+                            percent,
+                            progress.tasks
+                        ),
+                        None => eprint!("\r{} Importing... {} tasks", "⟳".cyan(), progress.tasks),
+                    }
                 }
+            });
 
-                // Create the context with the new name
-                manager.create_context(new_name.clone())?;
-
-                // Add all tasks from the imported context
-                let new_context = manager.contexts.get_mut(&new_name).unwrap();
-                for task in context.tasks {
-                    added_tasks += 1;
-                    new_context.add_task(task);
-                }
+            // Move past the progress line before printing anything else
+            if shown {
+                eprintln!();
+            }
 
-                added_contexts += 1;
+            imported?
+        }
+        DataFormat::Taskwarrior => {
+            let (imported, report) = store.import_taskwarrior(&path)?;
 
+            // Tell the user about Taskwarrior data we had no place for
+            if output.is_text() && report.skipped_deleted > 0 {
+                println!(
+                    "{} Skipped {} deleted Taskwarrior tasks",
+                    "ℹ".yellow(),
+                    report.skipped_deleted
+                );
+            }
+            if output.is_text() && report.dropped_annotations > 0 {
                 println!(
-                    "{} Context '{}' renamed to '{}' (name conflict)",
+                    "{} Dropped {} annotations (not supported)",
                     "ℹ".yellow(),
-                    context_name.dimmed(),
-                    new_name.cyan()
+                    report.dropped_annotations
                 );
+            }
+
+            imported
+        }
+    };
+
+    // Counts for the final summary, filled in by whichever branch runs
+    let (context_count, task_count) = if let Some(strategy) = strategy {
+        if dry_run {
+            // Merge into a copy so the preview is exactly what a real run would do
+            let mut preview = manager.clone();
+            let report = merge(&mut preview, imported_manager, strategy)?;
+
+            if output.is_text() {
+                display_merge_report(&report);
             } else {
-                // Context doesn't exist - add it directly
-                added_tasks += context.tasks.len();
-                manager.contexts.insert(context_name, context);
-                added_contexts += 1;
+                emit(output, &report)?;
             }
+            return Ok(());
         }
 
-        println!(
-            "{} Imported {} contexts and {} tasks",
-            "✓".green().bold(),
-            added_contexts,
-            added_tasks
-        );
+        let report = merge(manager, imported_manager, strategy)?;
+
+        if output.is_text() {
+            for change in &report.changes {
+                if let MergeChange::RenameContext {
+                    context,
+                    renamed_to,
+                    ..
+                } = change
+                {
+                    println!(
+                        "{} Context '{}' renamed to '{}' (name conflict)",
+                        "ℹ".yellow(),
+                        context.dimmed(),
+                        renamed_to.cyan()
+                    );
+                }
+            }
+            println!(
+                "{} Imported {} contexts and {} tasks",
+                "✓".green().bold(),
+                report.added_contexts(),
+                report.added_tasks()
+            );
+            if report.updated_tasks() > 0 || report.conflicts() > 0 {
+                println!(
+                    "{} Updated {} tasks, kept local values in {} conflicts",
+                    "ℹ".yellow(),
+                    report.updated_tasks(),
+                    report.conflicts()
+                );
+            }
+        }
+
+        (report.added_contexts(), report.added_tasks())
     } else {
         // Replace existing data with imported data
         let context_count = imported_manager.contexts.len();
@@ -652,54 +1811,433 @@ fn handle_import(
             .map(|c| c.tasks.len())
             .sum();
 
+        if dry_run {
+            if output.is_text() {
+                println!(
+                    "{} Would replace existing data with {} contexts and {} tasks",
+                    "ℹ".yellow(),
+                    context_count,
+                    task_count
+                );
+            } else {
+                emit(
+                    output,
+                    &ActionOutput {
+                        action: "import",
+                        path: path.display().to_string(),
+                        contexts: context_count,
+                        tasks: task_count,
+                    },
+                )?;
+            }
+            return Ok(());
+        }
+
         *manager = imported_manager;
 
+        if output.is_text() {
+            println!(
+                "{} Imported {} contexts and {} tasks (replaced existing data)",
+                "✓".green().bold(),
+                context_count,
+                task_count
+            );
+        }
+
+        (context_count, task_count)
+    };
+
+    if !output.is_text() {
+        return emit(
+            output,
+            &ActionOutput {
+                action: "import",
+                path: path.display().to_string(),
+                contexts: context_count,
+                tasks: task_count,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+/// Handles the Sync command - merges with another data file both ways
+///
+/// This function demonstrates:
+/// - Coordinating three files (local, remote and the stored base)
+/// - Passing a closure that makes decisions for library code
+/// - Interactive prompts on the terminal
+///
+/// # Arguments
+///
+/// * `store` - Reference to the Store (also keeps the sync base)
+/// * `manager` - Mutable reference to the ContextManager, replaced by the merged data
+/// * `path` - Path of the other data file
+/// * `policy` - How to resolve conflicts
+/// * `output` - Output format; JSON formats print the sync report
+///
+/// # Returns
+///
+/// Ok(()) if both files were synced. On error (including an unresolved
+/// conflict), neither file is changed.
+fn handle_sync(
+    store: &Store,
+    manager: &mut ContextManager,
+    path: PathBuf,
+    policy: SyncPolicy,
+    output: OutputFormat,
+) -> Result<()> {
+    let remote_store = Store::new(path.clone());
+    let remote = remote_store.load()?;
+    let base = store.load_sync_base(&path)?;
+
+    // Prompts go to stderr so they never mix with JSON on stdout
+    let interactive = std::io::stdin().is_terminal() && std::io::stderr().is_terminal();
+    let (merged, report) = sync(base.as_ref(), manager, &remote, |conflict| {
+        match policy.resolve(conflict) {
+            Some(side) => Ok(side),
+            None if interactive => ask_sync_conflict(conflict),
+            None => Err(AppError::SyncConflict(format!(
+                "{} of task {} differs (use --resolve ours, theirs or newest)",
+                conflict.field,
+                conflict.id.get(..6).unwrap_or(&conflict.id)
+            ))),
+        }
+    })?;
+
+    // Write the other file, keeping its own active context, and the new
+    // base; run() saves the local file
+    let mut remote_result = merged.clone();
+    if merged.contexts.contains_key(&remote.active_context) {
+        remote_result.active_context = remote.active_context.clone();
+    }
+    remote_store.save(&remote_result)?;
+    store.save_sync_base(&path, &merged)?;
+    *manager = merged;
+
+    if !output.is_text() {
+        return emit(
+            output,
+            &SyncOutput {
+                path: path.display().to_string(),
+                report: &report,
+            },
+        );
+    }
+
+    println!(
+        "{} Synced with: {}",
+        "✓".green().bold(),
+        path.display().to_string().cyan()
+    );
+    display_sync_report(&report);
+
+    Ok(())
+}
+
+/// Asks the user which side of a sync conflict to keep
+///
+/// Reads answers from stdin until one is valid: `l` (local) or `r` (remote).
+fn ask_sync_conflict(conflict: &SyncConflict) -> Result<Side> {
+    eprintln!(
+        "{} Conflict in [{}] {}: {}",
+        "!".red().bold(),
+        conflict.id.get(..6).unwrap_or(&conflict.id).dimmed(),
+        conflict.description,
+        conflict.field.bold()
+    );
+    eprintln!(
+        "    local:  {} {}",
+        conflict.local,
+        format!("({})", conflict.local_modified).dimmed()
+    );
+    eprintln!(
+        "    remote: {} {}",
+        conflict.remote,
+        format!("({})", conflict.remote_modified).dimmed()
+    );
+
+    loop {
+        eprint!("  Keep (l)ocal or (r)emote? ");
+        let mut answer = String::new();
+        if std::io::stdin().read_line(&mut answer)? == 0 {
+            // stdin closed mid-sync: nothing has been written yet
+            return Err(AppError::SyncConflict(conflict.id.clone()));
+        }
+        match answer.trim().to_lowercase().as_str() {
+            "l" | "local" => return Ok(Side::Local),
+            "r" | "remote" => return Ok(Side::Remote),
+            _ => {}
+        }
+    }
+}
+
+/// Handles the 'shell' command
+///
+/// Reads commands until `exit`, `quit` or Ctrl-D and runs each through
+/// dispatch() against the loaded data. A failing command prints its error
+/// and the shell carries on. run() saves the data once the shell ends;
+/// `save` saves (and commits, in git-backed mode) in between.
+fn handle_shell(store: &Store, manager: &mut ContextManager, repo: Option<&GitRepo>) -> Result<()> {
+    let mut editor: Editor<ShellHelper, DefaultHistory> = Editor::new().map_err(readline_error)?;
+    editor.set_helper(Some(ShellHelper::new()));
+
+    // There is no history file before the first session
+    let history = store.file_path().with_file_name(shell::HISTORY_FILE);
+    let _ = editor.load_history(&history);
+
+    println!(
+        "{}",
+        "Type commands without `todo`; `help` lists them, `save` saves, `exit` quits.".dimmed()
+    );
+
+    loop {
+        if let Some(helper) = editor.helper_mut() {
+            helper.refresh(manager);
+        }
+
+        let line = match editor.readline(&format!("{}> ", manager.active_context)) {
+            Ok(line) => line,
+            // Ctrl-C abandons the current line, like in other shells
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(error) => return Err(readline_error(error)),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line);
+
+        match line {
+            "exit" | "quit" => break,
+            "save" => {
+                store.save(manager)?;
+                if let Some(repo) = repo {
+                    repo.commit_all("shell: save")?;
+                }
+                println!("{} Saved", "✓".green().bold());
+            }
+            line => {
+                if let Err(error) = run_shell_line(store, manager, repo, line) {
+                    eprintln!("{} {}", "Error:".red().bold(), error);
+                }
+            }
+        }
+    }
+
+    // History is a convenience; failing to write it must not stop the data
+    // from being saved
+    if let Some(dir) = history.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let _ = editor.save_history(&history);
+    Ok(())
+}
+
+/// Parses and runs one shell line
+fn run_shell_line(
+    store: &Store,
+    manager: &mut ContextManager,
+    repo: Option<&GitRepo>,
+    line: &str,
+) -> Result<()> {
+    let Some(cli) = shell::parse_line(line)? else {
+        return Ok(());
+    };
+    let output = OutputFormat::from_str(&cli.output)?;
+    dispatch(store, manager, repo, cli.command, output)
+}
+
+/// Converts a line editor failure (e.g., no usable terminal) to an AppError
+fn readline_error(error: ReadlineError) -> AppError {
+    match error {
+        ReadlineError::Io(error) => AppError::IoError(error),
+        error => AppError::IoError(std::io::Error::other(error.to_string())),
+    }
+}
+
+/// Handles the 'serve' command
+///
+/// Runs the REST API on `bind` until the process is stopped. The server gets
+/// its own Store for the same file, shared between its worker threads.
+fn handle_serve(store: &Store, bind: &str, output: OutputFormat) -> Result<()> {
+    let server_store = Store::new(store.file_path().to_path_buf());
+
+    if output.is_text() {
         println!(
-            "{} Imported {} contexts and {} tasks (replaced existing data)",
+            "{} Serving on {} (Ctrl-C to stop)",
             "✓".green().bold(),
-            context_count,
-            task_count
+            format!("http://{}", bind).cyan()
         );
     }
 
-    Ok(())
+    server::serve(bind, server_store)
 }
 
-/// Helper function to find a task by partial ID matching
+/// Handles the Git command - git-backed storage
 ///
-/// This function searches for a task whose ID starts with the provided partial ID.
-/// This allows users to use shortened IDs (e.g., "abc123" instead of the full UUID).
+/// This function demonstrates:
+/// - Nested subcommands with match
+/// - Reloading state after the data file changed underneath us
 ///
 /// # Arguments
 ///
-/// * `context` - Mutable reference to the context to search
-/// * `partial_id` - The partial ID to match
+/// * `store` - Reference to the Store
+/// * `manager` - Mutable reference to the ContextManager (replaced on restore)
+/// * `repo` - The data directory's repository, if it is one
+/// * `action` - The git subcommand
+/// * `output` - Output format; JSON formats print log entries or an action summary
 ///
 /// # Returns
 ///
-/// A mutable reference to the matching task, or an error if not found or multiple matches.
+/// Ok(()) on success, or an error if git failed or the data directory is not
+/// a repository (except for init).
+fn handle_git(
+    store: &Store,
+    manager: &mut ContextManager,
+    repo: Option<&GitRepo>,
+    action: GitAction,
+    output: OutputFormat,
+) -> Result<()> {
+    let not_a_repo = || {
+        AppError::GitError(
+            "data directory is not a git repository (run `todo git init`)".to_string(),
+        )
+    };
+    let data_dir = store
+        .file_path()
+        .parent()
+        .unwrap_or_else(|| std::path::Path::new("."));
+
+    match action {
+        GitAction::Init => {
+            let repo = GitRepo::init(data_dir)?;
+
+            // Make sure the data file exists so the first commit has it
+            store.save(manager)?;
+            repo.commit_all("init: todo data")?;
+
+            if !output.is_text() {
+                return emit(
+                    output,
+                    &ActionOutput {
+                        action: "git init",
+                        path: repo.dir().display().to_string(),
+                        contexts: manager.contexts.len(),
+                        tasks: manager.contexts.values().map(|c| c.tasks.len()).sum(),
+                    },
+                );
+            }
+
+            println!(
+                "{} Git-backed storage enabled in: {}",
+                "✓".green().bold(),
+                repo.dir().display().to_string().cyan()
+            );
+        }
+        GitAction::Log { limit } => {
+            let entries = repo.ok_or_else(not_a_repo)?.log(limit)?;
+
+            if !output.is_text() {
+                return emit_list(output, &entries);
+            }
+
+            if entries.is_empty() {
+                println!("{}", "No commits yet.".dimmed());
+            }
+            for entry in entries {
+                println!(
+                    "{} {} {}",
+                    entry.rev.yellow(),
+                    entry.date.dimmed(),
+                    entry.message
+                );
+            }
+        }
+        GitAction::Restore { rev } => {
+            let repo = repo.ok_or_else(not_a_repo)?;
+            let file_name = store.file_path().file_name().ok_or_else(not_a_repo)?;
+            repo.restore(&rev, std::path::Path::new(file_name))?;
+
+            // The file on disk changed; reload it so run() saves the
+            // restored data rather than what was loaded before
+            *manager = store.load()?;
+
+            if !output.is_text() {
+                return emit(
+                    output,
+                    &ActionOutput {
+                        action: "git restore",
+                        path: store.file_path().display().to_string(),
+                        contexts: manager.contexts.len(),
+                        tasks: manager.contexts.values().map(|c| c.tasks.len()).sum(),
+                    },
+                );
+            }
+
+            println!("{} Restored data from {}", "✓".green().bold(), rev.yellow());
+        }
+    }
+
+    Ok(())
+}
+
+/// Handles the Report command - writes an HTML report
+///
+/// This function demonstrates:
+/// - Delegating rendering to a library module
+/// - Writing a generated file to disk
+/// - User feedback with file path
 ///
-/// # Error Cases
+/// # Arguments
 ///
-/// - No task found with matching ID prefix
-/// - Multiple tasks found with matching ID prefix (ambiguous)
-fn find_task_by_partial_id<'a>(
-    context: &'a mut rust_todo::context::Context,
-    partial_id: &str,
-) -> Result<&'a mut Task> {
-    // First, find the full ID by matching
-    let full_id = find_task_id_by_partial(context, partial_id)?;
+/// * `manager` - Reference to the ContextManager
+/// * `path` - Path where the HTML report should be written
+/// * `output` - Output format; JSON formats print an action summary
+///
+/// # Returns
+///
+/// Ok(()) if the report was written, or an error if the file can't be created.
+fn handle_report(manager: &ContextManager, path: PathBuf, output: OutputFormat) -> Result<()> {
+    // Stamp the report with the current UTC time
+    let generated_at = chrono::Utc::now().format("%Y-%m-%d %H:%M UTC").to_string();
+    let html = render_html(manager, &generated_at);
+
+    // Create the parent directory if it doesn't exist, like export does
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, html)?;
+
+    if !output.is_text() {
+        return emit(
+            output,
+            &ActionOutput {
+                action: "report",
+                path: path.display().to_string(),
+                contexts: manager.contexts.len(),
+                tasks: manager.contexts.values().map(|c| c.tasks.len()).sum(),
+            },
+        );
+    }
+
+    println!(
+        "{} Report written to: {}",
+        "✓".green().bold(),
+        path.display().to_string().cyan()
+    );
 
-    // Now find and return the mutable reference
-    context
-        .find_task_mut(&full_id)
-        .ok_or_else(|| AppError::TaskNotFound(partial_id.to_string()))
+    Ok(())
 }
 
 /// Helper function to find a full task ID by partial matching
 ///
-/// Similar to find_task_by_partial_id, but returns the full ID string instead
-/// of a reference to the task. This is useful when we need to remove the task.
+/// This function searches for a task whose ID starts with the provided partial ID.
+/// This allows users to use shortened IDs (e.g., "abc123" instead of the full UUID).
+/// It returns the full ID rather than a reference, so the caller can go on
+/// to change or remove the task.
 ///
 /// # Arguments
 ///
@@ -713,31 +2251,119 @@ fn find_task_id_by_partial(
     context: &rust_todo::context::Context,
     partial_id: &str,
 ) -> Result<String> {
-    // Find all tasks whose ID starts with the partial ID
-    let matches: Vec<String> = context
-        .tasks
-        .iter()
-        .filter(|task| task.id.starts_with(partial_id))
-        .map(|task| task.id.clone())
-        .collect();
+    context.resolve_id(partial_id)
+}
+
+/// What a changing command is about, for the git commit message
+///
+/// Captured before the command runs: a deleted task can only be described
+/// from the data as it was, and commands are consumed when they run.
+enum CommitSubject {
+    /// A task command: the verb and the partial ID (None for add, whose
+    /// task only exists afterwards)
+    Task(&'static str, Option<String>),
+
+    /// A task command on several tasks (several IDs or `--where`): the verb
+    Bulk(&'static str),
+
+    /// Any other change, already described
+    Other(String),
+}
+
+impl CommitSubject {
+    /// Returns the subject for commands that change data, None for read-only ones
+    fn of(command: &Commands) -> Option<CommitSubject> {
+        let task = |verb, ids: &[String], filter: &Option<String>| match (ids, filter) {
+            ([id], None) => Some(CommitSubject::Task(verb, Some(id.clone()))),
+            _ => Some(CommitSubject::Bulk(verb)),
+        };
+        match command {
+            Commands::Add { .. } => Some(CommitSubject::Task("add", None)),
+            Commands::Complete { ids, filter, .. } => task("complete", ids, filter),
+            Commands::Edit { ids, filter, .. } => task("edit", ids, filter),
+            Commands::Delete { ids, filter, .. } => task("delete", ids, filter),
+            Commands::Move { id, .. } => Some(CommitSubject::Task("move", Some(id.clone()))),
+            Commands::Age { dry_run: false } => Some(CommitSubject::Bulk("age")),
+            Commands::Review => Some(CommitSubject::Bulk("review")),
+            Commands::Context { action } => match action {
+                ContextAction::New { name } => Some(format!("context new: {}", name)),
+                ContextAction::Switch { name } => Some(format!("context switch: {}", name)),
+                ContextAction::Delete { name } => Some(format!("context delete: {}", name)),
+                ContextAction::List => None,
+            }
+            .map(CommitSubject::Other),
+            Commands::View {
+                action: Some(action),
+                ..
+            } => match action {
+                ViewAction::Save { name, .. } => Some(format!("view save: {}", name)),
+                ViewAction::Delete { name } => Some(format!("view delete: {}", name)),
+                ViewAction::List => None,
+            }
+            .map(CommitSubject::Other),
+            Commands::Import { path, dry_run, .. } if !dry_run => {
+                Some(CommitSubject::Other(format!("import: {}", path.display())))
+            }
+            Commands::Sync { path, .. } => {
+                Some(CommitSubject::Other(format!("sync: {}", path.display())))
+            }
+            // One commit for the whole session rather than one per keypress
+            Commands::Tui => Some(CommitSubject::Other("tui: session".to_string())),
+            Commands::Shell => Some(CommitSubject::Other("shell: session".to_string())),
+            Commands::Git {
+                action: GitAction::Restore { rev },
+            } => Some(CommitSubject::Other(format!("restore: {}", rev))),
+            _ => None,
+        }
+    }
 
-    // Check the number of matches
-    match matches.len() {
-        0 => {
-            // No matches found
-            Err(AppError::TaskNotFound(partial_id.to_string()))
-        }
-        1 => {
-            // Exactly one match - return it
-            Ok(matches[0].clone())
-        }
-        _ => {
-            // Multiple matches - ambiguous
-            Err(AppError::TaskNotFound(format!(
-                "Ambiguous ID '{}' matches multiple tasks: {}",
-                partial_id,
-                matches.join(", ")
-            )))
+    /// Builds the commit message, e.g. "complete: Write tests [59ead9]"
+    /// or "complete: 3 tasks"
+    fn message(&self, before: &ContextManager, after: &ContextManager) -> String {
+        let (verb, task) = match self {
+            CommitSubject::Other(message) => return message.clone(),
+            CommitSubject::Bulk(verb) => {
+                // Changed tasks were deleted, or got a new updated_at or
+                // reviewed_at. Aging can touch every context, so count them all.
+                let changed = before
+                    .contexts
+                    .values()
+                    .flat_map(|context| {
+                        let now = after.contexts.get(&context.name);
+                        context.tasks.iter().map(move |task| (now, task))
+                    })
+                    .filter(
+                        |(now, task)| match now.and_then(|c| c.find_task(&task.id)) {
+                            Some(now) => {
+                                now.updated_at != task.updated_at
+                                    || now.reviewed_at != task.reviewed_at
+                            }
+                            None => true,
+                        },
+                    )
+                    .count();
+                let noun = if changed == 1 { "task" } else { "tasks" };
+                return format!("{}: {} {}", verb, changed, noun);
+            }
+            CommitSubject::Task(verb, Some(id)) => {
+                let context = before.active_context();
+                let task = find_task_id_by_partial(context, id)
+                    .ok()
+                    .and_then(|full_id| context.find_task(&full_id));
+                (verb, task)
+            }
+            // add_task() appends, so the new task is the last one
+            CommitSubject::Task(verb, None) => (verb, after.active_context().tasks.last()),
+        };
+
+        match task {
+            Some(task) => format!(
+                "{}: {} [{}]",
+                verb,
+                task.description,
+                task.id.get(..6).unwrap_or(&task.id)
+            ),
+            None => verb.to_string(),
         }
     }
 }
@@ -0,0 +1,602 @@
+// Merge module - combines imported data with the existing contexts
+// This module demonstrates enums with data, HashMap indexing, and reporting changes as values
+//
+// `todo import --merge` used to have a single behaviour: when an imported context
+// had the same name as an existing one, the whole imported context was added under
+// a new name (`work-imported`, `work-imported-2`, ...). That is safe, but
+// re-importing the same backup duplicates every task each time.
+//
+// # Strategies
+//
+// - `rename` - the original behaviour: name clashes become `<name>-imported-N`
+// - `union`  - tasks are merged into the same-named context and deduplicated by
+//              ID. For tasks present on both sides, completion and tags are
+//              combined; for the other fields the local value is kept and the
+//              difference is reported as a conflict
+// - `theirs` - like union, but the imported values win every conflict
+// - `ours`   - like union, but the local values win every conflict
+// - `newest` - like union, but whichever copy was modified last wins
+//
+// For every strategy except `rename`, tasks are matched by ID across all
+// contexts, so a task that was moved to another context locally is updated
+// where it is instead of being duplicated.
+//
+// # Reporting
+//
+// merge() returns a MergeReport listing every change it made (or, for
+// `--dry-run`, would make). The CLI prints it as a diff or as JSON.
+
+use crate::context::ContextManager;
+use crate::error::{AppError, Result};
+use crate::task::{compare_timestamps, FieldChange, Task};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// How imported data is combined with existing data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// Add clashing contexts under a new name (the default)
+    #[default]
+    Rename,
+    /// Merge tasks by ID, combining completion and tags, keeping local values otherwise
+    Union,
+    /// Merge tasks by ID, imported values win conflicts
+    Theirs,
+    /// Merge tasks by ID, local values win conflicts
+    Ours,
+    /// Merge tasks by ID, the most recently modified copy wins conflicts
+    Newest,
+}
+
+impl FromStr for MergeStrategy {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rename" => Ok(MergeStrategy::Rename),
+            "union" => Ok(MergeStrategy::Union),
+            "theirs" => Ok(MergeStrategy::Theirs),
+            "ours" => Ok(MergeStrategy::Ours),
+            "newest" => Ok(MergeStrategy::Newest),
+            _ => Err(AppError::InvalidMergeStrategy(s.to_string())),
+        }
+    }
+}
+
+/// A single change made by a merge
+///
+/// Serialized with a `change` tag, e.g.
+/// `{"change":"add_task","context":"work","id":"...","description":"..."}`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum MergeChange {
+    /// A context that did not exist locally was created
+    AddContext { context: String },
+
+    /// An imported context clashed with a local one and was added under a new name
+    RenameContext {
+        context: String,
+        renamed_to: String,
+        tasks: usize,
+    },
+
+    /// A task that did not exist locally was added
+    AddTask {
+        context: String,
+        id: String,
+        description: String,
+    },
+
+    /// A local task took imported values; `from` is local, `to` is imported
+    UpdateTask {
+        context: String,
+        id: String,
+        fields: Vec<FieldChange>,
+    },
+
+    /// A local task differs from the imported copy and was left as is;
+    /// `from` is the kept local value, `to` the discarded imported one
+    Conflict {
+        context: String,
+        id: String,
+        fields: Vec<FieldChange>,
+    },
+}
+
+/// Everything a merge changed, in the order it happened
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MergeReport {
+    pub changes: Vec<MergeChange>,
+}
+
+impl MergeReport {
+    /// Number of contexts created, including renamed ones
+    pub fn added_contexts(&self) -> usize {
+        self.changes
+            .iter()
+            .filter(|c| {
+                matches!(
+                    c,
+                    MergeChange::AddContext { .. } | MergeChange::RenameContext { .. }
+                )
+            })
+            .count()
+    }
+
+    /// Number of tasks added, including those in renamed contexts
+    pub fn added_tasks(&self) -> usize {
+        self.changes
+            .iter()
+            .map(|c| match c {
+                MergeChange::AddTask { .. } => 1,
+                MergeChange::RenameContext { tasks, .. } => *tasks,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Number of existing tasks that took imported values
+    pub fn updated_tasks(&self) -> usize {
+        self.changes
+            .iter()
+            .filter(|c| matches!(c, MergeChange::UpdateTask { .. }))
+            .count()
+    }
+
+    /// Number of tasks whose differences were resolved in favour of local data
+    pub fn conflicts(&self) -> usize {
+        self.changes
+            .iter()
+            .filter(|c| matches!(c, MergeChange::Conflict { .. }))
+            .count()
+    }
+
+    /// Returns true if the merge changed nothing
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Merges imported contexts into the manager using the given strategy
+///
+/// The active context is never changed. Imported contexts are processed in
+/// name order so the report is stable between runs.
+///
+/// # Arguments
+///
+/// * `manager` - The existing data, modified in place
+/// * `imported` - The data read from the import file
+/// * `strategy` - How to resolve clashes (see the module documentation)
+///
+/// # Returns
+///
+/// A MergeReport describing every change. Run it against a clone of the
+/// manager to preview a merge without applying it.
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::context::ContextManager;
+/// use rust_todo::merge::{merge, MergeStrategy};
+/// use rust_todo::task::{Task, TimeHorizon, Priority};
+///
+/// let mut manager = ContextManager::new();
+/// manager.active_context_mut().add_task(
+///     Task::new("Plan".to_string(), TimeHorizon::ShortTerm, Priority::Low),
+/// );
+///
+/// // Re-importing the same data with union adds nothing
+/// let backup = manager.clone();
+/// let report = merge(&mut manager, backup, MergeStrategy::Union)?;
+/// assert!(report.is_empty());
+/// assert_eq!(manager.active_context().tasks.len(), 1);
+/// # Ok::<(), rust_todo::error::AppError>(())
+/// ```
+pub fn merge(
+    manager: &mut ContextManager,
+    imported: ContextManager,
+    strategy: MergeStrategy,
+) -> Result<MergeReport> {
+    let mut report = MergeReport::default();
+
+    let mut contexts: Vec<_> = imported.contexts.into_values().collect();
+    contexts.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if strategy == MergeStrategy::Rename {
+        for context in contexts {
+            merge_renaming(manager, context, &mut report)?;
+        }
+        return Ok(report);
+    }
+
+    // Where each existing task lives, so imported tasks can be matched by ID
+    // no matter which context they are in on either side
+    let mut locations: HashMap<String, String> = HashMap::new();
+    for (name, context) in &manager.contexts {
+        for task in &context.tasks {
+            locations.insert(task.id.clone(), name.clone());
+        }
+    }
+
+    for context in contexts {
+        if !manager.contexts.contains_key(&context.name) {
+            manager.create_context(context.name.clone())?;
+            report.changes.push(MergeChange::AddContext {
+                context: context.name.clone(),
+            });
+        }
+
+        for theirs in context.tasks {
+            match locations.get(&theirs.id) {
+                Some(location) => {
+                    // The index and the contexts are kept in sync below,
+                    // so the task is always where the index says
+                    let ours = manager
+                        .contexts
+                        .get_mut(location)
+                        .and_then(|c| c.find_task_mut(&theirs.id))
+                        .ok_or_else(|| AppError::TaskNotFound(theirs.id.clone()))?;
+                    resolve(location, ours, &theirs, strategy, &mut report);
+                }
+                None => {
+                    locations.insert(theirs.id.clone(), context.name.clone());
+                    report.changes.push(MergeChange::AddTask {
+                        context: context.name.clone(),
+                        id: theirs.id.clone(),
+                        description: theirs.description.clone(),
+                    });
+                    if let Some(target) = manager.contexts.get_mut(&context.name) {
+                        target.add_task(theirs);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Adds an imported context, renaming it if the name is already taken
+fn merge_renaming(
+    manager: &mut ContextManager,
+    mut context: crate::context::Context,
+    report: &mut MergeReport,
+) -> Result<()> {
+    if !manager.contexts.contains_key(&context.name) {
+        report.changes.push(MergeChange::AddContext {
+            context: context.name.clone(),
+        });
+        report
+            .changes
+            .extend(context.tasks.iter().map(|task| MergeChange::AddTask {
+                context: context.name.clone(),
+                id: task.id.clone(),
+                description: task.description.clone(),
+            }));
+        manager.contexts.insert(context.name.clone(), context);
+        return Ok(());
+    }
+
+    // Find a unique name by adding a counter
+    let mut new_name = format!("{}-imported", context.name);
+    let mut counter = 1;
+    while manager.contexts.contains_key(&new_name) {
+        counter += 1;
+        new_name = format!("{}-imported-{}", context.name, counter);
+    }
+
+    report.changes.push(MergeChange::RenameContext {
+        context: context.name.clone(),
+        renamed_to: new_name.clone(),
+        tasks: context.tasks.len(),
+    });
+    context.name = new_name.clone();
+    manager.contexts.insert(new_name, context);
+    Ok(())
+}
+
+/// Resolves an imported task against the local task with the same ID
+fn resolve(
+    context: &str,
+    ours: &mut Task,
+    theirs: &Task,
+    strategy: MergeStrategy,
+    report: &mut MergeReport,
+) {
+    let differences = ours.diff(theirs);
+    if differences.is_empty() {
+        return;
+    }
+
+    let take_theirs = match strategy {
+        MergeStrategy::Theirs => true,
+        MergeStrategy::Newest => {
+            compare_timestamps(theirs.modified_at(), ours.modified_at()).is_gt()
+        }
+        _ => false,
+    };
+
+    let (updated, kept) = if take_theirs {
+        (differences, Vec::new())
+    } else if strategy == MergeStrategy::Union {
+        // Completion and tags can be combined; everything else stays local
+        differences.into_iter().partition(|change| {
+            (change.field == "completed" && theirs.completed) || change.field == "tags"
+        })
+    } else {
+        (Vec::new(), differences)
+    };
+
+    if !updated.is_empty() {
+        let before = ours.clone();
+        if take_theirs {
+            ours.description = theirs.description.clone();
+            ours.time_horizon = theirs.time_horizon;
+            ours.priority = theirs.priority;
+            ours.completed = theirs.completed;
+            ours.tags = theirs.tags.clone();
+            ours.due = theirs.due;
+        } else {
+            ours.completed |= theirs.completed;
+            for tag in &theirs.tags {
+                if !ours.tags.contains(tag) {
+                    ours.tags.push(tag.clone());
+                }
+            }
+        }
+
+        // The merged fields go in the history like any other edit. But the
+        // values are only as fresh as the copy they came from, so updated_at
+        // stays the later of the two modification times rather than the time
+        // of the import; otherwise a later `newest` merge or sync would take
+        // a stale imported value for the newest one
+        ours.record_changes(&before);
+        ours.updated_at = if compare_timestamps(theirs.modified_at(), before.modified_at()).is_gt()
+        {
+            theirs.updated_at.clone()
+        } else {
+            before.updated_at.clone()
+        };
+
+        report.changes.push(MergeChange::UpdateTask {
+            context: context.to_string(),
+            id: ours.id.clone(),
+            fields: before.diff(ours),
+        });
+    }
+
+    if !kept.is_empty() {
+        report.changes.push(MergeChange::Conflict {
+            context: context.to_string(),
+            id: ours.id.clone(),
+            fields: kept,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Priority, TimeHorizon};
+
+    /// Local data with one task in "work", plus an export of it
+    fn setup() -> (ContextManager, ContextManager, String) {
+        let mut manager = ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+        let mut task = Task::new(
+            "Write report".to_string(),
+            TimeHorizon::ShortTerm,
+            Priority::Low,
+        );
+        task.created_at = "2024-01-15T10:00:00+00:00".to_string();
+        let id = task.id.clone();
+        manager.contexts.get_mut("work").unwrap().add_task(task);
+
+        let exported = manager.clone();
+        (manager, exported, id)
+    }
+
+    fn task<'a>(manager: &'a ContextManager, id: &str) -> &'a Task {
+        manager
+            .contexts
+            .values()
+            .find_map(|c| c.find_task(id))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_merge_strategy_from_str() {
+        assert_eq!(
+            MergeStrategy::from_str("rename").unwrap(),
+            MergeStrategy::Rename
+        );
+        assert_eq!(
+            MergeStrategy::from_str("UNION").unwrap(),
+            MergeStrategy::Union
+        );
+        assert_eq!(
+            MergeStrategy::from_str("theirs").unwrap(),
+            MergeStrategy::Theirs
+        );
+        assert_eq!(
+            MergeStrategy::from_str("ours").unwrap(),
+            MergeStrategy::Ours
+        );
+        assert_eq!(
+            MergeStrategy::from_str("newest").unwrap(),
+            MergeStrategy::Newest
+        );
+        assert!(MergeStrategy::from_str("mine").is_err());
+        assert_eq!(MergeStrategy::default(), MergeStrategy::Rename);
+    }
+
+    #[test]
+    fn test_rename_duplicates_clashing_contexts() {
+        let (mut manager, exported, _) = setup();
+
+        let report = merge(&mut manager, exported.clone(), MergeStrategy::Rename).unwrap();
+        assert!(manager.contexts.contains_key("work-imported"));
+        assert!(manager.contexts.contains_key("default-imported"));
+        assert_eq!(report.added_tasks(), 1);
+
+        // A second import picks the next free name
+        merge(&mut manager, exported, MergeStrategy::Rename).unwrap();
+        assert!(manager.contexts.contains_key("work-imported-2"));
+        assert_eq!(manager.contexts["work-imported-2"].name, "work-imported-2");
+    }
+
+    #[test]
+    fn test_union_deduplicates_by_id() {
+        let (mut manager, mut exported, _) = setup();
+        exported
+            .contexts
+            .get_mut("work")
+            .unwrap()
+            .add_task(Task::new(
+                "New".to_string(),
+                TimeHorizon::MidTerm,
+                Priority::High,
+            ));
+        exported.create_context("home".to_string()).unwrap();
+
+        let report = merge(&mut manager, exported.clone(), MergeStrategy::Union).unwrap();
+        assert_eq!(manager.contexts["work"].tasks.len(), 2);
+        assert_eq!(report.added_contexts(), 1);
+        assert_eq!(report.added_tasks(), 1);
+
+        // Importing the same file again changes nothing
+        let report = merge(&mut manager, exported, MergeStrategy::Union).unwrap();
+        assert!(report.is_empty());
+        assert_eq!(manager.contexts.len(), 3);
+    }
+
+    #[test]
+    fn test_union_combines_completion_and_tags() {
+        let (mut manager, mut exported, id) = setup();
+        let theirs = exported.contexts.get_mut("work").unwrap();
+        let theirs = theirs.find_task_mut(&id).unwrap();
+        theirs.mark_complete();
+        theirs.tags.push("q1".to_string());
+        theirs.priority = Priority::High;
+
+        let report = merge(&mut manager, exported, MergeStrategy::Union).unwrap();
+        let merged = task(&manager, &id);
+        assert!(merged.completed);
+        assert_eq!(merged.tags, vec!["q1".to_string()]);
+        assert_eq!(merged.priority, Priority::Low);
+        assert_eq!(report.updated_tasks(), 1);
+        assert_eq!(report.conflicts(), 1);
+    }
+
+    #[test]
+    fn test_theirs_and_ours() {
+        let (mut ours_manager, mut exported, id) = setup();
+        exported
+            .contexts
+            .get_mut("work")
+            .unwrap()
+            .find_task_mut(&id)
+            .unwrap()
+            .update(Some("Renamed".to_string()), None, None, None);
+        let mut theirs_manager = ours_manager.clone();
+
+        let report = merge(&mut ours_manager, exported.clone(), MergeStrategy::Ours).unwrap();
+        assert_eq!(task(&ours_manager, &id).description, "Write report");
+        assert_eq!(report.conflicts(), 1);
+
+        let report = merge(&mut theirs_manager, exported, MergeStrategy::Theirs).unwrap();
+        assert_eq!(task(&theirs_manager, &id).description, "Renamed");
+        assert_eq!(
+            report.changes,
+            vec![MergeChange::UpdateTask {
+                context: "work".to_string(),
+                id: id.clone(),
+                fields: vec![FieldChange {
+                    field: "description".to_string(),
+                    from: "Write report".to_string(),
+                    to: "Renamed".to_string(),
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_newest_uses_modification_time() {
+        let (mut manager, mut exported, id) = setup();
+
+        // Local edit at 12:00, imported edit at 11:00: local wins
+        let local = manager.contexts.get_mut("work").unwrap();
+        let local = local.find_task_mut(&id).unwrap();
+        local.priority = Priority::Medium;
+        local.updated_at = Some("2024-01-15T12:00:00+00:00".to_string());
+
+        let remote = exported.contexts.get_mut("work").unwrap();
+        let remote = remote.find_task_mut(&id).unwrap();
+        remote.priority = Priority::High;
+        remote.updated_at = Some("2024-01-15T11:00:00+00:00".to_string());
+
+        let mut preview = manager.clone();
+        merge(&mut preview, exported.clone(), MergeStrategy::Newest).unwrap();
+        assert_eq!(task(&preview, &id).priority, Priority::Medium);
+
+        // Imported edit at 13:00 (given in another offset): imported wins
+        let remote = exported.contexts.get_mut("work").unwrap();
+        let remote = remote.find_task_mut(&id).unwrap();
+        remote.updated_at = Some("2024-01-15T15:00:00+02:00".to_string());
+
+        merge(&mut manager, exported, MergeStrategy::Newest).unwrap();
+        let merged = task(&manager, &id);
+        assert_eq!(merged.priority, Priority::High);
+        assert_eq!(merged.history.last().unwrap().change.field, "priority");
+
+        // The merged task is as fresh as the imported edit, not the import
+        assert_eq!(
+            merged.updated_at.as_deref(),
+            Some("2024-01-15T15:00:00+02:00")
+        );
+    }
+
+    #[test]
+    fn test_taking_stale_values_keeps_the_local_modification_time() {
+        let (mut manager, mut exported, id) = setup();
+
+        let local = manager.contexts.get_mut("work").unwrap();
+        let local = local.find_task_mut(&id).unwrap();
+        local.updated_at = Some("2024-01-15T12:00:00+00:00".to_string());
+
+        // An older copy that was changed before the local edit
+        let remote = exported.contexts.get_mut("work").unwrap();
+        let remote = remote.find_task_mut(&id).unwrap();
+        remote.priority = Priority::High;
+        remote.updated_at = Some("2024-01-15T11:00:00+00:00".to_string());
+
+        merge(&mut manager, exported, MergeStrategy::Theirs).unwrap();
+        let merged = task(&manager, &id);
+        assert_eq!(merged.priority, Priority::High);
+        assert_eq!(merged.history.last().unwrap().change.field, "priority");
+        assert_eq!(
+            merged.updated_at.as_deref(),
+            Some("2024-01-15T12:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn test_merge_matches_tasks_moved_to_another_context() {
+        let (mut manager, exported, id) = setup();
+
+        // Locally, the task has moved from "work" to "default"
+        let moved = manager
+            .contexts
+            .get_mut("work")
+            .unwrap()
+            .remove_task(&id)
+            .unwrap();
+        manager.active_context_mut().add_task(moved);
+
+        let report = merge(&mut manager, exported, MergeStrategy::Union).unwrap();
+        assert!(report.is_empty());
+        assert!(manager.contexts["work"].tasks.is_empty());
+        assert_eq!(manager.active_context().tasks.len(), 1);
+    }
+}
@@ -0,0 +1,630 @@
+// Output module - machine-readable output for scripts
+// This module demonstrates borrowed serialization types, serde attributes, and trait bounds
+//
+// By default every command prints coloured, human-oriented text. Scripts should not
+// have to parse that, so the global `--output` flag switches commands to JSON:
+//
+// - `--output text`   (default) coloured terminal output
+// - `--output json`   one pretty-printed JSON document per command
+// - `--output ndjson` newline-delimited JSON: one compact object per line
+//
+// # Stable Output Shapes
+//
+// The shapes below are a public interface. Fields may be added in the future,
+// but existing fields will not be renamed or removed.
+//
+// **Task object** - returned by `show`, `add`, `complete`, `edit`, `delete`,
+// `move`; `list` returns an array of them (one per line with ndjson), and so do
+// `complete`, `edit` and `delete` when given several IDs or `--where`:
+//
+// ```json
+// {
+//   "id": "5f0e3b1a-7c1e-4a53-9d0c-1e2f3a4b5c6d",
+//   "description": "Write docs",
+//   "time_horizon": "ShortTerm",
+//   "priority": "High",
+//   "completed": false,
+//   "created_at": "2026-10-18T09:00:00+00:00",
+//   "tags": ["docs"],
+//   "short_id": "5f0e3b",
+//   "context": "work"
+// }
+// ```
+//
+// The task fields are exactly the `Task` serde shape used in the data file,
+// plus `short_id` and `context`. `tags` is omitted when the task has none;
+// `updated_at` and `history` are omitted when the task was never changed,
+// `reviewed_at` when it was never reviewed, and `due` (a "YYYY-MM-DD" day)
+// when the task has no due date.
+//
+// **History event** - `history` returns an array of them, oldest first. The
+// first event is always `created`; the others carry the changed field:
+//
+// ```json
+// { "at": "2026-10-18T09:00:00+00:00", "event": "created" }
+// { "at": "2026-10-18T10:00:00+00:00", "event": "changed",
+//   "field": "priority", "from": "Low", "to": "High" }
+// ```
+//
+// **Search result** - `search` returns an array of them, best first: a task
+// object plus its score and the matched parts of the description, as
+// `[start, end)` character offsets:
+//
+// ```json
+// { "id": "...", "description": "Write API docs", ..., "context": "work",
+//   "score": 100, "highlights": [[6, 9]] }
+// ```
+//
+// **Ranked task** - `next` returns an array of them, most urgent first: a
+// task object plus its urgency score and the factors it adds up:
+//
+// ```json
+// { "id": "...", "description": "Fix login", ..., "context": "work",
+//   "urgency": 10.52,
+//   "breakdown": { "priority": 6.0, "horizon": 4.0, "age": 0.52, "due": 0.0,
+//                  "blocked": 0.0 } }
+// ```
+//
+// **Aged task** - `age` returns an array of them, by context: a task the
+// aging rules found too old, how long it has been in its horizon and what was
+// done about it. `promoted_to` is only present when `action` is "promoted":
+//
+// ```json
+// { "context": "work", "id": "...", "short_id": "5f0e3b", "description": "Plan offsite",
+//   "time_horizon": "MidTerm", "days": 75, "limit": 60, "action": "promoted",
+//   "promoted_to": "ShortTerm" }
+// ```
+//
+// **Review summary** - returned by `review`: the aged tasks the aging rules
+// found before the review started, how many tasks got each answer, and how
+// many were left when the review ended early:
+//
+// ```json
+// { "context": "work", "aged": [ ... ], "kept": 4, "completed": 2, "deleted": 1, "rescheduled": 0,
+//   "reprioritized": 1, "deferred": 1, "skipped": 0, "remaining": 0 }
+// ```
+//
+// **Stats object** - returned by `stats`. Dates are UTC days, activity is
+// oldest first, and `average_days` is null for groups with no completed tasks
+// whose completion time is known:
+//
+// ```json
+// { "period": "week",
+//   "activity": [ { "start": "2026-10-12", "created": 5, "completed": 3 } ],
+//   "by_horizon": [ { "group": "ShortTerm", "tasks": 3, "average_days": 1.5 } ],
+//   "by_priority": [ { "group": "High", "tasks": 2, "average_days": 0.8 } ],
+//   "contexts": [ { "context": "work", "open": 4, "completed": 3 } ],
+//   "oldest": [ { "context": "work", "id": "...", "description": "Plan offsite",
+//                 "time_horizon": "MidTerm", "priority": "Low", "age_days": 75 } ] }
+// ```
+//
+// **Chart object** - returned by `chart burndown` and `chart throughput`.
+// Burndown points are days and count the tasks open at the end of the day;
+// throughput points are weeks (starting on Monday) and count completions:
+//
+// ```json
+// { "chart": "burndown", "context": "work",
+//   "points": [ { "date": "2026-10-01", "count": 12 }, { "date": "2026-10-02", "count": 11 } ] }
+// ```
+//
+// **Context object** - returned by `context new`, `context switch`,
+// `context delete`; `context list` returns an array of them:
+//
+// ```json
+// { "name": "work", "active": true, "task_count": 3, "open_count": 2 }
+// ```
+//
+// **View object** - returned by `view save` and `view delete`; `view list`
+// returns an array of them. `sort` and `context` are omitted when unset, and
+// running a view (`view <name>`) returns an array of task objects:
+//
+// ```json
+// { "name": "triage", "filter": "priority:high not completed", "sort": "-created" }
+// ```
+//
+// **Action object** - returned by `export`, `import` and `report`:
+//
+// ```json
+// { "action": "export", "path": "backup.json", "contexts": 2, "tasks": 5 }
+// ```
+//
+// **Sync object** - returned by `sync`. `local` and `remote` count the tasks
+// changed in each file; each conflict lists both values and the side kept:
+//
+// ```json
+// { "path": "shared.json",
+//   "local": { "added": 1, "updated": 0, "deleted": 0 },
+//   "remote": { "added": 0, "updated": 1, "deleted": 0 },
+//   "conflicts": [ { "id": "...", "description": "Write docs", "field": "priority",
+//                    "local": "High", "remote": "Low", "local_modified": "...",
+//                    "remote_modified": "...", "kept": "local" } ] }
+// ```
+//
+// **Error object** - printed instead of the result when a command fails. The
+// process still exits with a non-zero status. `code` is one of the values
+// returned by AppError::code():
+//
+// ```json
+// { "error": { "code": "task_not_found", "message": "Task not found: abc123" } }
+// ```
+
+use crate::aging::{AgedTask, AgingAction};
+use crate::chart::Point;
+use crate::context::Context;
+use crate::error::{AppError, Result};
+use crate::review::ReviewSummary;
+use crate::search::SearchHit;
+use crate::sync::SyncReport;
+use crate::task::{FieldChange, Task, TimeHorizon};
+use crate::urgency::{Ranked, Urgency};
+use serde::Serialize;
+use std::str::FromStr;
+
+/// Output formats selectable with the global `--output` flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Coloured, human-oriented text (the default)
+    Text,
+    /// A single pretty-printed JSON document
+    Json,
+    /// Newline-delimited JSON, one compact object per line
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Returns true for the default human-oriented text output
+    ///
+    /// Handlers use this to decide between printing coloured text and
+    /// emitting one of the JSON shapes documented above.
+    pub fn is_text(self) -> bool {
+        self == OutputFormat::Text
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" | "jsonl" => Ok(OutputFormat::Ndjson),
+            _ => Err(AppError::InvalidOutputFormat(s.to_string())),
+        }
+    }
+}
+
+/// JSON shape for a single task
+///
+/// #[serde(flatten)] inlines the Task's own fields, so this is the Task
+/// serde shape with two extra fields. Borrowing the task avoids cloning it
+/// just to print it.
+#[derive(Debug, Serialize)]
+pub struct TaskOutput<'a> {
+    #[serde(flatten)]
+    pub task: &'a Task,
+
+    /// First 6 characters of the ID, as shown in the text output
+    pub short_id: &'a str,
+
+    /// Name of the context the task belongs to
+    pub context: &'a str,
+}
+
+impl<'a> TaskOutput<'a> {
+    /// Creates the JSON view of a task in the named context
+    pub fn new(task: &'a Task, context: &'a str) -> Self {
+        Self {
+            task,
+            short_id: task.id.get(..6).unwrap_or(&task.id),
+            context,
+        }
+    }
+}
+
+/// JSON shape for a search result
+#[derive(Debug, Serialize)]
+pub struct SearchResultOutput<'a> {
+    #[serde(flatten)]
+    pub task: TaskOutput<'a>,
+
+    /// Relevance; higher is better
+    pub score: u32,
+
+    /// Matched parts of the description as [start, end) character offsets
+    pub highlights: Vec<[usize; 2]>,
+}
+
+impl<'a> SearchResultOutput<'a> {
+    /// Creates the JSON view of a search hit
+    ///
+    /// The hit's byte ranges become character offsets, which is what
+    /// clients in other languages can use.
+    pub fn new(hit: &SearchHit<'a>) -> Self {
+        let description = &hit.task.description;
+        let chars = |byte: usize| description[..byte].chars().count();
+        Self {
+            task: TaskOutput::new(hit.task, hit.context),
+            score: hit.score,
+            highlights: hit
+                .highlights
+                .iter()
+                .map(|range| [chars(range.start), chars(range.end)])
+                .collect(),
+        }
+    }
+}
+
+/// JSON shape for a task ranked by `todo next`
+#[derive(Debug, Serialize)]
+pub struct RankedOutput<'a> {
+    #[serde(flatten)]
+    pub task: TaskOutput<'a>,
+
+    /// The urgency score, rounded to two decimals
+    pub urgency: f64,
+
+    /// The factors the score adds up, rounded the same way
+    pub breakdown: Urgency,
+}
+
+impl<'a> RankedOutput<'a> {
+    /// Creates the JSON view of a ranked task
+    pub fn new(ranked: &Ranked<'a>) -> Self {
+        let round = |value: f64| (value * 100.0).round() / 100.0;
+        let urgency = ranked.urgency;
+        Self {
+            task: TaskOutput::new(ranked.task, ranked.context),
+            urgency: round(urgency.total()),
+            breakdown: Urgency {
+                priority: round(urgency.priority),
+                horizon: round(urgency.horizon),
+                age: round(urgency.age),
+                due: round(urgency.due),
+                blocked: round(urgency.blocked),
+            },
+        }
+    }
+}
+
+/// JSON shape for a task found by the aging rules
+#[derive(Debug, Serialize)]
+pub struct AgedTaskOutput<'a> {
+    pub context: &'a str,
+    pub id: &'a str,
+    pub short_id: &'a str,
+    pub description: &'a str,
+
+    /// The horizon the task was in
+    pub time_horizon: TimeHorizon,
+
+    /// Whole days it had been there
+    pub days: i64,
+
+    /// The maximum age it went over
+    pub limit: u32,
+
+    /// "flagged" or "promoted"
+    pub action: &'static str,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub promoted_to: Option<TimeHorizon>,
+}
+
+impl<'a> AgedTaskOutput<'a> {
+    /// Creates the JSON view of an aged task
+    pub fn new(aged: &'a AgedTask) -> Self {
+        let (action, promoted_to) = match aged.action {
+            AgingAction::Flagged => ("flagged", None),
+            AgingAction::Promoted(to) => ("promoted", Some(to)),
+        };
+        Self {
+            context: &aged.context,
+            id: &aged.id,
+            short_id: aged.id.get(..6).unwrap_or(&aged.id),
+            description: &aged.description,
+            time_horizon: aged.horizon,
+            days: aged.days,
+            limit: aged.limit,
+            action,
+            promoted_to,
+        }
+    }
+}
+
+/// JSON shape for one event in a task's timeline
+#[derive(Debug, Serialize)]
+pub struct HistoryEventOutput<'a> {
+    /// ISO 8601 timestamp of the event
+    pub at: &'a str,
+
+    /// "created" for the first event, "changed" for the rest
+    pub event: &'static str,
+
+    /// The changed field, absent for the creation event
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub change: Option<&'a FieldChange>,
+}
+
+impl<'a> HistoryEventOutput<'a> {
+    /// Lists a task's timeline: its creation followed by every recorded change
+    pub fn timeline(task: &'a Task) -> Vec<Self> {
+        let created = HistoryEventOutput {
+            at: &task.created_at,
+            event: "created",
+            change: None,
+        };
+        std::iter::once(created)
+            .chain(task.history.iter().map(|entry| HistoryEventOutput {
+                at: &entry.at,
+                event: "changed",
+                change: Some(&entry.change),
+            }))
+            .collect()
+    }
+}
+
+/// JSON shape for a single context
+#[derive(Debug, Serialize)]
+pub struct ContextOutput<'a> {
+    /// Context name
+    pub name: &'a str,
+
+    /// Whether this is the active context
+    pub active: bool,
+
+    /// Number of tasks in the context
+    pub task_count: usize,
+
+    /// Number of tasks not yet completed
+    pub open_count: usize,
+}
+
+impl<'a> ContextOutput<'a> {
+    /// Creates the JSON view of a context
+    pub fn new(context: &'a Context, active: bool) -> Self {
+        Self {
+            name: &context.name,
+            active,
+            task_count: context.tasks.len(),
+            open_count: context.tasks.iter().filter(|t| !t.completed).count(),
+        }
+    }
+}
+
+/// JSON shape for file-level commands (export, import, report)
+#[derive(Debug, Serialize)]
+pub struct ActionOutput {
+    /// The command that ran ("export", "import", "report")
+    pub action: &'static str,
+
+    /// The file that was written or read
+    pub path: String,
+
+    /// Number of contexts affected
+    pub contexts: usize,
+
+    /// Number of tasks affected
+    pub tasks: usize,
+}
+
+/// JSON shape for the sync command
+#[derive(Debug, Serialize)]
+pub struct SyncOutput<'a> {
+    /// The other data file
+    pub path: String,
+
+    /// What changed on each side, and the conflicts
+    #[serde(flatten)]
+    pub report: &'a SyncReport,
+}
+
+/// JSON shape for the review command
+#[derive(Debug, Serialize)]
+pub struct ReviewOutput<'a> {
+    /// The context that was reviewed
+    pub context: &'a str,
+
+    /// What the aging rules found and did before the review started
+    pub aged: Vec<AgedTaskOutput<'a>>,
+
+    #[serde(flatten)]
+    pub summary: &'a ReviewSummary,
+}
+
+/// JSON shape for the chart command
+#[derive(Debug, Serialize)]
+pub struct ChartOutput<'a> {
+    /// "burndown" or "throughput"
+    pub chart: &'static str,
+    pub context: &'a str,
+    pub points: &'a [Point],
+}
+
+/// JSON shape for a failed command
+#[derive(Debug, Serialize)]
+pub struct ErrorOutput {
+    pub error: ErrorBody,
+}
+
+/// The `error` member of ErrorOutput
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    /// Stable, machine-readable error code (see AppError::code)
+    pub code: &'static str,
+
+    /// Human-readable message, same as the text output
+    pub message: String,
+}
+
+impl From<&AppError> for ErrorOutput {
+    fn from(error: &AppError) -> Self {
+        Self {
+            error: ErrorBody {
+                code: error.code(),
+                message: error.to_string(),
+            },
+        }
+    }
+}
+
+/// Renders a single value in the given format
+///
+/// Text output is produced by the handlers themselves, so for
+/// OutputFormat::Text this returns an empty string.
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::output::{render, ContextOutput, OutputFormat};
+/// use rust_todo::context::Context;
+///
+/// let context = Context::new("work".to_string());
+/// let line = render(OutputFormat::Ndjson, &ContextOutput::new(&context, true))?;
+/// assert_eq!(line, r#"{"name":"work","active":true,"task_count":0,"open_count":0}"#);
+/// # Ok::<(), rust_todo::error::AppError>(())
+/// ```
+pub fn render<T: Serialize>(format: OutputFormat, value: &T) -> Result<String> {
+    Ok(match format {
+        OutputFormat::Text => String::new(),
+        OutputFormat::Json => serde_json::to_string_pretty(value)?,
+        OutputFormat::Ndjson => serde_json::to_string(value)?,
+    })
+}
+
+/// Renders a list of values in the given format
+///
+/// JSON output is a single array; NDJSON output is one object per line,
+/// which lets scripts stream results with tools like `jq -c` or `while read`.
+pub fn render_list<T: Serialize>(format: OutputFormat, values: &[T]) -> Result<String> {
+    Ok(match format {
+        OutputFormat::Text => String::new(),
+        OutputFormat::Json => serde_json::to_string_pretty(values)?,
+        OutputFormat::Ndjson => {
+            let lines: std::result::Result<Vec<String>, _> =
+                values.iter().map(serde_json::to_string).collect();
+            lines?.join("\n")
+        }
+    })
+}
+
+/// Prints a single value to stdout in the given format
+pub fn emit<T: Serialize>(format: OutputFormat, value: &T) -> Result<()> {
+    println!("{}", render(format, value)?);
+    Ok(())
+}
+
+/// Prints a list of values to stdout in the given format
+///
+/// An empty list prints `[]` for JSON and nothing at all for NDJSON.
+pub fn emit_list<T: Serialize>(format: OutputFormat, values: &[T]) -> Result<()> {
+    let rendered = render_list(format, values)?;
+    if !rendered.is_empty() {
+        println!("{}", rendered);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Priority, TimeHorizon};
+
+    #[test]
+    fn test_output_format_from_str() {
+        assert_eq!(OutputFormat::from_str("text").unwrap(), OutputFormat::Text);
+        assert_eq!(OutputFormat::from_str("JSON").unwrap(), OutputFormat::Json);
+        assert_eq!(
+            OutputFormat::from_str("ndjson").unwrap(),
+            OutputFormat::Ndjson
+        );
+        assert!(OutputFormat::from_str("yaml").is_err());
+        assert!(OutputFormat::Text.is_text());
+        assert!(!OutputFormat::Json.is_text());
+    }
+
+    #[test]
+    fn test_task_output_shape() {
+        let task = Task::new(
+            "Write docs".to_string(),
+            TimeHorizon::MidTerm,
+            Priority::High,
+        );
+        let value = serde_json::to_value(TaskOutput::new(&task, "work")).unwrap();
+
+        // Task fields are flattened in, plus short_id and context
+        assert_eq!(value["id"], task.id.as_str());
+        assert_eq!(value["description"], "Write docs");
+        assert_eq!(value["time_horizon"], "MidTerm");
+        assert_eq!(value["priority"], "High");
+        assert_eq!(value["completed"], false);
+        assert_eq!(value["created_at"], task.created_at.as_str());
+        assert_eq!(value["short_id"], &task.id[..6]);
+        assert_eq!(value["context"], "work");
+    }
+
+    #[test]
+    fn test_history_timeline_shape() {
+        let mut task = Task::new("a".to_string(), TimeHorizon::ShortTerm, Priority::Low);
+        task.mark_complete();
+
+        let timeline = HistoryEventOutput::timeline(&task);
+        let values = serde_json::to_value(&timeline).unwrap();
+        assert_eq!(values[0]["event"], "created");
+        assert_eq!(values[0]["at"], task.created_at.as_str());
+        assert!(values[0].get("field").is_none());
+        assert_eq!(values[1]["event"], "changed");
+        assert_eq!(values[1]["field"], "completed");
+        assert_eq!(values[1]["from"], "false");
+        assert_eq!(values[1]["to"], "true");
+    }
+
+    #[test]
+    fn test_context_output_counts() {
+        let mut context = Context::new("work".to_string());
+        context.add_task(Task::new(
+            "a".to_string(),
+            TimeHorizon::ShortTerm,
+            Priority::Low,
+        ));
+        let mut done = Task::new("b".to_string(), TimeHorizon::ShortTerm, Priority::Low);
+        done.mark_complete();
+        context.add_task(done);
+
+        let output = ContextOutput::new(&context, false);
+        assert_eq!(output.task_count, 2);
+        assert_eq!(output.open_count, 1);
+        assert!(!output.active);
+    }
+
+    #[test]
+    fn test_render_list_formats() {
+        let a = Context::new("a".to_string());
+        let b = Context::new("b".to_string());
+        let values = vec![ContextOutput::new(&a, true), ContextOutput::new(&b, false)];
+
+        let ndjson = render_list(OutputFormat::Ndjson, &values).unwrap();
+        let lines: Vec<&str> = ndjson.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(r#"{"name":"a""#));
+
+        let json = render_list(OutputFormat::Json, &values).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.as_array().unwrap().len(), 2);
+
+        let empty: Vec<ContextOutput> = Vec::new();
+        assert_eq!(render_list(OutputFormat::Json, &empty).unwrap(), "[]");
+        assert_eq!(render_list(OutputFormat::Ndjson, &empty).unwrap(), "");
+        assert_eq!(render_list(OutputFormat::Text, &values).unwrap(), "");
+    }
+
+    #[test]
+    fn test_error_output_shape() {
+        let error = AppError::TaskNotFound("abc123".to_string());
+        let json = render(OutputFormat::Ndjson, &ErrorOutput::from(&error)).unwrap();
+        assert_eq!(
+            json,
+            r#"{"error":{"code":"task_not_found","message":"Task not found: abc123"}}"#
+        );
+    }
+}
@@ -0,0 +1,338 @@
+// Report module - renders task data as a self-contained HTML page
+// This module demonstrates string building, HTML escaping, and reuse of display conventions
+//
+// The HTML report is meant to be shared with people who don't use the CLI, for
+// example attached to a weekly status email. Because of that, the page must not
+// depend on anything outside the file itself: all styling is inlined in a <style>
+// block and there are no scripts, fonts or images.
+//
+// # Layout
+//
+// - A header with the overall completion percentage and a generated-at stamp
+// - One section per context (sorted by name, active context marked)
+// - Inside each section, one column per time horizon
+// - Each task shows its status, short ID, priority badge and description
+//
+// # Colour Coding
+//
+// Priority colours mirror the terminal output in display.rs:
+// - High: red and bold
+// - Medium: yellow
+// - Low: dimmed (grey)
+
+use crate::context::{Context, ContextManager};
+use crate::task::{Priority, Task, TimeHorizon};
+use std::fmt::Write;
+
+/// Inline stylesheet for the report
+///
+/// Kept as a constant so the page stays self-contained and the markup
+/// generation below only deals with structure.
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2em; color: #222; background: #fafafa; }
+h1 { margin-bottom: 0.2em; }
+.stamp { color: #888; font-size: 0.9em; margin-top: 0; }
+.context { background: #fff; border: 1px solid #ddd; border-radius: 6px; padding: 1em 1.5em; margin: 1.5em 0; }
+.context h2 { margin-top: 0; }
+.active-marker { color: #2a9d2a; font-size: 0.7em; vertical-align: middle; }
+.progress { background: #eee; border-radius: 4px; height: 8px; overflow: hidden; margin: 0.3em 0 0.8em; }
+.progress .bar { background: #2a9d2a; height: 100%; }
+.horizons { display: grid; grid-template-columns: repeat(3, 1fr); gap: 1em; }
+.horizon h3 { color: #1b8a9c; font-size: 0.95em; letter-spacing: 0.05em; margin-bottom: 0.2em; }
+.horizon .pct { color: #888; font-size: 0.85em; margin-top: 0; }
+ul.tasks { list-style: none; padding: 0; margin: 0; }
+ul.tasks li { padding: 0.25em 0; border-bottom: 1px solid #f0f0f0; }
+.id { color: #999; font-family: monospace; }
+.priority { font-family: monospace; font-size: 0.85em; }
+.priority-high { color: #d62828; font-weight: bold; }
+.priority-medium { color: #b8860b; }
+.priority-low { color: #999; }
+.done { color: #2a9d2a; }
+li.completed .description { text-decoration: line-through; color: #888; }
+.empty { color: #aaa; font-style: italic; }
+"#;
+
+/// Horizons in display order, with their column headers
+///
+/// The headers match the ones printed by display_tasks().
+const HORIZONS: [(TimeHorizon, &str); 3] = [
+    (TimeHorizon::ShortTerm, "SHORT-TERM TASKS"),
+    (TimeHorizon::MidTerm, "MID-TERM TASKS"),
+    (TimeHorizon::LongTerm, "LONG-TERM TASKS"),
+];
+
+/// Renders every context as a self-contained HTML document
+///
+/// # Arguments
+///
+/// * `manager` - The contexts and tasks to report on
+/// * `generated_at` - Timestamp printed in the page header
+///
+/// # Returns
+///
+/// A complete HTML document as a String.
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::context::ContextManager;
+/// use rust_todo::report::render_html;
+/// use rust_todo::task::{Task, TimeHorizon, Priority};
+///
+/// let mut manager = ContextManager::new();
+/// manager.active_context_mut().add_task(
+///     Task::new("Ship <v2>".to_string(), TimeHorizon::ShortTerm, Priority::High),
+/// );
+///
+/// let html = render_html(&manager, "2026-10-18 09:00 UTC");
+/// assert!(html.starts_with("<!DOCTYPE html>"));
+/// assert!(html.contains("Ship &lt;v2&gt;"));
+/// assert!(html.contains("2026-10-18 09:00 UTC"));
+/// ```
+pub fn render_html(manager: &ContextManager, generated_at: &str) -> String {
+    // Sort contexts by name so the report is stable between runs
+    let mut contexts: Vec<&Context> = manager.contexts.values().collect();
+    contexts.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let all_tasks: Vec<&Task> = contexts.iter().flat_map(|c| c.tasks.iter()).collect();
+
+    // Writing to a String with write!() can't fail, so the results are ignored
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    html.push_str("<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Todo Report</title>\n");
+    let _ = writeln!(html, "<style>{}</style>", STYLE);
+    html.push_str("</head>\n<body>\n");
+
+    html.push_str("<h1>Todo Report</h1>\n");
+    let _ = writeln!(
+        html,
+        "<p class=\"stamp\">Generated at {} &middot; {} contexts &middot; {}</p>",
+        escape_html(generated_at),
+        contexts.len(),
+        completion_label(&all_tasks)
+    );
+
+    for context in contexts {
+        render_context(&mut html, context, context.name == manager.active_context);
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Renders one context section with a column per time horizon
+fn render_context(html: &mut String, context: &Context, active: bool) {
+    let tasks: Vec<&Task> = context.tasks.iter().collect();
+
+    html.push_str("<section class=\"context\">\n");
+    let _ = write!(html, "<h2>{}", escape_html(&context.name));
+    if active {
+        html.push_str(" <span class=\"active-marker\">&#9679; active</span>");
+    }
+    html.push_str("</h2>\n");
+
+    let _ = writeln!(html, "<div>{}</div>", completion_label(&tasks));
+    let _ = writeln!(
+        html,
+        "<div class=\"progress\"><div class=\"bar\" style=\"width: {}%\"></div></div>",
+        completion_percent(&tasks)
+    );
+
+    html.push_str("<div class=\"horizons\">\n");
+    for (horizon, header) in HORIZONS.iter() {
+        // Reuse the context's own ordering (priority high to low within a horizon)
+        let horizon_tasks: Vec<&Task> = context
+            .sorted_tasks()
+            .into_iter()
+            .filter(|task| task.time_horizon == *horizon)
+            .collect();
+
+        html.push_str("<div class=\"horizon\">\n");
+        let _ = writeln!(html, "<h3>{}</h3>", header);
+        let _ = writeln!(
+            html,
+            "<p class=\"pct\">{}</p>",
+            completion_label(&horizon_tasks)
+        );
+
+        if horizon_tasks.is_empty() {
+            html.push_str("<p class=\"empty\">No tasks</p>\n");
+        } else {
+            html.push_str("<ul class=\"tasks\">\n");
+            for task in horizon_tasks {
+                render_task(html, task);
+            }
+            html.push_str("</ul>\n");
+        }
+        html.push_str("</div>\n");
+    }
+    html.push_str("</div>\n</section>\n");
+}
+
+/// Renders a single task as a list item, mirroring format_task_line()
+fn render_task(html: &mut String, task: &Task) {
+    let (class, status) = if task.completed {
+        (
+            " class=\"completed\"",
+            "<span class=\"done\">[&#10003;]</span>",
+        )
+    } else {
+        ("", "<span>[&nbsp;]</span>")
+    };
+
+    let priority = match task.priority {
+        Priority::High => "<span class=\"priority priority-high\">[HIGH]</span>",
+        Priority::Medium => "<span class=\"priority priority-medium\">[MED]</span>",
+        Priority::Low => "<span class=\"priority priority-low\">[LOW]</span>",
+    };
+
+    let short_id = task.id.get(..6).unwrap_or(&task.id);
+
+    let _ = writeln!(
+        html,
+        "<li{}>{} <span class=\"id\">{}</span> {} <span class=\"description\">{}</span></li>",
+        class,
+        status,
+        escape_html(short_id),
+        priority,
+        escape_html(&task.description)
+    );
+}
+
+/// Returns the percentage of completed tasks, rounded down (0 for no tasks)
+fn completion_percent(tasks: &[&Task]) -> usize {
+    if tasks.is_empty() {
+        return 0;
+    }
+    let done = tasks.iter().filter(|task| task.completed).count();
+    done * 100 / tasks.len()
+}
+
+/// Formats "N% complete (done/total)" for a group of tasks
+fn completion_label(tasks: &[&Task]) -> String {
+    let done = tasks.iter().filter(|task| task.completed).count();
+    format!(
+        "{}% complete ({}/{})",
+        completion_percent(tasks),
+        done,
+        tasks.len()
+    )
+}
+
+/// Escapes the characters that are significant in HTML text and attributes
+///
+/// Task descriptions and context names are user input, so they must be
+/// escaped before being embedded in the page.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_manager() -> ContextManager {
+        let mut manager = ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+
+        let work = manager.contexts.get_mut("work").unwrap();
+        let mut done = Task::new(
+            "Done task".to_string(),
+            TimeHorizon::ShortTerm,
+            Priority::Low,
+        );
+        done.mark_complete();
+        work.add_task(done);
+        work.add_task(Task::new(
+            "Open task".to_string(),
+            TimeHorizon::ShortTerm,
+            Priority::High,
+        ));
+        work.add_task(Task::new(
+            "Later".to_string(),
+            TimeHorizon::LongTerm,
+            Priority::Medium,
+        ));
+
+        manager
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+        assert_eq!(escape_html("plain"), "plain");
+    }
+
+    #[test]
+    fn test_completion_percent() {
+        let open = Task::new("a".to_string(), TimeHorizon::ShortTerm, Priority::Low);
+        let mut done = open.clone();
+        done.mark_complete();
+
+        assert_eq!(completion_percent(&[]), 0);
+        assert_eq!(completion_percent(&[&open]), 0);
+        assert_eq!(completion_percent(&[&done]), 100);
+        assert_eq!(completion_percent(&[&open, &done, &open]), 33);
+        assert_eq!(completion_label(&[&open, &done]), "50% complete (1/2)");
+    }
+
+    #[test]
+    fn test_render_html_sections_and_columns() {
+        let html = render_html(&sample_manager(), "2026-10-18 09:00 UTC");
+
+        // One section per context, sorted by name, active context marked
+        let default_pos = html.find("<h2>default").unwrap();
+        let work_pos = html.find("<h2>work").unwrap();
+        assert!(default_pos < work_pos);
+        assert!(html.contains("<h2>default <span class=\"active-marker\">"));
+
+        // Three horizon columns per context
+        assert_eq!(html.matches("SHORT-TERM TASKS").count(), 2);
+        assert_eq!(html.matches("LONG-TERM TASKS").count(), 2);
+
+        // Percentages for the work context and its short-term column
+        assert!(html.contains("33% complete (1/3)"));
+        assert!(html.contains("50% complete (1/2)"));
+        assert!(html.contains("Generated at 2026-10-18 09:00 UTC"));
+    }
+
+    #[test]
+    fn test_render_html_priority_classes() {
+        let html = render_html(&sample_manager(), "now");
+
+        assert!(html.contains("priority-high\">[HIGH]"));
+        assert!(html.contains("priority-medium\">[MED]"));
+        assert!(html.contains("priority-low\">[LOW]"));
+        assert!(html.contains("<li class=\"completed\">"));
+
+        // High priority is listed before low priority in the same horizon
+        assert!(html.find("Open task").unwrap() < html.find("Done task").unwrap());
+    }
+
+    #[test]
+    fn test_render_html_is_self_contained() {
+        let html = render_html(&sample_manager(), "now");
+
+        // No external stylesheets, scripts, images or fonts
+        assert!(!html.contains("<link"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("<img"));
+        assert!(!html.contains("http://"));
+        assert!(!html.contains("https://"));
+    }
+}
@@ -0,0 +1,389 @@
+// Review module - a guided walkthrough of every open task
+// This module demonstrates keeping an interactive workflow testable: the
+// decisions are plain values, and only the prompting lives in main.rs
+//
+// `todo review` visits each open task of the active context, short-term first
+// and the oldest first within each horizon, and asks what to do with it:
+//
+// | Answer          | Effect                                          |
+// |-----------------|-------------------------------------------------|
+// | `k`, `keep`     | leave the task as it is                         |
+// | `c`, `complete` | mark it completed                               |
+// | `d`, `delete`   | delete it                                       |
+// | `h <horizon>`   | move it to another horizon, e.g. `h short`      |
+// | `p <priority>`  | change its priority, e.g. `p high`              |
+// | `f`, `defer`    | move it one horizon later (long-term stays put) |
+// | `s`, `skip`     | come back to it next review                     |
+//
+// Every answer but skip (and delete, which leaves nothing to mark) records
+// when the task was last reviewed in its `reviewed_at` field. Reviewing is not
+// an edit, so it doesn't change `updated_at` or the task's history.
+//
+// `q` ends the review early; the answers given so far are kept.
+//
+// Before the first question, the aging rules are applied as by `todo age` and
+// their report is shown, so tasks they promote are reviewed in their new
+// horizon.
+
+use crate::aging::{self, AgingReport, AgingRules};
+use crate::context::{Context, ContextManager};
+use crate::error::{AppError, Result};
+use crate::sort::SortOrder;
+use crate::task::{Priority, Task, TimeHorizon};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::str::FromStr;
+
+/// What to do with a task under review
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewAction {
+    Keep,
+    Complete,
+    Delete,
+    Horizon(TimeHorizon),
+    Priority(Priority),
+    Defer,
+    Skip,
+}
+
+impl FromStr for ReviewAction {
+    type Err = AppError;
+
+    /// Parses an answer such as `k`, `complete` or `h short` (case-insensitive)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::review::ReviewAction;
+    /// use rust_todo::task::TimeHorizon;
+    ///
+    /// assert_eq!("k".parse::<ReviewAction>().unwrap(), ReviewAction::Keep);
+    /// assert_eq!(
+    ///     "h short".parse::<ReviewAction>().unwrap(),
+    ///     ReviewAction::Horizon(TimeHorizon::ShortTerm)
+    /// );
+    /// assert!("h".parse::<ReviewAction>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self> {
+        let lower = s.trim().to_lowercase();
+        let mut words = lower.split_whitespace();
+        let action = words.next().unwrap_or("");
+        let argument = words.next();
+        if words.next().is_some() {
+            return Err(AppError::InvalidReviewAction(s.trim().to_string()));
+        }
+
+        match (action, argument) {
+            ("k" | "keep", None) => Ok(ReviewAction::Keep),
+            ("c" | "complete", None) => Ok(ReviewAction::Complete),
+            ("d" | "delete", None) => Ok(ReviewAction::Delete),
+            ("f" | "defer", None) => Ok(ReviewAction::Defer),
+            ("s" | "skip", None) => Ok(ReviewAction::Skip),
+            ("h" | "horizon", Some(horizon)) => horizon.parse().map(ReviewAction::Horizon),
+            ("p" | "priority", Some(priority)) => priority.parse().map(ReviewAction::Priority),
+            _ => Err(AppError::InvalidReviewAction(s.trim().to_string())),
+        }
+    }
+}
+
+/// How many tasks got each answer
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReviewSummary {
+    pub kept: usize,
+    pub completed: usize,
+    pub deleted: usize,
+
+    /// Moved to another horizon with `h`
+    pub rescheduled: usize,
+
+    /// Given another priority with `p`
+    pub reprioritized: usize,
+    pub deferred: usize,
+    pub skipped: usize,
+
+    /// Tasks not reached because the review ended early
+    pub remaining: usize,
+}
+
+impl ReviewSummary {
+    /// Counts one answer
+    pub fn record(&mut self, action: ReviewAction) {
+        let count = match action {
+            ReviewAction::Keep => &mut self.kept,
+            ReviewAction::Complete => &mut self.completed,
+            ReviewAction::Delete => &mut self.deleted,
+            ReviewAction::Horizon(_) => &mut self.rescheduled,
+            ReviewAction::Priority(_) => &mut self.reprioritized,
+            ReviewAction::Defer => &mut self.deferred,
+            ReviewAction::Skip => &mut self.skipped,
+        };
+        *count += 1;
+    }
+
+    /// Number of tasks answered, skips included
+    pub fn reviewed(&self) -> usize {
+        self.kept
+            + self.completed
+            + self.deleted
+            + self.rescheduled
+            + self.reprioritized
+            + self.deferred
+            + self.skipped
+    }
+}
+
+/// The IDs of the open tasks to review, in review order
+///
+/// Short-term tasks come first, then mid-term and long-term ones; within a
+/// horizon the oldest task comes first.
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::context::Context;
+/// use rust_todo::review::queue;
+/// use rust_todo::task::{Priority, Task, TimeHorizon};
+///
+/// let mut context = Context::new("work".to_string());
+/// context.add_task(Task::new("Later".to_string(), TimeHorizon::LongTerm, Priority::High));
+/// context.add_task(Task::new("Soon".to_string(), TimeHorizon::ShortTerm, Priority::Low));
+///
+/// let ids = queue(&context);
+/// assert_eq!(context.find_task(&ids[0]).unwrap().description, "Soon");
+/// ```
+pub fn queue(context: &Context) -> Vec<String> {
+    let mut tasks: Vec<&Task> = context
+        .tasks
+        .iter()
+        .filter(|task| !task.completed)
+        .collect();
+    "horizon,created"
+        .parse::<SortOrder>()
+        .expect("valid sort keys")
+        .sort(&mut tasks);
+    tasks.into_iter().map(|task| task.id.clone()).collect()
+}
+
+/// Applies the aging rules, then queues the active context for review
+///
+/// # Arguments
+///
+/// * `manager` - The data; tasks the rules promote are changed in place
+/// * `rules` - The aging rules from the configuration
+/// * `now` - The instant ages are measured to
+///
+/// # Returns
+///
+/// The aging report, and the review queue of the active context (see queue())
+/// built after the promotions.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Duration, Utc};
+/// use rust_todo::aging::AgingRules;
+/// use rust_todo::context::ContextManager;
+/// use rust_todo::review::start;
+/// use rust_todo::task::{Priority, Task, TimeHorizon};
+///
+/// let mut manager = ContextManager::new();
+/// let mut task = Task::new("Learn Rust".to_string(), TimeHorizon::LongTerm, Priority::Medium);
+/// task.created_at = (Utc::now() - Duration::days(200)).to_rfc3339();
+/// manager.active_context_mut().add_task(task);
+///
+/// let rules = AgingRules { promote: true, ..AgingRules::default() };
+/// let (report, ids) = start(&mut manager, &rules, Utc::now());
+///
+/// assert_eq!(report.promoted(), 1);
+/// let task = manager.active_context().find_task(&ids[0]).unwrap();
+/// assert_eq!(task.time_horizon, TimeHorizon::MidTerm);
+/// ```
+pub fn start(
+    manager: &mut ContextManager,
+    rules: &AgingRules,
+    now: DateTime<Utc>,
+) -> (AgingReport, Vec<String>) {
+    let report = aging::apply(manager, rules, now, false);
+    let ids = queue(manager.active_context());
+    (report, ids)
+}
+
+/// Carries out one answer for the task with the full ID `id`
+///
+/// # Arguments
+///
+/// * `context` - The context the task is in
+/// * `id` - The task's full ID, as returned by queue()
+/// * `action` - The answer
+/// * `now` - The instant recorded as the review time
+///
+/// # Returns
+///
+/// Ok(()) on success, or Err(AppError::TaskNotFound) if there is no such task.
+pub fn apply(
+    context: &mut Context,
+    id: &str,
+    action: ReviewAction,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    if action == ReviewAction::Delete {
+        context.delete_task(id)?;
+        return Ok(());
+    }
+
+    let task = context
+        .find_task_mut(id)
+        .ok_or_else(|| AppError::TaskNotFound(id.to_string()))?;
+    let before = task.clone();
+    match action {
+        ReviewAction::Skip => return Ok(()),
+        ReviewAction::Keep | ReviewAction::Delete => {}
+        ReviewAction::Complete => task.completed = true,
+        ReviewAction::Horizon(horizon) => task.time_horizon = horizon,
+        ReviewAction::Priority(priority) => task.priority = priority,
+        ReviewAction::Defer => task.time_horizon = deferral(task.time_horizon),
+    }
+    task.record_changes(&before);
+    task.reviewed_at = Some(now.to_rfc3339());
+
+    Ok(())
+}
+
+/// The next horizon further from now; long-term stays long-term
+fn deferral(horizon: TimeHorizon) -> TimeHorizon {
+    match horizon {
+        TimeHorizon::ShortTerm => TimeHorizon::MidTerm,
+        TimeHorizon::MidTerm | TimeHorizon::LongTerm => TimeHorizon::LongTerm,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(description: &str, horizon: TimeHorizon, created_at: &str) -> Task {
+        let mut task = Task::new(description.to_string(), horizon, Priority::Medium);
+        task.created_at = created_at.to_string();
+        task
+    }
+
+    #[test]
+    fn test_queue_is_oldest_first_per_horizon() {
+        let mut context = Context::new("work".to_string());
+        context.add_task(task(
+            "new mid",
+            TimeHorizon::MidTerm,
+            "2026-10-01T00:00:00+00:00",
+        ));
+        context.add_task(task(
+            "long",
+            TimeHorizon::LongTerm,
+            "2026-01-01T00:00:00+00:00",
+        ));
+        context.add_task(task(
+            "old mid",
+            TimeHorizon::MidTerm,
+            "2026-02-01T00:00:00+00:00",
+        ));
+        context.add_task(task(
+            "short",
+            TimeHorizon::ShortTerm,
+            "2026-10-10T00:00:00+00:00",
+        ));
+        let mut done = task("done", TimeHorizon::ShortTerm, "2026-01-01T00:00:00+00:00");
+        done.completed = true;
+        context.add_task(done);
+
+        let order: Vec<&str> = queue(&context)
+            .iter()
+            .map(|id| context.find_task(id).unwrap().description.as_str())
+            .collect();
+        assert_eq!(order, ["short", "old mid", "new mid", "long"]);
+    }
+
+    #[test]
+    fn test_start_reviews_promoted_tasks_in_their_new_horizon() {
+        let now = Utc::now();
+        let mut manager = ContextManager::new();
+        let days_ago = |days: i64| (now - chrono::Duration::days(days)).to_rfc3339();
+        manager.active_context_mut().add_task(task(
+            "stale mid",
+            TimeHorizon::MidTerm,
+            &days_ago(90),
+        ));
+        manager.active_context_mut().add_task(task(
+            "fresh short",
+            TimeHorizon::ShortTerm,
+            &days_ago(1),
+        ));
+        let rules = AgingRules {
+            promote: true,
+            ..AgingRules::default()
+        };
+
+        let (report, ids) = start(&mut manager, &rules, now);
+
+        assert_eq!(report.promoted(), 1);
+        let context = manager.active_context();
+        let order: Vec<&str> = ids
+            .iter()
+            .map(|id| context.find_task(id).unwrap().description.as_str())
+            .collect();
+        // The promoted task is short-term now, and older than the other one
+        assert_eq!(order, ["stale mid", "fresh short"]);
+        let promoted = context.find_task(&ids[0]).unwrap();
+        assert_eq!(promoted.time_horizon, TimeHorizon::ShortTerm);
+        assert_eq!(promoted.history[0].change.field, "time_horizon");
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        assert_eq!(
+            "P high".parse::<ReviewAction>().unwrap(),
+            ReviewAction::Priority(Priority::High)
+        );
+        assert_eq!(
+            "defer".parse::<ReviewAction>().unwrap(),
+            ReviewAction::Defer
+        );
+        assert!("p urgent".parse::<ReviewAction>().is_err());
+        assert!("k now".parse::<ReviewAction>().is_err());
+        assert!("".parse::<ReviewAction>().is_err());
+    }
+
+    #[test]
+    fn test_apply_records_review_but_not_an_edit() {
+        let now = Utc::now();
+        let mut context = Context::new("work".to_string());
+        context.add_task(task(
+            "a",
+            TimeHorizon::ShortTerm,
+            "2026-01-01T00:00:00+00:00",
+        ));
+        context.add_task(task(
+            "b",
+            TimeHorizon::ShortTerm,
+            "2026-01-02T00:00:00+00:00",
+        ));
+        context.add_task(task("c", TimeHorizon::MidTerm, "2026-01-03T00:00:00+00:00"));
+        let ids = queue(&context);
+
+        apply(&mut context, &ids[0], ReviewAction::Keep, now).unwrap();
+        let kept = context.find_task(&ids[0]).unwrap();
+        assert_eq!(kept.reviewed_at, Some(now.to_rfc3339()));
+        assert!(kept.updated_at.is_none());
+        assert!(kept.history.is_empty());
+
+        apply(&mut context, &ids[1], ReviewAction::Defer, now).unwrap();
+        let deferred = context.find_task(&ids[1]).unwrap();
+        assert_eq!(deferred.time_horizon, TimeHorizon::MidTerm);
+        assert_eq!(deferred.history[0].change.field, "time_horizon");
+
+        apply(&mut context, &ids[2], ReviewAction::Skip, now).unwrap();
+        assert!(context.find_task(&ids[2]).unwrap().reviewed_at.is_none());
+
+        apply(&mut context, &ids[2], ReviewAction::Delete, now).unwrap();
+        assert!(context.find_task(&ids[2]).is_none());
+        assert_eq!(context.tombstones.len(), 1);
+    }
+}
@@ -0,0 +1,682 @@
+// RPC module - JSON-RPC 2.0 over stdin/stdout for editor integrations
+// This module demonstrates serde_json::Value handling, polling threads, and shared locks
+//
+// `todo rpc` is a long-lived process an editor plugin can talk to instead of
+// running `todo` for every keystroke. Messages are JSON-RPC 2.0, one JSON
+// document per line in each direction (batches are supported).
+//
+// # Methods
+//
+// Each method mirrors the CLI command of the same name and works on the
+// active context. Parameters are passed by name; results are the shapes
+// documented in the output module (what `--output json` prints).
+//
+// | Method           | Params                                      | Result            |
+// |------------------|---------------------------------------------|-------------------|
+// | add              | description, horizon?, priority?, due?      | task              |
+// | list             | all?, horizon?                              | array of tasks    |
+// | show             | id                                          | task              |
+// | history          | id                                          | array of events   |
+// | complete         | id                                          | task              |
+// | edit             | id, description?, horizon?, priority?, due? | task              |
+// | delete           | id                                          | task              |
+// | context.list     |                                             | array of contexts |
+// | context.new      | name                                        | context           |
+// | context.switch   | name                                        | context           |
+// | context.delete   | name                                        | context           |
+//
+// `id` may be partial, as on the command line; `horizon`, `priority` and `due`
+// take the same strings as the CLI flags ("short", "high", "2026-10-31", ...).
+//
+// ```text
+// --> {"jsonrpc":"2.0","id":1,"method":"add","params":{"description":"Ship","priority":"high"}}
+// <-- {"jsonrpc":"2.0","id":1,"result":{"id":"5f0e3b1a-...","description":"Ship",...}}
+// ```
+//
+// # Errors
+//
+// Protocol errors use the standard codes (-32700 parse error, -32600 invalid
+// request, -32601 method not found, -32602 invalid params). A failed command
+// is -32000 with the AppError code in `data`:
+//
+// ```text
+// <-- {"jsonrpc":"2.0","id":2,"error":{"code":-32000,"message":"Task not found: abc",
+//      "data":{"code":"task_not_found"}}}
+// ```
+//
+// # Notifications
+//
+// When the data file is changed by anything other than this process (the CLI,
+// `todo serve`, a sync), a `data.changed` notification is sent so the plugin
+// can refresh:
+//
+// ```text
+// <-- {"jsonrpc":"2.0","method":"data.changed","params":{"path":"/home/me/.local/share/rust-todo/data.json"}}
+// ```
+//
+// Every request loads the data file afresh and saves it if the command changed
+// anything, so the process never works from stale data. When the data
+// directory is a git repository (see the git module), each such save is
+// committed with the method as the message, e.g. "rpc: complete".
+
+use crate::context::{ContextManager, ListOptions};
+use crate::error::{AppError, Result};
+use crate::git::GitRepo;
+use crate::output::{ContextOutput, HistoryEventOutput, TaskOutput};
+use crate::sort::SortOrder;
+use crate::store::Store;
+use crate::task::{parse_due, Priority, Task, TimeHorizon};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// How often the data file is checked for outside changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Standard JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Error code for a command that failed (the implementation-defined range)
+const COMMAND_FAILED: i64 = -32000;
+
+/// A JSON-RPC error: code, message and optional data
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+    data: Option<Value>,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let mut error = json!({ "code": self.code, "message": self.message });
+        if let Some(data) = &self.data {
+            error["data"] = data.clone();
+        }
+        error
+    }
+}
+
+impl From<AppError> for RpcError {
+    fn from(error: AppError) -> Self {
+        RpcError {
+            code: COMMAND_FAILED,
+            message: error.to_string(),
+            data: Some(json!({ "code": error.code() })),
+        }
+    }
+}
+
+/// What identifies the data file's current content, for change detection
+///
+/// Modification time alone can miss two writes within the clock's
+/// resolution, so the length is compared too.
+type FileStamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> FileStamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// A JSON-RPC session over one Store
+///
+/// The session remembers the data file as it last saw it, so the watcher
+/// can tell this session's own writes from outside changes.
+pub struct Session {
+    store: Store,
+    seen: Mutex<FileStamp>,
+}
+
+impl Session {
+    /// Creates a session for `store`
+    pub fn new(store: Store) -> Self {
+        let seen = Mutex::new(stamp(store.file_path()));
+        Session { store, seen }
+    }
+
+    /// Handles one incoming line and returns the line to send back, if any
+    ///
+    /// Notifications (requests without an `id`) get no response, and
+    /// neither does a batch made only of notifications.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::rpc::Session;
+    /// use rust_todo::store::Store;
+    ///
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let session = Session::new(Store::new(dir.path().join("data.json")));
+    ///
+    /// let reply = session
+    ///     .handle_line(r#"{"jsonrpc":"2.0","id":1,"method":"add","params":{"description":"Ship"}}"#)
+    ///     .unwrap();
+    /// assert!(reply.contains(r#""result""#));
+    /// ```
+    pub fn handle_line(&self, line: &str) -> Option<String> {
+        let message: Value = match serde_json::from_str(line) {
+            Ok(message) => message,
+            Err(error) => {
+                return Some(error_response(
+                    Value::Null,
+                    RpcError::new(PARSE_ERROR, format!("Parse error: {}", error)),
+                ))
+            }
+        };
+
+        match message {
+            Value::Array(batch) if batch.is_empty() => Some(error_response(
+                Value::Null,
+                RpcError::new(INVALID_REQUEST, "Invalid request: empty batch"),
+            )),
+            Value::Array(batch) => {
+                let responses: Vec<Value> =
+                    batch.into_iter().filter_map(|m| self.handle(m)).collect();
+                (!responses.is_empty()).then(|| Value::Array(responses).to_string())
+            }
+            message => self.handle(message).map(|response| response.to_string()),
+        }
+    }
+
+    /// Handles one request object; None for notifications
+    fn handle(&self, message: Value) -> Option<Value> {
+        let Value::Object(mut request) = message else {
+            return Some(error_value(
+                Value::Null,
+                RpcError::new(INVALID_REQUEST, "Invalid request: expected an object"),
+            ));
+        };
+
+        let id = request.remove("id");
+        let method = match (request.remove("jsonrpc"), request.remove("method")) {
+            (Some(Value::String(version)), Some(Value::String(method))) if version == "2.0" => {
+                method
+            }
+            _ => {
+                return Some(error_value(
+                    id.unwrap_or(Value::Null),
+                    RpcError::new(
+                        INVALID_REQUEST,
+                        "Invalid request: needs \"jsonrpc\": \"2.0\" and a method",
+                    ),
+                ))
+            }
+        };
+        let params = request.remove("params").unwrap_or_else(|| json!({}));
+
+        let result = self.call(&method, params);
+
+        // A request without an id is a notification: run it, but don't reply
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => error_value(id, error),
+        })
+    }
+
+    /// Runs a method against freshly loaded data, saving if it changed anything
+    fn call(&self, method: &str, params: Value) -> std::result::Result<Value, RpcError> {
+        // Hold the stamp lock across load and save so the watcher can't
+        // mistake this session's write for an outside change
+        let mut seen = self.seen.lock().unwrap_or_else(|p| p.into_inner());
+
+        let mut manager = self.store.load()?;
+        let (result, changed) = dispatch(&mut manager, method, params)?;
+        if changed {
+            self.store.save(&manager)?;
+            *seen = stamp(self.store.file_path());
+            if let Some(repo) = self.store.file_path().parent().and_then(GitRepo::open) {
+                repo.commit_all(&format!("rpc: {}", method))?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns true (once) if the data file changed since this session last saw it
+    pub fn poll_changed(&self) -> bool {
+        let current = stamp(self.store.file_path());
+        let mut seen = self.seen.lock().unwrap_or_else(|p| p.into_inner());
+        if current == *seen {
+            return false;
+        }
+        *seen = current;
+        true
+    }
+}
+
+/// Parses named params into `T`, reporting problems as invalid params
+fn params<T: DeserializeOwned>(params: Value) -> std::result::Result<T, RpcError> {
+    serde_json::from_value(params)
+        .map_err(|error| RpcError::new(INVALID_PARAMS, format!("Invalid params: {}", error)))
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NoParams {}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AddParams {
+    description: String,
+    #[serde(default = "default_horizon")]
+    horizon: String,
+    #[serde(default = "default_priority")]
+    priority: String,
+    due: Option<String>,
+}
+
+fn default_horizon() -> String {
+    "short".to_string()
+}
+
+fn default_priority() -> String {
+    "medium".to_string()
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ListParams {
+    #[serde(default)]
+    all: bool,
+    horizon: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IdParams {
+    id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EditParams {
+    id: String,
+    description: Option<String>,
+    horizon: Option<String>,
+    priority: Option<String>,
+    due: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NameParams {
+    name: String,
+}
+
+/// Runs a method; returns the result and whether the data changed
+fn dispatch(
+    manager: &mut ContextManager,
+    method: &str,
+    raw: Value,
+) -> std::result::Result<(Value, bool), RpcError> {
+    match method {
+        "add" => {
+            let p: AddParams = params(raw)?;
+            let horizon = TimeHorizon::from_str(&p.horizon)?;
+            let priority = Priority::from_str(&p.priority)?;
+            let mut task = Task::new(p.description, horizon, priority);
+            task.due = p.due.map(|d| parse_due(&d)).transpose()?.flatten();
+
+            let context = manager.active_context_mut();
+            context.add_task(task);
+            let task = context.tasks.last().expect("task was just added");
+            Ok((to_value(TaskOutput::new(task, &context.name))?, true))
+        }
+        "list" => {
+            let p: ListParams = params(raw)?;
+            let context = manager.active_context();
+
+            // Same selection and order as `todo list --output json`
+            let options = ListOptions {
+                show_all: p.all,
+                horizon: p.horizon.map(|h| TimeHorizon::from_str(&h)).transpose()?,
+                filter: None,
+                order: SortOrder::default(),
+                flat: false,
+            };
+
+            let items: Vec<TaskOutput> = options
+                .select(context)
+                .into_iter()
+                .map(|task| TaskOutput::new(task, &context.name))
+                .collect();
+            Ok((to_value(items)?, false))
+        }
+        "show" | "history" => {
+            let p: IdParams = params(raw)?;
+            let context = manager.active_context();
+            let full_id = context.resolve_id(&p.id)?;
+            let task = context
+                .find_task(&full_id)
+                .ok_or(AppError::TaskNotFound(p.id))?;
+
+            let result = if method == "show" {
+                to_value(TaskOutput::new(task, &context.name))?
+            } else {
+                to_value(HistoryEventOutput::timeline(task))?
+            };
+            Ok((result, false))
+        }
+        "complete" => {
+            let p: IdParams = params(raw)?;
+            let context = manager.active_context_mut();
+            let context_name = context.name.clone();
+            let full_id = context.resolve_id(&p.id)?;
+            let task = context
+                .find_task_mut(&full_id)
+                .ok_or(AppError::TaskNotFound(p.id))?;
+            task.mark_complete();
+
+            Ok((to_value(TaskOutput::new(task, &context_name))?, true))
+        }
+        "edit" => {
+            let p: EditParams = params(raw)?;
+            let horizon = p.horizon.map(|h| TimeHorizon::from_str(&h)).transpose()?;
+            let priority = p.priority.map(|p| Priority::from_str(&p)).transpose()?;
+            let due = p.due.map(|d| parse_due(&d)).transpose()?;
+
+            let context = manager.active_context_mut();
+            let context_name = context.name.clone();
+            let full_id = context.resolve_id(&p.id)?;
+            let task = context
+                .find_task_mut(&full_id)
+                .ok_or(AppError::TaskNotFound(p.id))?;
+            task.update(p.description, horizon, priority, due);
+
+            Ok((to_value(TaskOutput::new(task, &context_name))?, true))
+        }
+        "delete" => {
+            let p: IdParams = params(raw)?;
+            let context = manager.active_context_mut();
+            let full_id = context.resolve_id(&p.id)?;
+            let removed = context.delete_task(&full_id)?;
+            Ok((to_value(TaskOutput::new(&removed, &context.name))?, true))
+        }
+        "context.list" => {
+            let _: NoParams = params(raw)?;
+            let mut contexts: Vec<_> = manager.contexts.values().collect();
+            contexts.sort_by(|a, b| a.name.cmp(&b.name));
+
+            let items: Vec<ContextOutput> = contexts
+                .into_iter()
+                .map(|c| ContextOutput::new(c, c.name == manager.active_context))
+                .collect();
+            Ok((to_value(items)?, false))
+        }
+        "context.new" => {
+            let p: NameParams = params(raw)?;
+            manager.create_context(p.name.clone())?;
+            let context = &manager.contexts[&p.name];
+            Ok((to_value(ContextOutput::new(context, false))?, true))
+        }
+        "context.switch" => {
+            let p: NameParams = params(raw)?;
+            manager.switch_context(&p.name)?;
+            let context = manager.active_context();
+            Ok((to_value(ContextOutput::new(context, true))?, true))
+        }
+        "context.delete" => {
+            let p: NameParams = params(raw)?;
+            let removed = manager.contexts.get(&p.name).cloned();
+            manager.delete_context(&p.name)?;
+            let removed = removed.expect("delete_context succeeded, so it existed");
+            Ok((to_value(ContextOutput::new(&removed, false))?, true))
+        }
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Method not found: {}", method),
+        )),
+    }
+}
+
+/// Converts a result to JSON
+fn to_value<T: serde::Serialize>(value: T) -> std::result::Result<Value, RpcError> {
+    Ok(serde_json::to_value(value).map_err(AppError::from)?)
+}
+
+/// Builds an error response object
+fn error_value(id: Value, error: RpcError) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": error.to_json() })
+}
+
+/// Builds an error response line
+fn error_response(id: Value, error: RpcError) -> String {
+    error_value(id, error).to_string()
+}
+
+/// Writes one message line to stdout
+///
+/// The stdout lock is held for the whole line, so responses and
+/// notifications from the watcher never interleave.
+fn send(line: &str) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "{}", line)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Speaks JSON-RPC on stdin/stdout until stdin is closed
+///
+/// A background thread polls the data file and sends `data.changed`
+/// notifications for outside changes.
+pub fn serve_stdio(store: Store) -> Result<()> {
+    let session = Arc::new(Session::new(store));
+
+    let watcher = Arc::clone(&session);
+    let path = watcher.store.file_path().display().to_string();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(POLL_INTERVAL);
+        if watcher.poll_changed() {
+            let notification = json!({
+                "jsonrpc": "2.0",
+                "method": "data.changed",
+                "params": { "path": path },
+            });
+
+            // Stdout is gone once the client hangs up; the main loop
+            // notices that too and ends the process
+            if send(&notification.to_string()).is_err() {
+                return;
+            }
+        }
+    });
+
+    for line in std::io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = session.handle_line(&line) {
+            send(&response)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort::MoveDirection;
+
+    fn session() -> (tempfile::TempDir, Session) {
+        let dir = tempfile::tempdir().unwrap();
+        let session = Session::new(Store::new(dir.path().join("data.json")));
+        (dir, session)
+    }
+
+    fn call(session: &Session, id: u64, method: &str, params: Value) -> Value {
+        let request = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        let reply = session.handle_line(&request.to_string()).unwrap();
+        serde_json::from_str(&reply).unwrap()
+    }
+
+    #[test]
+    fn test_task_methods() {
+        let (_dir, session) = session();
+
+        let added = call(
+            &session,
+            1,
+            "add",
+            json!({"description": "Ship", "priority": "high"}),
+        );
+        assert_eq!(added["id"], 1);
+        assert_eq!(added["result"]["priority"], "High");
+        let short_id = added["result"]["short_id"].as_str().unwrap().to_string();
+
+        let edited = call(
+            &session,
+            2,
+            "edit",
+            json!({"id": short_id, "horizon": "mid"}),
+        );
+        assert_eq!(edited["result"]["time_horizon"], "MidTerm");
+
+        let completed = call(&session, 3, "complete", json!({"id": short_id}));
+        assert_eq!(completed["result"]["completed"], true);
+
+        let history = call(&session, 4, "history", json!({"id": short_id}));
+        assert_eq!(history["result"].as_array().unwrap().len(), 3);
+
+        let open = call(&session, 5, "list", json!({}));
+        assert!(open["result"].as_array().unwrap().is_empty());
+        let all = call(&session, 6, "list", json!({"all": true}));
+        assert_eq!(all["result"].as_array().unwrap().len(), 1);
+
+        call(&session, 7, "delete", json!({"id": short_id}));
+        let missing = call(&session, 8, "show", json!({"id": short_id}));
+        assert_eq!(missing["error"]["code"], COMMAND_FAILED);
+        assert_eq!(missing["error"]["data"]["code"], "task_not_found");
+    }
+
+    #[test]
+    fn test_list_keeps_the_manual_order() {
+        let (dir, session) = session();
+        for description in ["first", "second"] {
+            call(&session, 1, "add", json!({ "description": description }));
+        }
+
+        // Move "second" to the top, as `todo move` would
+        let store = Store::new(dir.path().join("data.json"));
+        let mut manager = store.load().unwrap();
+        let context = manager.active_context_mut();
+        let id = context.tasks[1].id.clone();
+        context.move_task(&id, MoveDirection::Top).unwrap();
+        store.save(&manager).unwrap();
+
+        let listed = call(&session, 2, "list", json!({}));
+        let order: Vec<&str> = listed["result"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|task| task["description"].as_str().unwrap())
+            .collect();
+        assert_eq!(order, ["second", "first"]);
+    }
+
+    #[test]
+    fn test_context_methods() {
+        let (_dir, session) = session();
+
+        call(&session, 1, "context.new", json!({"name": "work"}));
+        let switched = call(&session, 2, "context.switch", json!({"name": "work"}));
+        assert_eq!(switched["result"]["active"], true);
+
+        let list = call(&session, 3, "context.list", Value::Null);
+        assert_eq!(list["error"]["code"], INVALID_PARAMS);
+        let list = call(&session, 3, "context.list", json!({}));
+        assert_eq!(list["result"][1]["name"], "work");
+        assert_eq!(list["result"][1]["active"], true);
+
+        let duplicate = call(&session, 4, "context.new", json!({"name": "work"}));
+        assert_eq!(duplicate["error"]["data"]["code"], "context_already_exists");
+    }
+
+    #[test]
+    fn test_protocol_errors() {
+        let (_dir, session) = session();
+
+        let parse: Value = serde_json::from_str(&session.handle_line("{").unwrap()).unwrap();
+        assert_eq!(parse["error"]["code"], PARSE_ERROR);
+        assert_eq!(parse["id"], Value::Null);
+
+        let unknown = call(&session, 1, "frobnicate", json!({}));
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+
+        let bad_params = call(&session, 2, "add", json!({"text": "Ship"}));
+        assert_eq!(bad_params["error"]["code"], INVALID_PARAMS);
+
+        let no_version = session.handle_line(r#"{"id":3,"method":"list"}"#).unwrap();
+        assert!(no_version.contains(&INVALID_REQUEST.to_string()));
+    }
+
+    #[test]
+    fn test_notifications_and_batches() {
+        let (_dir, session) = session();
+
+        // A notification runs but gets no reply
+        let notification = r#"{"jsonrpc":"2.0","method":"add","params":{"description":"a"}}"#;
+        assert!(session.handle_line(notification).is_none());
+
+        let batch = format!(
+            r#"[{},{{"jsonrpc":"2.0","id":1,"method":"list","params":{{}}}}]"#,
+            notification
+        );
+        let replies: Value = serde_json::from_str(&session.handle_line(&batch).unwrap()).unwrap();
+        assert_eq!(replies.as_array().unwrap().len(), 1);
+        assert_eq!(replies[0]["result"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_changes_are_committed_in_git_mode() {
+        // Git-backed storage needs the git executable; skip where it's missing
+        if std::process::Command::new("git")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            return;
+        }
+        let (dir, session) = session();
+        let repo = GitRepo::init(dir.path()).unwrap();
+
+        call(&session, 1, "add", json!({ "description": "Ship" }));
+        call(&session, 2, "list", json!({}));
+
+        let log = repo.log(10).unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].message, "rpc: add");
+    }
+
+    #[test]
+    fn test_poll_changed_ignores_own_writes() {
+        let (_dir, session) = session();
+        call(&session, 1, "add", json!({"description": "mine"}));
+        assert!(!session.poll_changed());
+
+        // Another writer of the same file
+        let other = Store::new(session.store.file_path().to_path_buf());
+        let mut manager = other.load().unwrap();
+        manager.create_context("elsewhere".to_string()).unwrap();
+        other.save(&manager).unwrap();
+
+        assert!(session.poll_changed());
+        assert!(!session.poll_changed());
+    }
+}
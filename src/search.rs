@@ -0,0 +1,317 @@
+// Search module - ranked, typo-tolerant search across every context
+// This module demonstrates scoring with iterators, byte ranges into strings, and
+// using an external crate for edit distances
+//
+// `todo search <terms>` looks through the tasks of all contexts, not just the
+// active one, since the point is often to find out which context a task is in:
+//
+// ```bash
+// todo search deploy
+// todo search "relase notes"     # finds "Write release notes"
+// ```
+//
+// # Matching
+//
+// Descriptions are split into words, and each search term is compared with
+// every word. The best comparison counts:
+//
+// | Match                                  | Score |
+// |----------------------------------------|-------|
+// | the whole word                         | 100   |
+// | the start of the word                  | 80    |
+// | somewhere inside the word              | 60    |
+// | the word, with typos                   | 40 - 10 per typo |
+// | the start of the word, with typos      | 30 - 10 per typo |
+//
+// A typo is one inserted, deleted, changed or swapped character. Terms of
+// 4-7 characters may have one typo, longer terms two; shorter terms must
+// match exactly, or "a" would match everything.
+//
+// Tags are searched too, scoring 20 less than a description match. (Tasks
+// have no separate notes field, so descriptions and tags are all there is.)
+//
+// A task is a result when every term matches somewhere. Results are ranked
+// by total score, open tasks before completed ones when scores tie. The
+// matched words are returned as ranges of the description so they can be
+// highlighted.
+
+use crate::context::ContextManager;
+use crate::task::Task;
+use std::ops::Range;
+
+/// How much less a tag match scores than the same match in the description
+const TAG_PENALTY: u32 = 20;
+
+/// One search result
+#[derive(Debug, Clone)]
+pub struct SearchHit<'a> {
+    /// Name of the context the task is in
+    pub context: &'a str,
+
+    /// The matching task
+    pub task: &'a Task,
+
+    /// Relevance; higher is better
+    pub score: u32,
+
+    /// Byte ranges of the description that matched, sorted and not overlapping
+    pub highlights: Vec<Range<usize>>,
+}
+
+/// Searches the tasks of every context
+///
+/// # Arguments
+///
+/// * `manager` - The data to search
+/// * `query` - Search terms separated by spaces or punctuation; case is ignored
+///
+/// # Returns
+///
+/// The matching tasks, best first. An empty query matches nothing.
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::context::ContextManager;
+/// use rust_todo::search::search;
+/// use rust_todo::task::{Priority, Task, TimeHorizon};
+///
+/// let mut manager = ContextManager::new();
+/// manager.active_context_mut().add_task(Task::new(
+///     "Write release notes".to_string(),
+///     TimeHorizon::ShortTerm,
+///     Priority::High,
+/// ));
+///
+/// // One typo in "release" is forgiven
+/// let hits = search(&manager, "relase");
+/// assert_eq!(hits.len(), 1);
+/// assert_eq!(hits[0].context, "default");
+/// assert_eq!(&hits[0].task.description[hits[0].highlights[0].clone()], "release");
+/// ```
+pub fn search<'a>(manager: &'a ContextManager, query: &str) -> Vec<SearchHit<'a>> {
+    // Terms are split like descriptions, so "api-docs" looks for "api" and "docs"
+    let terms: Vec<String> = words(query).into_iter().map(|(_, word)| word).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut hits: Vec<SearchHit> = manager
+        .contexts
+        .values()
+        .flat_map(|context| {
+            context
+                .tasks
+                .iter()
+                .map(move |task| (context.name.as_str(), task))
+        })
+        .filter_map(|(context, task)| score_task(&terms, task).map(|hit| (context, task, hit)))
+        .map(|(context, task, (score, highlights))| SearchHit {
+            context,
+            task,
+            score,
+            highlights,
+        })
+        .collect();
+
+    // Best first; then open tasks, then a stable order for equal results
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.task.completed.cmp(&b.task.completed))
+            .then(a.context.cmp(b.context))
+            .then(a.task.description.cmp(&b.task.description))
+    });
+    hits
+}
+
+/// Scores a task against all terms, or None if some term doesn't match
+fn score_task(terms: &[String], task: &Task) -> Option<(u32, Vec<Range<usize>>)> {
+    let words = words(&task.description);
+    let mut total = 0;
+    let mut highlights: Vec<Range<usize>> = Vec::new();
+
+    for term in terms {
+        // Best match in the description, remembering which word it was
+        let in_description = words
+            .iter()
+            .filter_map(|(range, word)| score_word(term, word).map(|score| (score, range)))
+            .max_by_key(|(score, _)| *score);
+
+        // Best match among the tags
+        let in_tags = task
+            .tags
+            .iter()
+            .filter_map(|tag| score_word(term, &tag.to_lowercase()))
+            .max()
+            .map(|score| score.saturating_sub(TAG_PENALTY));
+
+        match (in_description, in_tags) {
+            (Some((score, range)), None) => {
+                total += score;
+                highlights.push(range.clone());
+            }
+            (Some((score, range)), Some(tag_score)) if score >= tag_score => {
+                total += score;
+                highlights.push(range.clone());
+            }
+            (_, Some(tag_score)) => total += tag_score,
+            (None, None) => return None,
+        }
+    }
+
+    highlights.sort_by_key(|range| range.start);
+    highlights.dedup();
+    Some((total, highlights))
+}
+
+/// Splits text into lowercased words with their byte ranges
+///
+/// Words are runs of letters and digits.
+fn words(text: &str) -> Vec<(Range<usize>, String)> {
+    let mut words = Vec::new();
+    let mut start = None;
+
+    for (index, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(index),
+            (false, Some(from)) => {
+                words.push((from..index, text[from..index].to_lowercase()));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// Scores one lowercased term against one lowercased word (see the table above)
+fn score_word(term: &str, word: &str) -> Option<u32> {
+    if word == term {
+        return Some(100);
+    }
+    if word.starts_with(term) {
+        return Some(80);
+    }
+    if word.contains(term) {
+        return Some(60);
+    }
+
+    let length = term.chars().count();
+    let allowed = match length {
+        0..=3 => return None,
+        4..=7 => 1,
+        _ => 2,
+    };
+
+    let typos = strsim::osa_distance(term, word);
+    if typos <= allowed {
+        return Some(40 - 10 * typos as u32);
+    }
+
+    // A mistyped start of a longer word ("documnt" for "documentation"). The
+    // prefix may be a little shorter or longer than the term, since typos
+    // can drop or add characters.
+    let typos = (length - allowed..=length + allowed)
+        .map(|length| {
+            let prefix: String = word.chars().take(length).collect();
+            strsim::osa_distance(term, &prefix)
+        })
+        .min()?;
+    (typos <= allowed).then(|| 30 - 10 * typos as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Priority, TimeHorizon};
+
+    fn manager_with(tasks: &[(&str, &str, &[&str])]) -> ContextManager {
+        let mut manager = ContextManager::new();
+        for (context, description, tags) in tasks {
+            if !manager.contexts.contains_key(*context) {
+                manager.create_context(context.to_string()).unwrap();
+            }
+            let mut task = Task::new(
+                description.to_string(),
+                TimeHorizon::ShortTerm,
+                Priority::Medium,
+            );
+            task.tags = tags.iter().map(|tag| tag.to_string()).collect();
+            manager.contexts.get_mut(*context).unwrap().add_task(task);
+        }
+        manager
+    }
+
+    fn descriptions(hits: &[SearchHit]) -> Vec<String> {
+        hits.iter()
+            .map(|hit| hit.task.description.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_score_word() {
+        assert_eq!(score_word("api", "api"), Some(100));
+        assert_eq!(score_word("doc", "docs"), Some(80));
+        assert_eq!(score_word("port", "report"), Some(60));
+        assert_eq!(score_word("relase", "release"), Some(30));
+        assert_eq!(score_word("recieve", "receive"), Some(30));
+        assert_eq!(score_word("documnt", "documentation"), Some(20));
+        // Short terms must match exactly
+        assert_eq!(score_word("apl", "api"), None);
+        assert_eq!(score_word("deploy", "review"), None);
+    }
+
+    #[test]
+    fn test_ranks_across_contexts() {
+        let manager = manager_with(&[
+            ("work", "Write API docs", &[]),
+            ("home", "Rapid prototyping", &[]),
+            ("default", "Fix the apiary", &[]),
+            ("work", "Unrelated", &[]),
+        ]);
+
+        let hits = search(&manager, "API");
+        assert_eq!(
+            descriptions(&hits),
+            ["Write API docs", "Fix the apiary", "Rapid prototyping"]
+        );
+        assert_eq!(hits[0].context, "work");
+        assert_eq!(hits[0].highlights, vec![Range { start: 6, end: 9 }]);
+        assert!(hits[0].score > hits[1].score);
+    }
+
+    #[test]
+    fn test_every_term_must_match() {
+        let manager = manager_with(&[
+            ("default", "Release notes", &[]),
+            ("default", "Release party", &[]),
+        ]);
+
+        let hits = search(&manager, "relese notse");
+        assert_eq!(descriptions(&hits), ["Release notes"]);
+        assert_eq!(hits[0].highlights, [0..7, 8..13]);
+
+        assert!(search(&manager, "release cake").is_empty());
+        assert!(search(&manager, " - ").is_empty());
+        assert_eq!(search(&manager, "release-notes").len(), 1);
+    }
+
+    #[test]
+    fn test_tags_and_completed_tasks() {
+        let mut manager = manager_with(&[
+            ("default", "Buy milk", &["groceries"]),
+            ("default", "Groceries budget", &[]),
+            ("default", "Buy eggs", &["groceries"]),
+        ]);
+        manager.active_context_mut().tasks[0].mark_complete();
+
+        let hits = search(&manager, "groceries");
+        // The description match outranks the tag matches, which carry no highlight
+        assert_eq!(
+            descriptions(&hits),
+            ["Groceries budget", "Buy eggs", "Buy milk"]
+        );
+        assert!(hits[1].highlights.is_empty());
+    }
+}
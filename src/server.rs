@@ -0,0 +1,719 @@
+// Server module - a local HTTP REST API over the task data
+// This module demonstrates request routing with slice patterns, Arc<Mutex<T>>, and worker threads
+//
+// `todo serve --bind 127.0.0.1:8080` lets other programs (e.g., a dashboard) read
+// and change tasks without shelling out to the CLI.
+//
+// # Endpoints
+//
+// | Method | Path                                    | Success                     |
+// |--------|-----------------------------------------|-----------------------------|
+// | GET    | /contexts                               | 200, array of contexts      |
+// | POST   | /contexts                               | 201, the new context        |
+// | DELETE | /contexts/{name}                        | 204                         |
+// | GET    | /contexts/{name}/tasks                  | 200, array of tasks         |
+// | POST   | /contexts/{name}/tasks                  | 201, the new task           |
+// | GET    | /contexts/{name}/tasks/{id}             | 200, the task               |
+// | PATCH  | /contexts/{name}/tasks/{id}             | 200, the updated task       |
+// | POST   | /contexts/{name}/tasks/{id}/complete    | 200, the completed task     |
+// | DELETE | /contexts/{name}/tasks/{id}             | 204                         |
+//
+// Task listing accepts `?horizon=short|mid|long` and `?completed=true|false`.
+// Task IDs must be given in full. Bodies use the same JSON shapes as
+// `--output json` (see the output module): tasks are the `Task` serde shape,
+// so a new task is e.g. `{"description": "Ship", "time_horizon": "ShortTerm",
+// "priority": "High"}`. Errors are the usual error object, with a status code
+// chosen by status_for().
+//
+// # Concurrency
+//
+// Requests are served by a few worker threads sharing one Store behind a
+// Mutex. Each request locks it, loads the data file, applies the change and
+// saves, so requests to this server never interleave, and changes made with
+// the CLI while the server runs are picked up by the next request.
+//
+// The lock is only held inside this process: the data file itself is not
+// locked. Saves are atomic, so the file is never half-written, but if a CLI
+// command (or another process) saves between a request's load and save, the
+// later save wins and the other change is lost.
+//
+// # Git-backed storage
+//
+// When the data directory is a git repository (see the git module), every
+// request that changes the data is committed like a CLI command, with the
+// request as the message, e.g. "serve: POST /contexts/work/tasks".
+
+use crate::context::ContextManager;
+use crate::error::{AppError, Result};
+use crate::git::GitRepo;
+use crate::output::{ContextOutput, ErrorBody, ErrorOutput, TaskOutput};
+use crate::store::Store;
+use crate::task::{Priority, Task, TimeHorizon};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+/// Number of threads accepting requests
+const WORKERS: usize = 4;
+
+/// An HTTP response: status code and optional JSON body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Option<String>,
+}
+
+impl Response {
+    /// A response with a JSON body
+    fn json<T: Serialize>(status: u16, value: &T) -> Response {
+        match serde_json::to_string(value) {
+            Ok(body) => Response {
+                status,
+                body: Some(body),
+            },
+            Err(error) => Response::error(&AppError::from(error)),
+        }
+    }
+
+    /// A 204 No Content response
+    fn no_content() -> Response {
+        Response {
+            status: 204,
+            body: None,
+        }
+    }
+
+    /// An error response with the standard error object
+    fn error(error: &AppError) -> Response {
+        Response::json(status_for(error), &ErrorOutput::from(error))
+    }
+
+    /// A 404 or 405 response for requests that match no endpoint
+    fn unrouted(status: u16, code: &'static str, message: String) -> Response {
+        Response::json(
+            status,
+            &ErrorOutput {
+                error: ErrorBody { code, message },
+            },
+        )
+    }
+}
+
+/// Maps an error to the HTTP status code it is reported with
+///
+/// - 400 for invalid input (bad values, malformed bodies)
+/// - 404 for unknown tasks and contexts
+/// - 409 for requests that conflict with the current data
+/// - 500 for storage failures
+pub fn status_for(error: &AppError) -> u16 {
+    match error {
+        AppError::TaskNotFound(_) | AppError::ContextNotFound(_) | AppError::ViewNotFound(_) => 404,
+        AppError::ContextAlreadyExists(_)
+        | AppError::CannotDeleteLastContext
+        | AppError::SyncConflict(_) => 409,
+        AppError::InvalidTimeHorizon(_)
+        | AppError::InvalidPriority(_)
+        | AppError::InvalidFileFormat(_)
+        | AppError::InvalidOutputFormat(_)
+        | AppError::InvalidMergeStrategy(_)
+        | AppError::InvalidSyncPolicy(_)
+        | AppError::InvalidShell(_)
+        | AppError::InvalidFilter(_)
+        | AppError::InvalidSortKey(_)
+        | AppError::InvalidMoveDirection(_)
+        | AppError::InvalidReviewAction(_)
+        | AppError::InvalidPeriod(_)
+        | AppError::InvalidDate(_)
+        | AppError::InvalidDueDate(_)
+        | AppError::InvalidRequest(_) => 400,
+        AppError::IoError(_)
+        | AppError::JsonError(_)
+        | AppError::InvalidDataFormat(_)
+        | AppError::GitError(_) => 500,
+    }
+}
+
+/// Body of POST /contexts
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewContext {
+    name: String,
+}
+
+/// Body of POST /contexts/{name}/tasks
+///
+/// Defaults match `todo add`: short-term, medium priority.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewTask {
+    description: String,
+    #[serde(default = "default_horizon")]
+    time_horizon: TimeHorizon,
+    #[serde(default = "default_priority")]
+    priority: Priority,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+fn default_horizon() -> TimeHorizon {
+    TimeHorizon::ShortTerm
+}
+
+fn default_priority() -> Priority {
+    Priority::Medium
+}
+
+/// Body of PATCH /contexts/{name}/tasks/{id}; absent fields are unchanged
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TaskPatch {
+    description: Option<String>,
+    time_horizon: Option<TimeHorizon>,
+    priority: Option<Priority>,
+    completed: Option<bool>,
+    tags: Option<Vec<String>>,
+}
+
+/// Handles one request against the data in `store`
+///
+/// This is the whole API minus the HTTP transport, so it can be tested (and
+/// reused) without opening a socket.
+///
+/// # Arguments
+///
+/// * `store` - The store, locked for the duration of the request
+/// * `method` - HTTP method, e.g. "GET"
+/// * `url` - Path and optional query string, e.g. "/contexts/work/tasks?horizon=short"
+/// * `body` - Request body (empty if none)
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Mutex;
+/// use rust_todo::server::handle;
+/// use rust_todo::store::Store;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// let store = Mutex::new(Store::new(dir.path().join("data.json")));
+///
+/// let response = handle(&store, "POST", "/contexts/default/tasks", r#"{"description":"Ship"}"#);
+/// assert_eq!(response.status, 201);
+///
+/// let response = handle(&store, "GET", "/contexts/nope/tasks", "");
+/// assert_eq!(response.status, 404);
+/// ```
+pub fn handle(store: &Mutex<Store>, method: &str, url: &str, body: &str) -> Response {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| percent_decode(segment, false))
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    // A poisoned lock only means another request panicked; the data on
+    // disk is still consistent because saves are atomic
+    let store = store
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let result = (|| -> Result<Response> {
+        let mut manager = store.load()?;
+        let (response, changed) = route(&mut manager, method, &segments, query, body)?;
+        if changed {
+            store.save(&manager)?;
+            if let Some(repo) = store.file_path().parent().and_then(GitRepo::open) {
+                repo.commit_all(&format!("serve: {} {}", method, path))?;
+            }
+        }
+        Ok(response)
+    })();
+
+    result.unwrap_or_else(|error| Response::error(&error))
+}
+
+/// Dispatches a request; returns the response and whether the data changed
+fn route(
+    manager: &mut ContextManager,
+    method: &str,
+    segments: &[&str],
+    query: &str,
+    body: &str,
+) -> Result<(Response, bool)> {
+    match (method, segments) {
+        ("GET", ["contexts"]) => {
+            let mut contexts: Vec<ContextOutput> = manager
+                .contexts
+                .values()
+                .map(|c| ContextOutput::new(c, c.name == manager.active_context))
+                .collect();
+            contexts.sort_by(|a, b| a.name.cmp(b.name));
+            Ok((Response::json(200, &contexts), false))
+        }
+        ("POST", ["contexts"]) => {
+            let request: NewContext = parse_body(body)?;
+            manager.create_context(request.name.clone())?;
+            let context = &manager.contexts[&request.name];
+            Ok((
+                Response::json(201, &ContextOutput::new(context, false)),
+                true,
+            ))
+        }
+        ("DELETE", ["contexts", name]) => {
+            manager.delete_context(name)?;
+            Ok((Response::no_content(), true))
+        }
+        ("GET", ["contexts", name, "tasks"]) => {
+            let context = context(manager, name)?;
+            let mut horizon = None;
+            let mut completed = None;
+            for (key, value) in query_pairs(query) {
+                match key.as_str() {
+                    "horizon" => horizon = Some(TimeHorizon::from_str(&value)?),
+                    "completed" => {
+                        completed = Some(value.parse::<bool>().map_err(|_| {
+                            AppError::InvalidRequest(format!(
+                                "completed must be true or false, not '{}'",
+                                value
+                            ))
+                        })?)
+                    }
+                    _ => {
+                        return Err(AppError::InvalidRequest(format!(
+                            "unknown query parameter '{}'",
+                            key
+                        )))
+                    }
+                }
+            }
+
+            let tasks: Vec<TaskOutput> = context
+                .sorted_tasks()
+                .into_iter()
+                .filter(|task| match horizon {
+                    Some(horizon) => task.time_horizon == horizon,
+                    None => true,
+                })
+                .filter(|task| match completed {
+                    Some(completed) => task.completed == completed,
+                    None => true,
+                })
+                .map(|task| TaskOutput::new(task, &context.name))
+                .collect();
+            Ok((Response::json(200, &tasks), false))
+        }
+        ("POST", ["contexts", name, "tasks"]) => {
+            let request: NewTask = parse_body(body)?;
+            require_description(&request.description)?;
+
+            let mut task = Task::new(request.description, request.time_horizon, request.priority);
+            task.tags = request.tags;
+            let context = context_mut(manager, name)?;
+            context.add_task(task);
+
+            let task = context.tasks.last().expect("task was just added");
+            Ok((
+                Response::json(201, &TaskOutput::new(task, &context.name)),
+                true,
+            ))
+        }
+        ("GET", ["contexts", name, "tasks", id]) => {
+            let context = context(manager, name)?;
+            let task = context
+                .find_task(id)
+                .ok_or_else(|| AppError::TaskNotFound(id.to_string()))?;
+            Ok((
+                Response::json(200, &TaskOutput::new(task, &context.name)),
+                false,
+            ))
+        }
+        ("PATCH", ["contexts", name, "tasks", id]) => {
+            let patch: TaskPatch = parse_body(body)?;
+            if let Some(description) = &patch.description {
+                require_description(description)?;
+            }
+
+            let context = context_mut(manager, name)?;
+            let task = context
+                .find_task_mut(id)
+                .ok_or_else(|| AppError::TaskNotFound(id.to_string()))?;
+
+            // Apply every field, then record what actually changed
+            let before = task.clone();
+            if let Some(description) = patch.description {
+                task.description = description;
+            }
+            if let Some(horizon) = patch.time_horizon {
+                task.time_horizon = horizon;
+            }
+            if let Some(priority) = patch.priority {
+                task.priority = priority;
+            }
+            if let Some(completed) = patch.completed {
+                task.completed = completed;
+            }
+            if let Some(tags) = patch.tags {
+                task.tags = tags;
+            }
+            task.record_changes(&before);
+
+            let response = Response::json(200, &TaskOutput::new(task, name));
+            Ok((response, true))
+        }
+        ("POST", ["contexts", name, "tasks", id, "complete"]) => {
+            let context = context_mut(manager, name)?;
+            let task = context
+                .find_task_mut(id)
+                .ok_or_else(|| AppError::TaskNotFound(id.to_string()))?;
+            task.mark_complete();
+
+            let response = Response::json(200, &TaskOutput::new(task, name));
+            Ok((response, true))
+        }
+        ("DELETE", ["contexts", name, "tasks", id]) => {
+            context_mut(manager, name)?.delete_task(id)?;
+            Ok((Response::no_content(), true))
+        }
+        (_, ["contexts"])
+        | (_, ["contexts", _])
+        | (_, ["contexts", _, "tasks"])
+        | (_, ["contexts", _, "tasks", _])
+        | (_, ["contexts", _, "tasks", _, "complete"]) => Ok((
+            Response::unrouted(
+                405,
+                "method_not_allowed",
+                format!("{} is not supported here", method),
+            ),
+            false,
+        )),
+        _ => Ok((
+            Response::unrouted(404, "not_found", "no such endpoint".to_string()),
+            false,
+        )),
+    }
+}
+
+/// Looks up a context by name
+fn context<'a>(manager: &'a ContextManager, name: &str) -> Result<&'a crate::context::Context> {
+    manager
+        .contexts
+        .get(name)
+        .ok_or_else(|| AppError::ContextNotFound(name.to_string()))
+}
+
+/// Looks up a context by name for modification
+fn context_mut<'a>(
+    manager: &'a mut ContextManager,
+    name: &str,
+) -> Result<&'a mut crate::context::Context> {
+    manager
+        .contexts
+        .get_mut(name)
+        .ok_or_else(|| AppError::ContextNotFound(name.to_string()))
+}
+
+/// Parses a JSON request body, reporting problems as 400 Bad Request
+fn parse_body<'a, T: Deserialize<'a>>(body: &'a str) -> Result<T> {
+    serde_json::from_str(body).map_err(|error| AppError::InvalidRequest(error.to_string()))
+}
+
+/// Rejects empty descriptions, which the CLI can't create either
+fn require_description(description: &str) -> Result<()> {
+    if description.trim().is_empty() {
+        return Err(AppError::InvalidRequest(
+            "description must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Splits a query string into decoded key/value pairs
+fn query_pairs(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key, true), percent_decode(value, true))
+        })
+        .collect()
+}
+
+/// Decodes %XX escapes, leaving malformed escapes as they are
+///
+/// `+` means a space only in form-encoded query strings (`plus_as_space`);
+/// in a path it is just a plus sign.
+fn percent_decode(text: &str, plus_as_space: bool) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        decoded.push(byte);
+                        i += 3;
+                        continue;
+                    }
+                    Err(_) => decoded.push(b'%'),
+                }
+            }
+            b'+' if plus_as_space => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Serves the API on `bind` until the process is stopped
+///
+/// # Arguments
+///
+/// * `bind` - Address to listen on, e.g. "127.0.0.1:8080"
+/// * `store` - The store to serve
+///
+/// # Returns
+///
+/// Only returns if the address can't be bound.
+pub fn serve(bind: &str, store: Store) -> Result<()> {
+    let server = tiny_http::Server::http(bind)
+        .map_err(|error| AppError::IoError(std::io::Error::other(error.to_string())))?;
+    let server = Arc::new(server);
+    let store = Arc::new(Mutex::new(store));
+
+    let workers: Vec<_> = (0..WORKERS)
+        .map(|_| {
+            let server = Arc::clone(&server);
+            let store = Arc::clone(&store);
+            std::thread::spawn(move || {
+                for mut request in server.incoming_requests() {
+                    let mut body = String::new();
+                    let response = match request.as_reader().read_to_string(&mut body) {
+                        Ok(_) => handle(&store, request.method().as_str(), request.url(), &body),
+                        Err(_) => Response::error(&AppError::InvalidRequest(
+                            "request body is not valid UTF-8".to_string(),
+                        )),
+                    };
+
+                    // A client that hung up can't be told anything; keep serving
+                    let _ = request.respond(to_http(response));
+                }
+            })
+        })
+        .collect();
+
+    for worker in workers {
+        let _ = worker.join();
+    }
+    Ok(())
+}
+
+/// Converts a Response to a tiny_http response
+fn to_http(response: Response) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+    let content_type =
+        tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+            .expect("static header is valid");
+    match response.body {
+        Some(body) => tiny_http::Response::from_string(body)
+            .with_status_code(response.status)
+            .with_header(content_type),
+        None => tiny_http::Response::from_data(Vec::new()).with_status_code(response.status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn store() -> (tempfile::TempDir, Mutex<Store>) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Mutex::new(Store::new(dir.path().join("data.json")));
+        (dir, store)
+    }
+
+    fn json(response: &Response) -> Value {
+        serde_json::from_str(response.body.as_deref().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_changes_are_committed_in_git_mode() {
+        // Git-backed storage needs the git executable; skip where it's missing
+        if std::process::Command::new("git")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            return;
+        }
+        let (dir, store) = store();
+        let repo = GitRepo::init(dir.path()).unwrap();
+
+        handle(&store, "POST", "/contexts", r#"{"name":"work"}"#);
+        handle(&store, "GET", "/contexts", "");
+        handle(&store, "POST", "/contexts", r#"{"name":"work"}"#);
+
+        // Reads and failed requests change nothing, so only one commit
+        let log = repo.log(10).unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].message, "serve: POST /contexts");
+    }
+
+    #[test]
+    fn test_contexts_endpoints() {
+        let (_dir, store) = store();
+
+        let created = handle(&store, "POST", "/contexts", r#"{"name":"work"}"#);
+        assert_eq!(created.status, 201);
+        assert_eq!(json(&created)["name"], "work");
+
+        let duplicate = handle(&store, "POST", "/contexts", r#"{"name":"work"}"#);
+        assert_eq!(duplicate.status, 409);
+        assert_eq!(json(&duplicate)["error"]["code"], "context_already_exists");
+
+        let list = json(&handle(&store, "GET", "/contexts", ""));
+        assert_eq!(list.as_array().unwrap().len(), 2);
+        assert_eq!(list[0]["name"], "default");
+        assert_eq!(list[0]["active"], true);
+
+        assert_eq!(handle(&store, "DELETE", "/contexts/work", "").status, 204);
+        assert_eq!(handle(&store, "DELETE", "/contexts/work", "").status, 404);
+        assert_eq!(
+            handle(&store, "DELETE", "/contexts/default", "").status,
+            409
+        );
+    }
+
+    #[test]
+    fn test_task_lifecycle() {
+        let (_dir, store) = store();
+
+        let created = handle(
+            &store,
+            "POST",
+            "/contexts/default/tasks",
+            r#"{"description":"Ship","priority":"High","tags":["v2"]}"#,
+        );
+        assert_eq!(created.status, 201);
+        let task = json(&created);
+        assert_eq!(task["time_horizon"], "ShortTerm");
+        assert_eq!(task["tags"][0], "v2");
+        let url = format!("/contexts/default/tasks/{}", task["id"].as_str().unwrap());
+
+        let patched = handle(&store, "PATCH", &url, r#"{"time_horizon":"MidTerm"}"#);
+        assert_eq!(patched.status, 200);
+        assert_eq!(json(&patched)["time_horizon"], "MidTerm");
+        assert_eq!(json(&patched)["history"][0]["field"], "time_horizon");
+
+        let completed = handle(&store, "POST", &format!("{}/complete", url), "");
+        assert_eq!(json(&completed)["completed"], true);
+
+        assert_eq!(handle(&store, "GET", &url, "").status, 200);
+        assert_eq!(handle(&store, "DELETE", &url, "").status, 204);
+        assert_eq!(handle(&store, "GET", &url, "").status, 404);
+
+        // Changes were saved to disk as they happened
+        let manager = store.lock().unwrap().load().unwrap();
+        assert!(manager.active_context().tasks.is_empty());
+        assert_eq!(manager.active_context().tombstones.len(), 1);
+    }
+
+    #[test]
+    fn test_list_filters() {
+        let (_dir, store) = store();
+        for body in [
+            r#"{"description":"a","time_horizon":"ShortTerm"}"#,
+            r#"{"description":"b","time_horizon":"LongTerm"}"#,
+        ] {
+            handle(&store, "POST", "/contexts/default/tasks", body);
+        }
+
+        let short = json(&handle(
+            &store,
+            "GET",
+            "/contexts/default/tasks?horizon=short",
+            "",
+        ));
+        assert_eq!(short.as_array().unwrap().len(), 1);
+        assert_eq!(short[0]["description"], "a");
+
+        let done = json(&handle(
+            &store,
+            "GET",
+            "/contexts/default/tasks?completed=true",
+            "",
+        ));
+        assert!(done.as_array().unwrap().is_empty());
+
+        let bad = handle(&store, "GET", "/contexts/default/tasks?horizon=soon", "");
+        assert_eq!(bad.status, 400);
+        assert_eq!(json(&bad)["error"]["code"], "invalid_time_horizon");
+    }
+
+    #[test]
+    fn test_bad_requests() {
+        let (_dir, store) = store();
+
+        let malformed = handle(&store, "POST", "/contexts/default/tasks", "{");
+        assert_eq!(malformed.status, 400);
+        assert_eq!(json(&malformed)["error"]["code"], "invalid_request");
+
+        let empty = handle(
+            &store,
+            "POST",
+            "/contexts/default/tasks",
+            r#"{"description":" "}"#,
+        );
+        assert_eq!(empty.status, 400);
+
+        let unknown_field = handle(&store, "POST", "/contexts", r#"{"name":"x","color":"red"}"#);
+        assert_eq!(unknown_field.status, 400);
+
+        assert_eq!(handle(&store, "PUT", "/contexts", "").status, 405);
+        assert_eq!(handle(&store, "GET", "/tasks", "").status, 404);
+    }
+
+    #[test]
+    fn test_percent_decoded_context_names() {
+        let (_dir, store) = store();
+        handle(&store, "POST", "/contexts", r#"{"name":"side project"}"#);
+
+        let response = handle(&store, "GET", "/contexts/side%20project/tasks", "");
+        assert_eq!(response.status, 200);
+        assert_eq!(percent_decode("a%2Fb+c%zz", true), "a/b c%zz");
+        assert_eq!(percent_decode("a%2Fb+c%zz", false), "a/b+c%zz");
+
+        // A plus sign in a path is a plus sign, not a space
+        handle(&store, "POST", "/contexts", r#"{"name":"a+b"}"#);
+        let response = handle(&store, "GET", "/contexts/a+b/tasks", "");
+        assert_eq!(response.status, 200);
+        let response = handle(&store, "GET", "/contexts/a%2Bb/tasks", "");
+        assert_eq!(response.status, 200);
+    }
+
+    #[test]
+    fn test_concurrent_writes_are_serialised() {
+        let (_dir, store) = store();
+        let store = Arc::new(store);
+
+        let threads: Vec<_> = (0..8)
+            .map(|i| {
+                let store = Arc::clone(&store);
+                std::thread::spawn(move || {
+                    let body = format!(r#"{{"description":"task {}"}}"#, i);
+                    handle(&store, "POST", "/contexts/default/tasks", &body).status
+                })
+            })
+            .collect();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), 201);
+        }
+
+        // No write was lost to a race between load and save
+        let tasks = json(&handle(&store, "GET", "/contexts/default/tasks", ""));
+        assert_eq!(tasks.as_array().unwrap().len(), 8);
+    }
+}
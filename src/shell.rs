@@ -0,0 +1,364 @@
+// Shell module - line splitting and tab completion for `todo shell`
+// This module demonstrates implementing library traits (rustyline's Helper) and a small tokenizer
+//
+// `todo shell` keeps the data loaded and reads commands line by line, in the
+// same syntax as the command line without the leading `todo`:
+//
+// ```text
+// default> add "Write docs" -p high
+// default> complete 5f0e
+// default> context switch work
+// work> save
+// work> exit
+// ```
+//
+// The data is saved by `save` and on exit (`exit`, `quit` or Ctrl-D). Changes
+// made to the data file by other processes while the shell is open are
+// overwritten when it saves.
+//
+// The read loop itself is in main.rs, next to the command handlers it calls.
+// This module provides the pieces that don't need them: splitting a line into
+// arguments, deciding which commands can't run inside the shell, and tab
+// completion of command names, task short IDs and context names.
+
+use crate::cli::{Cli, Commands};
+use crate::context::ContextManager;
+use crate::error::{AppError, Result};
+use clap::{CommandFactory, Parser};
+use rustyline::completion::{Completer, Pair};
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::Helper;
+
+/// Name of the history file, kept next to the data file
+pub const HISTORY_FILE: &str = "shell_history";
+
+/// Words the shell handles itself rather than passing to the parser
+pub const BUILTINS: [&str; 3] = ["save", "exit", "quit"];
+
+/// Commands whose argument is a task ID
+const TASK_COMMANDS: [&str; 6] = ["show", "history", "move", "complete", "edit", "delete"];
+
+/// Task commands that take several IDs
+const BULK_COMMANDS: [&str; 3] = ["complete", "edit", "delete"];
+
+/// Splits a line into arguments the way a POSIX shell would
+///
+/// Words are separated by whitespace. Single quotes keep everything
+/// literally, double quotes allow backslash escapes, and a backslash outside
+/// quotes escapes the next character.
+///
+/// # Errors
+///
+/// Returns AppError::InvalidRequest for an unterminated quote.
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::shell::split_line;
+///
+/// let words = split_line(r#"add "Write docs" -p high"#).unwrap();
+/// assert_eq!(words, ["add", "Write docs", "-p", "high"]);
+/// ```
+pub fn split_line(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(unterminated('\'')),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => word.extend(chars.next()),
+                        Some(c) => word.push(c),
+                        None => return Err(unterminated('"')),
+                    }
+                }
+            }
+            '\\' => word.get_or_insert_with(String::new).extend(chars.next()),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+fn unterminated(quote: char) -> AppError {
+    AppError::InvalidRequest(format!("unterminated {} quote", quote))
+}
+
+/// Explains why a command can't run inside the shell, or None if it can
+///
+/// The shell works on data loaded when it started. Commands that read or
+/// write the data file themselves would miss (or undo) the shell's unsaved
+/// changes, and commands that take over the terminal would never return.
+pub fn unavailable_reason(command: &Commands) -> Option<&'static str> {
+    match command {
+        Commands::Shell => Some("already in the shell"),
+        Commands::Rpc | Commands::Serve { .. } => {
+            Some("run it outside the shell; it serves the data file, not the shell's copy")
+        }
+        Commands::Sync { .. } | Commands::Git { .. } => {
+            Some("run it outside the shell; it works on the data file directly (save first)")
+        }
+        Commands::Completions { .. } => {
+            Some("run it outside the shell; the script is for your login shell")
+        }
+        _ => None,
+    }
+}
+
+/// Parses one shell line into a command the shell can run
+///
+/// # Returns
+///
+/// - Ok(Some(cli)) for a command to run
+/// - Ok(None) when clap printed help or a usage error instead
+/// - Err for unbalanced quotes, or a command that can't run in the shell
+///   (see unavailable_reason())
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::shell::parse_line;
+///
+/// assert!(parse_line("list --all").unwrap().is_some());
+/// assert!(parse_line("shell").is_err());
+/// ```
+pub fn parse_line(line: &str) -> Result<Option<Cli>> {
+    let words = split_line(line)?;
+    let cli = match Cli::try_parse_from(std::iter::once("todo".to_string()).chain(words)) {
+        Ok(cli) => cli,
+        Err(error) => {
+            // Covers `help` and `--help` as well as usage errors
+            let _ = error.print();
+            return Ok(None);
+        }
+    };
+
+    if let Some(reason) = unavailable_reason(&cli.command) {
+        return Err(AppError::InvalidRequest(reason.to_string()));
+    }
+    Ok(Some(cli))
+}
+
+/// Tab completion for the shell
+///
+/// The helper keeps a snapshot of what can be completed; the read loop
+/// refreshes it before each prompt, since commands change the data.
+#[derive(Debug, Default)]
+pub struct ShellHelper {
+    commands: Vec<String>,
+    tasks: Vec<(String, String)>,
+    contexts: Vec<String>,
+}
+
+impl ShellHelper {
+    /// Creates a helper that completes the CLI's command names
+    pub fn new() -> Self {
+        let mut commands: Vec<String> = Cli::command()
+            .get_subcommands()
+            .map(|command| command.get_name().to_string())
+            .chain(BUILTINS.iter().map(|builtin| builtin.to_string()))
+            .collect();
+        commands.sort();
+
+        ShellHelper {
+            commands,
+            ..ShellHelper::default()
+        }
+    }
+
+    /// Takes the task IDs and context names to complete from `manager`
+    pub fn refresh(&mut self, manager: &ContextManager) {
+        self.tasks = manager
+            .active_context()
+            .tasks
+            .iter()
+            .map(|task| {
+                let short_id = task.id.get(..6).unwrap_or(&task.id).to_string();
+                (short_id, task.description.clone())
+            })
+            .collect();
+
+        self.contexts = manager.contexts.keys().cloned().collect();
+        self.contexts.sort();
+    }
+
+    /// Completes the word ending at `pos`
+    ///
+    /// # Returns
+    ///
+    /// Where the word starts, and the candidates for it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::context::ContextManager;
+    /// use rust_todo::shell::ShellHelper;
+    ///
+    /// let mut helper = ShellHelper::new();
+    /// helper.refresh(&ContextManager::new());
+    ///
+    /// let (start, candidates) = helper.candidates("context switch de", 17);
+    /// assert_eq!(start, 15);
+    /// assert_eq!(candidates[0].replacement, "default");
+    /// ```
+    pub fn candidates(&self, line: &str, pos: usize) -> (usize, Vec<Pair>) {
+        let before = &line[..pos];
+        let word = before.rsplit(char::is_whitespace).next().unwrap_or("");
+        let start = pos - word.len();
+        let previous: Vec<&str> = before[..start].split_whitespace().collect();
+
+        let plain = |names: &[String]| -> Vec<Pair> {
+            names
+                .iter()
+                .filter(|name| name.starts_with(word))
+                .map(|name| Pair {
+                    display: name.clone(),
+                    replacement: name.clone(),
+                })
+                .collect()
+        };
+
+        let ids = || -> Vec<Pair> {
+            self.tasks
+                .iter()
+                .filter(|(short_id, _)| short_id.starts_with(word))
+                .map(|(short_id, description)| Pair {
+                    // Show the description so IDs are recognisable
+                    display: format!("{}  {}", short_id, description),
+                    replacement: short_id.clone(),
+                })
+                .collect()
+        };
+
+        let candidates = match previous.as_slice() {
+            [] => plain(&self.commands),
+            ["context"] => {
+                let actions = ["delete", "list", "new", "switch"].map(String::from);
+                plain(&actions)
+            }
+            ["context", "switch" | "delete"] => plain(&self.contexts),
+            [command] if TASK_COMMANDS.contains(command) => ids(),
+            // More IDs, unless the previous word is a flag expecting a value
+            [command, .., last] if BULK_COMMANDS.contains(command) && !last.starts_with('-') => {
+                ids()
+            }
+            _ => Vec::new(),
+        };
+        (start, candidates)
+    }
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        Ok(self.candidates(line, pos))
+    }
+}
+
+// Hints, highlighting and multi-line input are not used
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Priority, Task, TimeHorizon};
+
+    #[test]
+    fn test_split_line_quoting() {
+        assert_eq!(split_line("  list   --all ").unwrap(), ["list", "--all"]);
+        assert_eq!(
+            split_line(r#"add 'it''s' "say \"hi\"" a\ b"#).unwrap(),
+            ["add", "its", r#"say "hi""#, "a b"]
+        );
+        assert_eq!(split_line(r#"add """#).unwrap(), ["add", ""]);
+        assert!(split_line("add \"oops").is_err());
+    }
+
+    #[test]
+    fn test_unavailable_commands() {
+        assert!(unavailable_reason(&Commands::Shell).is_some());
+        assert!(unavailable_reason(&Commands::Rpc).is_some());
+        assert!(unavailable_reason(&Commands::Tui).is_none());
+    }
+
+    #[test]
+    fn test_completions_in_the_shell_is_an_error() {
+        // The completions command is started by run(), never by dispatch()
+        assert!(matches!(
+            parse_line("completions bash"),
+            Err(AppError::InvalidRequest(_))
+        ));
+        assert!(parse_line("add hello").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_completes_commands_ids_and_contexts() {
+        let mut manager = ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+        let task = Task::new(
+            "Write docs".to_string(),
+            TimeHorizon::ShortTerm,
+            Priority::Low,
+        );
+        let short_id = task.id[..6].to_string();
+        manager.active_context_mut().add_task(task);
+
+        let mut helper = ShellHelper::new();
+        helper.refresh(&manager);
+
+        let replacements = |line: &str| -> Vec<String> {
+            let (_, candidates) = helper.candidates(line, line.len());
+            candidates.into_iter().map(|c| c.replacement).collect()
+        };
+
+        assert_eq!(replacements("com"), ["complete", "completions"]);
+        assert!(replacements("").contains(&"save".to_string()));
+        assert_eq!(replacements("context sw"), ["switch"]);
+        assert_eq!(replacements("context delete w"), ["work"]);
+        assert_eq!(
+            replacements(&format!("show {}", &short_id[..2])),
+            [short_id.as_str()]
+        );
+
+        let (_, candidates) = helper.candidates("complete ", 9);
+        assert_eq!(candidates[0].display, format!("{}  Write docs", short_id));
+        assert_eq!(replacements("delete abc "), [short_id.as_str()]);
+        assert!(replacements("edit abc -t ").is_empty());
+        assert!(replacements("show abc ").is_empty());
+
+        // Nothing sensible to offer for descriptions or flags
+        assert!(replacements("add Wri").is_empty());
+    }
+}
@@ -0,0 +1,412 @@
+// Sort module - ordering tasks by one or more chosen fields
+// This module demonstrates std::cmp::Ordering, chaining comparisons with
+// Ordering::then_with, and stable sorting
+//
+// `todo list --sort` and saved views (`todo view save triage "priority:high"
+// --sort created`) take a comma-separated list of sort keys. A key is a field
+// name, optionally prefixed with `-` to reverse it:
+//
+// | Key           | Natural order                  |
+// |---------------|--------------------------------|
+// | `priority`    | high first                     |
+// | `horizon`     | short-term first               |
+// | `created`     | oldest first                   |
+// | `updated`     | least recently changed first   |
+// | `description` | A to Z, ignoring case          |
+// | `urgency`     | most urgent first              |
+// | `due`         | soonest due date first         |
+// | `manual`      | the order set with `todo move` |
+//
+// So `--sort priority,-created` lists high priority tasks first, and the
+// newest first among tasks of the same priority. Later keys only decide ties
+// left by earlier ones; tasks that tie on every key keep their manual order.
+// Tasks without a due date come after those with one, even with `-due`.
+//
+// # Manual order
+//
+// The manual order is simply the order tasks are stored in. New tasks go at
+// the end, and `todo move <id> up` swaps a task with the one before it of the
+// same horizon and priority (see Context::move_task), so the stored order is
+// the tiebreak for every sort without needing a position field on the task.
+//
+// # Urgency
+//
+// Urgency is computed when sorting (see the urgency module), with the weights
+// the order was given by with_weights(), or the default weights.
+
+use crate::error::{AppError, Result};
+use crate::task::{compare_timestamps, Task};
+use crate::urgency::{Urgency, UrgencyWeights};
+use chrono::{DateTime, Utc};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// A task field that can be sorted on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    Priority,
+    Horizon,
+    Created,
+    Updated,
+    Description,
+    Urgency,
+    Due,
+
+    /// Position in the stored (manual) order
+    Manual,
+}
+
+/// A field plus a direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: SortField,
+
+    /// True to reverse the field's natural order
+    pub reverse: bool,
+}
+
+impl SortKey {
+    /// Compares two tasks by this key
+    ///
+    /// Each task comes with its place in the manual order, which the
+    /// `manual` key compares. `weights` and `now` are for the `urgency` key.
+    fn compare(
+        &self,
+        (a, a_position): (&Task, usize),
+        (b, b_position): (&Task, usize),
+        weights: &UrgencyWeights,
+        now: DateTime<Utc>,
+    ) -> Ordering {
+        let natural = match self.field {
+            // Priority sorts most important first
+            SortField::Priority => b.priority.cmp(&a.priority),
+            SortField::Horizon => a.time_horizon.cmp(&b.time_horizon),
+            SortField::Created => compare_timestamps(&a.created_at, &b.created_at),
+            SortField::Updated => compare_timestamps(a.modified_at(), b.modified_at()),
+            SortField::Description => a
+                .description
+                .to_lowercase()
+                .cmp(&b.description.to_lowercase()),
+            SortField::Urgency => {
+                let urgency = |task| Urgency::of(task, weights, now).total();
+                urgency(b).total_cmp(&urgency(a))
+            }
+            SortField::Due => match (a.due, b.due) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                // No due date sorts last in either direction
+                (Some(_), None) => return Ordering::Less,
+                (None, Some(_)) => return Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            SortField::Manual => a_position.cmp(&b_position),
+        };
+        if self.reverse {
+            natural.reverse()
+        } else {
+            natural
+        }
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = AppError;
+
+    /// Parses a single sort key such as `created` or `-priority`
+    fn from_str(s: &str) -> Result<Self> {
+        let (reverse, name) = match s.strip_prefix('-') {
+            Some(name) => (true, name),
+            None => (false, s),
+        };
+        let field = match name.to_lowercase().as_str() {
+            "priority" => SortField::Priority,
+            "horizon" => SortField::Horizon,
+            "created" => SortField::Created,
+            "updated" | "modified" => SortField::Updated,
+            "description" | "desc" => SortField::Description,
+            "urgency" => SortField::Urgency,
+            "due" => SortField::Due,
+            "manual" => SortField::Manual,
+            _ => return Err(AppError::InvalidSortKey(s.to_string())),
+        };
+        Ok(SortKey { field, reverse })
+    }
+}
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self.field {
+            SortField::Priority => "priority",
+            SortField::Horizon => "horizon",
+            SortField::Created => "created",
+            SortField::Updated => "updated",
+            SortField::Description => "description",
+            SortField::Urgency => "urgency",
+            SortField::Due => "due",
+            SortField::Manual => "manual",
+        };
+        if self.reverse {
+            write!(f, "-{}", name)
+        } else {
+            write!(f, "{}", name)
+        }
+    }
+}
+
+/// A list of sort keys, applied in turn
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortOrder {
+    pub keys: Vec<SortKey>,
+
+    /// Weights for the `urgency` key
+    pub weights: UrgencyWeights,
+}
+
+impl SortOrder {
+    /// Uses `weights` for the `urgency` key instead of the default weights
+    pub fn with_weights(self, weights: UrgencyWeights) -> Self {
+        SortOrder { weights, ..self }
+    }
+
+    /// Sorts tasks by these keys
+    ///
+    /// The slice's current order is taken as the manual order: it decides
+    /// the `manual` key and breaks ties left by every other key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use rust_todo::sort::SortOrder;
+    /// use rust_todo::task::{Priority, Task, TimeHorizon};
+    ///
+    /// let low = Task::new("b".to_string(), TimeHorizon::ShortTerm, Priority::Low);
+    /// let high = Task::new("a".to_string(), TimeHorizon::LongTerm, Priority::High);
+    /// let mut tasks = vec![&low, &high];
+    ///
+    /// SortOrder::from_str("priority").unwrap().sort(&mut tasks);
+    /// assert_eq!(tasks[0].description, "a");
+    ///
+    /// SortOrder::from_str("horizon,-description").unwrap().sort(&mut tasks);
+    /// assert_eq!(tasks[0].description, "b");
+    /// ```
+    pub fn sort(&self, tasks: &mut Vec<&Task>) {
+        self.sort_by_task(tasks, |task| task);
+    }
+
+    /// Sorts items that each carry a task, such as (context, task) pairs
+    ///
+    /// Works like sort(), with `task` picking out each item's task.
+    pub fn sort_by_task<T>(&self, items: &mut Vec<T>, task: impl Fn(&T) -> &Task) {
+        let mut positioned: Vec<(usize, T)> = items.drain(..).enumerate().collect();
+        let now = Utc::now();
+
+        // sort_by is stable, so items that tie on every key keep their order
+        positioned.sort_by(|(a_position, a), (b_position, b)| {
+            self.keys.iter().fold(Ordering::Equal, |ordering, key| {
+                ordering.then_with(|| {
+                    key.compare(
+                        (task(a), *a_position),
+                        (task(b), *b_position),
+                        &self.weights,
+                        now,
+                    )
+                })
+            })
+        });
+
+        items.extend(positioned.into_iter().map(|(_, item)| item));
+    }
+}
+
+impl Default for SortOrder {
+    /// The order `todo list` uses without `--sort`: priority, then manual
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::sort::SortOrder;
+    ///
+    /// assert_eq!(SortOrder::default().to_string(), "priority");
+    /// ```
+    fn default() -> Self {
+        SortOrder {
+            keys: vec![SortKey {
+                field: SortField::Priority,
+                reverse: false,
+            }],
+            weights: UrgencyWeights::default(),
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = AppError;
+
+    /// Parses comma-separated sort keys such as `priority,-created`
+    fn from_str(s: &str) -> Result<Self> {
+        let keys = s
+            .split(',')
+            .map(|key| SortKey::from_str(key.trim()))
+            .collect::<Result<Vec<SortKey>>>()?;
+
+        // Sorting twice by the same field is a mistake in the key list
+        for (i, key) in keys.iter().enumerate() {
+            if keys[..i].iter().any(|earlier| earlier.field == key.field) {
+                return Err(AppError::InvalidSortKey(format!(
+                    "{} is given more than once in '{}'",
+                    key, s
+                )));
+            }
+        }
+
+        Ok(SortOrder {
+            keys,
+            weights: UrgencyWeights::default(),
+        })
+    }
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let keys: Vec<String> = self.keys.iter().map(SortKey::to_string).collect();
+        write!(f, "{}", keys.join(","))
+    }
+}
+
+/// Where `todo move` puts a task among the other tasks of its horizon and priority
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveDirection {
+    /// Swap with the task before it
+    Up,
+
+    /// Swap with the task after it
+    Down,
+
+    /// Move before all the others
+    Top,
+
+    /// Move after all the others
+    Bottom,
+}
+
+impl FromStr for MoveDirection {
+    type Err = AppError;
+
+    /// Parses up, down, top or bottom (case-insensitive)
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "up" => Ok(MoveDirection::Up),
+            "down" => Ok(MoveDirection::Down),
+            "top" => Ok(MoveDirection::Top),
+            "bottom" => Ok(MoveDirection::Bottom),
+            _ => Err(AppError::InvalidMoveDirection(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Priority, TimeHorizon};
+
+    fn task(description: &str, priority: Priority, created_at: &str) -> Task {
+        let mut task = Task::new(description.to_string(), TimeHorizon::MidTerm, priority);
+        task.created_at = created_at.to_string();
+        task
+    }
+
+    fn descriptions(tasks: &[&Task]) -> Vec<String> {
+        tasks.iter().map(|t| t.description.clone()).collect()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let order = SortOrder::from_str("Priority, -Created,desc").unwrap();
+        assert_eq!(order.keys.len(), 3);
+        assert_eq!(order.keys[1].field, SortField::Created);
+        assert!(order.keys[1].reverse);
+        assert_eq!(order.to_string(), "priority,-created,description");
+
+        assert!(matches!(
+            SortOrder::from_str("priority,deadline"),
+            Err(AppError::InvalidSortKey(_))
+        ));
+        assert!(matches!(
+            SortOrder::from_str("created,-created"),
+            Err(AppError::InvalidSortKey(_))
+        ));
+        assert!(SortOrder::from_str("").is_err());
+    }
+
+    #[test]
+    fn test_sort_by_due_puts_undated_tasks_last() {
+        let due = |day| chrono::NaiveDate::from_ymd_opt(2026, 10, day);
+        let mut a = task("a", Priority::Medium, "2026-01-01T00:00:00+00:00");
+        a.due = due(20);
+        let b = task("b", Priority::High, "2026-01-01T00:00:00+00:00");
+        let mut c = task("c", Priority::Low, "2026-01-01T00:00:00+00:00");
+        c.due = due(5);
+        let mut tasks = vec![&a, &b, &c];
+
+        SortOrder::from_str("due").unwrap().sort(&mut tasks);
+        assert_eq!(descriptions(&tasks), ["c", "a", "b"]);
+
+        SortOrder::from_str("-due").unwrap().sort(&mut tasks);
+        assert_eq!(descriptions(&tasks), ["a", "c", "b"]);
+    }
+
+    #[test]
+    fn test_sort_by_time_compares_instants() {
+        // Written with different offsets: b is the earlier instant
+        let a = task("a", Priority::Medium, "2026-03-10T10:00:00+00:00");
+        let b = task("b", Priority::Medium, "2026-03-10T11:00:00+02:00");
+        let c = task("c", Priority::Medium, "2026-03-11T00:00:00+00:00");
+        let mut tasks = vec![&c, &a, &b];
+
+        SortOrder::from_str("created").unwrap().sort(&mut tasks);
+        assert_eq!(descriptions(&tasks), ["b", "a", "c"]);
+
+        SortOrder::from_str("-created").unwrap().sort(&mut tasks);
+        assert_eq!(descriptions(&tasks), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn test_later_keys_break_ties_then_manual_order() {
+        let old_high = task("old high", Priority::High, "2026-01-01T00:00:00+00:00");
+        let new_high = task("new high", Priority::High, "2026-02-01T00:00:00+00:00");
+        let low_1 = task("low 1", Priority::Low, "2026-01-01T00:00:00+00:00");
+        let low_2 = task("low 2", Priority::Low, "2026-01-01T00:00:00+00:00");
+
+        let mut tasks = vec![&low_2, &old_high, &low_1, &new_high];
+        SortOrder::from_str("priority,-created")
+            .unwrap()
+            .sort(&mut tasks);
+        // The lows tie on both keys and keep their incoming order
+        assert_eq!(
+            descriptions(&tasks),
+            ["new high", "old high", "low 2", "low 1"]
+        );
+
+        // Urgency with weights that only count age: oldest first
+        let mut tasks = vec![&new_high, &low_2, &old_high];
+        let age_only = UrgencyWeights {
+            priority_high: 0.0,
+            priority_low: 0.0,
+            horizon_mid: 0.0,
+            ..UrgencyWeights::default()
+        };
+        SortOrder::from_str("urgency")
+            .unwrap()
+            .with_weights(age_only)
+            .sort(&mut tasks);
+        assert_eq!(descriptions(&tasks), ["low 2", "old high", "new high"]);
+
+        let mut tasks = vec![&low_2, &old_high, &low_1, &new_high];
+        SortOrder::from_str("-manual").unwrap().sort(&mut tasks);
+        assert_eq!(
+            descriptions(&tasks),
+            ["new high", "low 1", "old high", "low 2"]
+        );
+    }
+}
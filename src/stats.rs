@@ -0,0 +1,469 @@
+// Stats module - productivity statistics over all contexts
+// This module demonstrates grouping with BTreeMap, date arithmetic with
+// chrono's NaiveDate, and keeping computation separate from presentation
+//
+// `todo stats` answers "how am I doing?":
+//
+// - activity: tasks created and completed per day or per week, for the last
+//   few periods (weeks start on Monday)
+// - completion time: the average time from creation to completion, per
+//   horizon and per priority
+// - open and completed counts per context
+// - the oldest open tasks
+//
+// All dates are UTC, like the timestamps in the data file.
+//
+// # Where completion times come from
+//
+// A task's completion time is the "completed" entry in its history (see
+// Task::completed_at()). Tasks completed before history was recorded, or
+// imported without it, count as completed but are left out of activity and
+// completion times, since when they were completed isn't known.
+
+use crate::context::ContextManager;
+use crate::error::{AppError, Result};
+use crate::task::{Priority, Task, TimeHorizon};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// The length of one activity bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Day,
+    Week,
+}
+
+impl Period {
+    /// The first day of the period containing `date`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use rust_todo::stats::Period;
+    ///
+    /// // 2026-10-18 is a Sunday; its week started on Monday the 12th
+    /// let sunday = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+    /// assert_eq!(Period::Week.start(sunday), NaiveDate::from_ymd_opt(2026, 10, 12).unwrap());
+    /// assert_eq!(Period::Day.start(sunday), sunday);
+    /// ```
+    pub fn start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Period::Day => date,
+            Period::Week => date - Duration::days(i64::from(date.weekday().num_days_from_monday())),
+        }
+    }
+
+    /// The length of the period in days
+    pub fn days(self) -> i64 {
+        match self {
+            Period::Day => 1,
+            Period::Week => 7,
+        }
+    }
+}
+
+impl FromStr for Period {
+    type Err = AppError;
+
+    /// Parses day or week (case-insensitive)
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "day" | "daily" => Ok(Period::Day),
+            "week" | "weekly" => Ok(Period::Week),
+            _ => Err(AppError::InvalidPeriod(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Period::Day => write!(f, "day"),
+            Period::Week => write!(f, "week"),
+        }
+    }
+}
+
+/// Tasks created and completed in one period
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Activity {
+    /// First day of the period
+    pub start: NaiveDate,
+    pub created: usize,
+    pub completed: usize,
+}
+
+/// Average completion time of one group of tasks
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CompletionTime<G> {
+    /// The horizon or priority the group is made of
+    pub group: G,
+
+    /// Completed tasks with a known completion time
+    pub tasks: usize,
+
+    /// Average days from creation to completion; None if `tasks` is 0
+    pub average_days: Option<f64>,
+}
+
+/// Task counts of one context
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContextCounts {
+    pub context: String,
+    pub open: usize,
+    pub completed: usize,
+}
+
+/// One of the oldest open tasks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OldTask {
+    pub context: String,
+    pub id: String,
+    pub description: String,
+    pub time_horizon: TimeHorizon,
+    pub priority: Priority,
+
+    /// Whole days since the task was created
+    pub age_days: i64,
+}
+
+/// Everything `todo stats` shows
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    pub period: Period,
+
+    /// Oldest period first, ending with the current one
+    pub activity: Vec<Activity>,
+
+    /// Short-term, mid-term, long-term
+    pub by_horizon: Vec<CompletionTime<TimeHorizon>>,
+
+    /// High, medium, low
+    pub by_priority: Vec<CompletionTime<Priority>>,
+
+    /// By context name
+    pub contexts: Vec<ContextCounts>,
+
+    /// Oldest first
+    pub oldest: Vec<OldTask>,
+}
+
+/// Computes the statistics of every context
+///
+/// # Arguments
+///
+/// * `manager` - The data
+/// * `period` - Whether activity is counted per day or per week
+/// * `periods` - How many periods of activity to count, ending with the current one
+/// * `oldest` - How many of the oldest open tasks to list
+/// * `now` - The current instant
+///
+/// # Example
+///
+/// ```
+/// use chrono::Utc;
+/// use rust_todo::context::ContextManager;
+/// use rust_todo::stats::{compute, Period};
+/// use rust_todo::task::{Priority, Task, TimeHorizon};
+///
+/// let mut manager = ContextManager::new();
+/// let mut task = Task::new("Write docs".to_string(), TimeHorizon::ShortTerm, Priority::High);
+/// task.mark_complete();
+/// manager.active_context_mut().add_task(task);
+///
+/// let stats = compute(&manager, Period::Week, 4, 5, Utc::now());
+/// assert_eq!(stats.activity.len(), 4);
+/// assert_eq!(stats.activity[3].created, 1);
+/// assert_eq!(stats.activity[3].completed, 1);
+/// assert_eq!(stats.by_horizon[0].tasks, 1);
+/// ```
+pub fn compute(
+    manager: &ContextManager,
+    period: Period,
+    periods: usize,
+    oldest: usize,
+    now: DateTime<Utc>,
+) -> Stats {
+    let mut names: Vec<&String> = manager.contexts.keys().collect();
+    names.sort();
+    let tasks: Vec<(&str, &Task)> = names
+        .iter()
+        .map(|name| &manager.contexts[*name])
+        .flat_map(|context| {
+            context
+                .tasks
+                .iter()
+                .map(|task| (context.name.as_str(), task))
+        })
+        .collect();
+
+    // Empty buckets for every period, so quiet ones still show up
+    let current = period.start(now.date_naive());
+    let mut activity: BTreeMap<NaiveDate, Activity> = (0..periods as i64)
+        .map(|back| current - Duration::days(back * period.days()))
+        .map(|start| {
+            (
+                start,
+                Activity {
+                    start,
+                    created: 0,
+                    completed: 0,
+                },
+            )
+        })
+        .collect();
+
+    let mut horizon_days: BTreeMap<TimeHorizon, Vec<f64>> = BTreeMap::new();
+    let mut priority_days: BTreeMap<Priority, Vec<f64>> = BTreeMap::new();
+
+    for (_, task) in &tasks {
+        let created = parse(&task.created_at);
+        if let Some(bucket) =
+            created.and_then(|at| activity.get_mut(&period.start(at.date_naive())))
+        {
+            bucket.created += 1;
+        }
+
+        let Some(completed) = task.completed_at().and_then(parse) else {
+            continue;
+        };
+        if let Some(bucket) = activity.get_mut(&period.start(completed.date_naive())) {
+            bucket.completed += 1;
+        }
+        if let Some(created) = created {
+            let days = (completed - created).num_seconds() as f64 / 86_400.0;
+            horizon_days
+                .entry(task.time_horizon)
+                .or_default()
+                .push(days);
+            priority_days.entry(task.priority).or_default().push(days);
+        }
+    }
+
+    let average = |days: Option<&Vec<f64>>| {
+        let days = days.map(Vec::as_slice).unwrap_or_default();
+        let mean = (!days.is_empty()).then(|| days.iter().sum::<f64>() / days.len() as f64);
+        (days.len(), mean)
+    };
+    let by_horizon = [
+        TimeHorizon::ShortTerm,
+        TimeHorizon::MidTerm,
+        TimeHorizon::LongTerm,
+    ]
+    .into_iter()
+    .map(|group| {
+        let (tasks, average_days) = average(horizon_days.get(&group));
+        CompletionTime {
+            group,
+            tasks,
+            average_days,
+        }
+    })
+    .collect();
+    let by_priority = [Priority::High, Priority::Medium, Priority::Low]
+        .into_iter()
+        .map(|group| {
+            let (tasks, average_days) = average(priority_days.get(&group));
+            CompletionTime {
+                group,
+                tasks,
+                average_days,
+            }
+        })
+        .collect();
+
+    let contexts = names
+        .iter()
+        .map(|name| {
+            let context = &manager.contexts[*name];
+            let completed = context.tasks.iter().filter(|task| task.completed).count();
+            ContextCounts {
+                context: context.name.clone(),
+                open: context.tasks.len() - completed,
+                completed,
+            }
+        })
+        .collect();
+
+    // Unparseable creation times can't be aged, so they sort last
+    let mut open: Vec<(&str, &Task, Option<DateTime<Utc>>)> = tasks
+        .iter()
+        .filter(|(_, task)| !task.completed)
+        .map(|(context, task)| (*context, *task, parse(&task.created_at)))
+        .collect();
+    open.sort_by_key(|(_, _, created)| created.map_or(i64::MAX, |at| at.timestamp()));
+    let oldest = open
+        .into_iter()
+        .take(oldest)
+        .map(|(context, task, created)| OldTask {
+            context: context.to_string(),
+            id: task.id.clone(),
+            description: task.description.clone(),
+            time_horizon: task.time_horizon,
+            priority: task.priority,
+            age_days: created.map_or(0, |at| (now - at).num_days()),
+        })
+        .collect();
+
+    Stats {
+        period,
+        activity: activity.into_values().collect(),
+        by_horizon,
+        by_priority,
+        contexts,
+        oldest,
+    }
+}
+
+/// Parses an RFC 3339 timestamp into UTC
+fn parse(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{FieldChange, HistoryEntry};
+
+    /// A task created `created` days before `now`, completed `completed` days before
+    fn task(
+        horizon: TimeHorizon,
+        priority: Priority,
+        created: i64,
+        completed: Option<i64>,
+        now: DateTime<Utc>,
+    ) -> Task {
+        let mut task = Task::new("Task".to_string(), horizon, priority);
+        task.created_at = (now - Duration::days(created)).to_rfc3339();
+        if let Some(days) = completed {
+            task.completed = true;
+            task.history.push(HistoryEntry {
+                at: (now - Duration::days(days)).to_rfc3339(),
+                change: FieldChange {
+                    field: "completed".to_string(),
+                    from: "false".to_string(),
+                    to: "true".to_string(),
+                },
+            });
+        }
+        task
+    }
+
+    fn now() -> DateTime<Utc> {
+        // A Wednesday, at noon
+        DateTime::parse_from_rfc3339("2026-10-14T12:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_activity_per_day_and_week() {
+        let now = now();
+        let mut manager = ContextManager::new();
+        let context = manager.active_context_mut();
+        context.add_task(task(
+            TimeHorizon::ShortTerm,
+            Priority::High,
+            0,
+            Some(0),
+            now,
+        ));
+        context.add_task(task(TimeHorizon::ShortTerm, Priority::High, 1, None, now));
+        context.add_task(task(
+            TimeHorizon::ShortTerm,
+            Priority::High,
+            3,
+            Some(1),
+            now,
+        ));
+        context.add_task(task(TimeHorizon::ShortTerm, Priority::High, 30, None, now));
+
+        let daily = compute(&manager, Period::Day, 3, 0, now);
+        let counts: Vec<(usize, usize)> = daily
+            .activity
+            .iter()
+            .map(|day| (day.created, day.completed))
+            .collect();
+        assert_eq!(counts, [(0, 0), (1, 1), (1, 1)]);
+        assert_eq!(
+            daily.activity[2].start,
+            NaiveDate::from_ymd_opt(2026, 10, 14).unwrap()
+        );
+
+        // Monday the 12th to today, and the week before
+        let weekly = compute(&manager, Period::Week, 2, 0, now);
+        assert_eq!(weekly.activity[1].created, 2);
+        assert_eq!(weekly.activity[1].completed, 2);
+        assert_eq!(weekly.activity[0].created, 1);
+    }
+
+    #[test]
+    fn test_average_completion_time_per_group() {
+        let now = now();
+        let mut manager = ContextManager::new();
+        let context = manager.active_context_mut();
+        context.add_task(task(TimeHorizon::MidTerm, Priority::Low, 10, Some(8), now));
+        context.add_task(task(TimeHorizon::MidTerm, Priority::High, 10, Some(6), now));
+        context.add_task(task(TimeHorizon::MidTerm, Priority::High, 10, None, now));
+
+        // Completed, but without a recorded completion time
+        let mut unknown = task(TimeHorizon::ShortTerm, Priority::High, 10, None, now);
+        unknown.completed = true;
+        context.add_task(unknown);
+
+        let stats = compute(&manager, Period::Week, 1, 0, now);
+        assert_eq!(stats.by_horizon[0].tasks, 0);
+        assert_eq!(stats.by_horizon[0].average_days, None);
+        assert_eq!(stats.by_horizon[1].tasks, 2);
+        assert_eq!(stats.by_horizon[1].average_days, Some(3.0));
+        assert_eq!(stats.by_priority[0].group, Priority::High);
+        assert_eq!(stats.by_priority[0].average_days, Some(4.0));
+        assert_eq!(stats.by_priority[2].average_days, Some(2.0));
+    }
+
+    #[test]
+    fn test_context_counts_and_oldest() {
+        let now = now();
+        let mut manager = ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+        let mut old = task(TimeHorizon::LongTerm, Priority::Low, 90, None, now);
+        old.description = "Old".to_string();
+        manager.contexts.get_mut("work").unwrap().add_task(old);
+        let context = manager.active_context_mut();
+        context.add_task(task(TimeHorizon::ShortTerm, Priority::High, 5, None, now));
+        context.add_task(task(
+            TimeHorizon::ShortTerm,
+            Priority::High,
+            200,
+            Some(1),
+            now,
+        ));
+
+        let stats = compute(&manager, Period::Week, 1, 1, now);
+        let counts: Vec<(&str, usize, usize)> = stats
+            .contexts
+            .iter()
+            .map(|c| (c.context.as_str(), c.open, c.completed))
+            .collect();
+        assert_eq!(counts, [("default", 1, 1), ("work", 1, 0)]);
+
+        assert_eq!(stats.oldest.len(), 1);
+        assert_eq!(stats.oldest[0].description, "Old");
+        assert_eq!(stats.oldest[0].context, "work");
+        assert_eq!(stats.oldest[0].age_days, 90);
+    }
+
+    #[test]
+    fn test_period_from_str() {
+        assert_eq!("Week".parse::<Period>().unwrap(), Period::Week);
+        assert_eq!("daily".parse::<Period>().unwrap(), Period::Day);
+        assert!("month".parse::<Period>().is_err());
+    }
+}
@@ -166,6 +166,163 @@ impl StorageData {
     }
 }
 
+/// File formats supported by the export and import commands
+///
+/// - Native: Our own StorageData JSON format (the default)
+/// - Taskwarrior: The JSON produced by Taskwarrior's `task export`
+///
+/// Like TimeHorizon and Priority, this is parsed from a command-line string
+/// via FromStr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFormat {
+    Native,
+    Taskwarrior,
+}
+
+impl std::str::FromStr for DataFormat {
+    type Err = crate::error::AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "native" | "json" => Ok(DataFormat::Native),
+            "taskwarrior" | "tw" => Ok(DataFormat::Taskwarrior),
+            _ => Err(crate::error::AppError::InvalidFileFormat(s.to_string())),
+        }
+    }
+}
+
+/// Selects the part of the data that an export should contain
+///
+/// The default filter selects everything, which is what a plain
+/// `todo export` writes. Each field narrows the selection:
+///
+/// - `contexts`: Only these contexts (all contexts if None)
+/// - `horizon`: Only tasks in this time horizon
+/// - `open_only`: Only tasks that are not completed
+/// - `matching`: Only tasks matching a filter expression
+///
+/// Selected contexts are always written, even if no task in them matches,
+/// so the result is a complete and valid StorageData document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportFilter {
+    /// Names of the contexts to include, or None for all of them
+    pub contexts: Option<Vec<String>>,
+
+    /// Only include tasks in this time horizon
+    pub horizon: Option<crate::task::TimeHorizon>,
+
+    /// Only include tasks that are not completed
+    pub open_only: bool,
+
+    /// Only include tasks matching this filter expression
+    pub matching: Option<crate::filter::Filter>,
+}
+
+impl ExportFilter {
+    /// Returns true if the filter selects everything
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Builds a new ContextManager containing only the selected data
+    ///
+    /// The active context is kept if it was selected. Otherwise the first
+    /// selected context (by name) becomes active, so the invariant that the
+    /// active context exists still holds in the exported file.
+    ///
+    /// # Returns
+    ///
+    /// Ok(ContextManager) with the selected subset, or
+    /// Err(AppError::ContextNotFound) if a requested context doesn't exist, or
+    /// Err(AppError::InvalidRequest) if no context is selected at all.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::context::ContextManager;
+    /// use rust_todo::store::ExportFilter;
+    /// use rust_todo::task::{Task, TimeHorizon, Priority};
+    ///
+    /// let mut manager = ContextManager::new();
+    /// manager.create_context("work".to_string()).unwrap();
+    /// manager.contexts.get_mut("work").unwrap().add_task(
+    ///     Task::new("Ship".to_string(), TimeHorizon::ShortTerm, Priority::High),
+    /// );
+    ///
+    /// let filter = ExportFilter {
+    ///     contexts: Some(vec!["work".to_string()]),
+    ///     ..ExportFilter::default()
+    /// };
+    /// let subset = filter.apply(&manager)?;
+    ///
+    /// assert_eq!(subset.contexts.len(), 1);
+    /// assert_eq!(subset.active_context, "work");
+    /// # Ok::<(), rust_todo::error::AppError>(())
+    /// ```
+    pub fn apply(
+        &self,
+        manager: &crate::context::ContextManager,
+    ) -> crate::error::Result<crate::context::ContextManager> {
+        // Resolve the context names, failing on the first unknown one
+        let mut names: Vec<String> = match &self.contexts {
+            Some(requested) => {
+                for name in requested {
+                    if !manager.contexts.contains_key(name) {
+                        return Err(crate::error::AppError::ContextNotFound(name.clone()));
+                    }
+                }
+                requested.clone()
+            }
+            None => manager.contexts.keys().cloned().collect(),
+        };
+        names.sort();
+        names.dedup();
+
+        let mut contexts = HashMap::new();
+        for name in &names {
+            let source = &manager.contexts[name];
+            let tasks = source
+                .tasks
+                .iter()
+                .filter(|task| match self.horizon {
+                    Some(horizon) => task.time_horizon == horizon,
+                    None => true,
+                })
+                .filter(|task| !self.open_only || !task.completed)
+                .filter(|task| match &self.matching {
+                    Some(filter) => filter.matches(task),
+                    None => true,
+                })
+                .cloned()
+                .collect();
+
+            contexts.insert(
+                name.clone(),
+                Context {
+                    name: name.clone(),
+                    tasks,
+                    tombstones: Vec::new(),
+                },
+            );
+        }
+
+        // Keep the current active context when possible; the result needs
+        // at least one context to make active
+        let active_context = if contexts.contains_key(&manager.active_context) {
+            manager.active_context.clone()
+        } else {
+            names.first().cloned().ok_or_else(|| {
+                crate::error::AppError::InvalidRequest("no contexts selected".to_string())
+            })?
+        };
+
+        Ok(crate::context::ContextManager {
+            contexts,
+            active_context,
+        })
+    }
+}
+
 /// Handles file I/O operations for persisting application data
 ///
 /// The Store struct is responsible for loading and saving the application's
@@ -299,6 +456,21 @@ impl Store {
         Self { file_path }
     }
 
+    /// Returns the path of the data file
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::path::{Path, PathBuf};
+    /// use rust_todo::store::Store;
+    ///
+    /// let store = Store::new(PathBuf::from("data.json"));
+    /// assert_eq!(store.file_path(), Path::new("data.json"));
+    /// ```
+    pub fn file_path(&self) -> &std::path::Path {
+        &self.file_path
+    }
+
     /// Loads the ContextManager from disk
     ///
     /// This method demonstrates:
@@ -770,6 +942,243 @@ impl Store {
         // The caller can decide whether to merge or replace their current data
         Ok(manager)
     }
+
+    /// Imports a ContextManager from a file without loading it into memory first
+    ///
+    /// This is the streaming counterpart of import(), meant for very large
+    /// export files. The file is parsed incrementally through a buffered reader,
+    /// and every context and task is validated as soon as it has been read (see
+    /// the streaming module). The callback receives progress updates while the
+    /// import runs.
+    ///
+    /// Like import(), nothing is returned until the whole file has been
+    /// validated, so a failed import never modifies the caller's state.
+    ///
+    /// # Arguments
+    ///
+    /// * `import_path` - The path to the JSON file to import
+    /// * `progress` - Called with an ImportProgress after each task and context
+    ///
+    /// # Returns
+    ///
+    /// Ok(ContextManager) with the imported data, or the first error found.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_todo::store::Store;
+    ///
+    /// let store = Store::new(PathBuf::from("data.json"));
+    /// let manager = store
+    ///     .import_streaming(Path::new("huge-dump.json"), |progress| {
+    ///         eprint!("\r{} tasks", progress.tasks);
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn import_streaming(
+        &self,
+        import_path: &std::path::Path,
+        progress: impl FnMut(&crate::streaming::ImportProgress),
+    ) -> crate::error::Result<crate::context::ContextManager> {
+        let file = std::fs::File::open(import_path)?;
+
+        // The file size lets the progress callback show a percentage
+        let total_bytes = file.metadata()?.len();
+
+        crate::streaming::import_from_reader(file, total_bytes, progress)
+    }
+
+    /// Exports the ContextManager in Taskwarrior's `task export` format
+    ///
+    /// This writes a JSON array of Taskwarrior task objects that can be loaded
+    /// with `task import`. See the taskwarrior module for the field mapping.
+    ///
+    /// # Arguments
+    ///
+    /// * `manager` - A reference to the ContextManager to export
+    /// * `export_path` - The path where the export file should be created
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the export was successful, or an error if the file can't be written.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_todo::store::Store;
+    /// use rust_todo::context::ContextManager;
+    ///
+    /// let store = Store::new(PathBuf::from("data.json"));
+    /// let manager = ContextManager::new();
+    ///
+    /// store.export_taskwarrior(&manager, Path::new("taskwarrior.json")).unwrap();
+    /// ```
+    pub fn export_taskwarrior(
+        &self,
+        manager: &crate::context::ContextManager,
+        export_path: &std::path::Path,
+    ) -> crate::error::Result<()> {
+        // Create the parent directory if it doesn't exist, as in export()
+        if let Some(parent) = export_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let tasks = crate::taskwarrior::to_taskwarrior(manager);
+        let json = serde_json::to_string_pretty(&tasks)?;
+        std::fs::write(export_path, json)?;
+
+        Ok(())
+    }
+
+    /// Imports a ContextManager from a Taskwarrior `task export` file
+    ///
+    /// Like import(), this validates the whole file before returning, so a
+    /// failed import never modifies the caller's state. Along with the
+    /// ContextManager it returns an ImportReport describing what was skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `import_path` - The path to the Taskwarrior export file
+    ///
+    /// # Returns
+    ///
+    /// Ok((ContextManager, ImportReport)) if successful.
+    ///
+    /// Err(AppError) if the file can't be read, isn't valid JSON, or contains
+    /// values that can't be mapped (unknown status, priority or date format).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_todo::store::Store;
+    ///
+    /// let store = Store::new(PathBuf::from("data.json"));
+    /// let (manager, report) = store.import_taskwarrior(Path::new("tw.json")).unwrap();
+    /// println!("Imported {} tasks", report.imported);
+    /// ```
+    pub fn import_taskwarrior(
+        &self,
+        import_path: &std::path::Path,
+    ) -> crate::error::Result<(
+        crate::context::ContextManager,
+        crate::taskwarrior::ImportReport,
+    )> {
+        let contents = std::fs::read_to_string(import_path)?;
+        let tasks = crate::taskwarrior::parse_export(&contents)?;
+        crate::taskwarrior::from_taskwarrior(tasks)
+    }
+
+    /// Returns where the sync base for another data file is kept
+    ///
+    /// Each file this store syncs with gets its own base, stored in a `sync`
+    /// directory next to the data file. The base file is named after the
+    /// absolute path of the other file, with every character that is not a
+    /// letter or digit replaced by `_`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Path of the data file being synced with
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::path::{Path, PathBuf};
+    /// use rust_todo::store::Store;
+    ///
+    /// let store = Store::new(PathBuf::from("/data/todo/data.json"));
+    /// let base = store.sync_base_path(Path::new("/shared/todo.json")).unwrap();
+    /// assert_eq!(base, PathBuf::from("/data/todo/sync/_shared_todo_json.json"));
+    /// ```
+    pub fn sync_base_path(
+        &self,
+        other: &std::path::Path,
+    ) -> crate::error::Result<std::path::PathBuf> {
+        let absolute = std::path::absolute(other)?;
+        let name: String = absolute
+            .to_string_lossy()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+
+        let dir = self
+            .file_path
+            .parent()
+            .unwrap_or_else(|| std::path::Path::new("."));
+        Ok(dir.join("sync").join(format!("{}.json", name)))
+    }
+
+    /// Loads the base from the last sync with another file, if there was one
+    pub fn load_sync_base(
+        &self,
+        other: &std::path::Path,
+    ) -> crate::error::Result<Option<crate::context::ContextManager>> {
+        let path = self.sync_base_path(other)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        Store::new(path).load().map(Some)
+    }
+
+    /// Saves the result of a sync as the base for the next sync with `other`
+    pub fn save_sync_base(
+        &self,
+        other: &std::path::Path,
+        manager: &crate::context::ContextManager,
+    ) -> crate::error::Result<()> {
+        Store::new(self.sync_base_path(other)?).save(manager)
+    }
+
+    /// Path of the saved views file, next to the data file
+    pub fn views_path(&self) -> std::path::PathBuf {
+        self.file_path.with_file_name(crate::views::VIEWS_FILE)
+    }
+
+    /// Loads the saved views; no views file means no views
+    pub fn load_views(&self) -> crate::error::Result<crate::views::Views> {
+        let path = self.views_path();
+        if !path.exists() {
+            return Ok(crate::views::Views::default());
+        }
+        let json = std::fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Saves the views, atomically like save()
+    pub fn save_views(&self, views: &crate::views::Views) -> crate::error::Result<()> {
+        let path = self.views_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, serde_json::to_string_pretty(views)?)?;
+        std::fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+
+    /// Path of the config file, next to the data file
+    pub fn config_path(&self) -> std::path::PathBuf {
+        self.file_path.with_file_name(crate::config::CONFIG_FILE)
+    }
+
+    /// Loads the user settings; no config file means all defaults
+    ///
+    /// # Errors
+    ///
+    /// AppError::InvalidDataFormat, naming the file, if it isn't valid JSON
+    /// or a setting has the wrong type.
+    pub fn load_config(&self) -> crate::error::Result<crate::config::Config> {
+        let path = self.config_path();
+        if !path.exists() {
+            return Ok(crate::config::Config::default());
+        }
+        let json = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&json).map_err(|error| {
+            crate::error::AppError::InvalidDataFormat(format!("{}: {}", path.display(), error))
+        })
+    }
 }
 
 #[cfg(test)]
@@ -963,7 +1372,6 @@ mod tests {
         // We can't directly access file_path since it's private,
         // but we can verify the Store was created successfully
         // by using it in load/save operations
-        assert!(true); // Store created successfully
     }
 
     #[test]
@@ -1350,7 +1758,7 @@ mod tests {
         assert_eq!(loaded_task.description, "Test task with metadata");
         assert_eq!(loaded_task.time_horizon, TimeHorizon::MidTerm);
         assert_eq!(loaded_task.priority, Priority::Low);
-        assert_eq!(loaded_task.completed, true);
+        assert!(loaded_task.completed);
         assert_eq!(loaded_task.created_at, task_created_at);
     }
 
@@ -1737,9 +2145,9 @@ mod tests {
         let default_ctx = imported_manager.contexts.get("default").unwrap();
         assert_eq!(default_ctx.tasks.len(), 2);
         assert_eq!(default_ctx.tasks[0].description, "Default task 1");
-        assert_eq!(default_ctx.tasks[0].completed, false);
+        assert!(!default_ctx.tasks[0].completed);
         assert_eq!(default_ctx.tasks[1].description, "Default task 2");
-        assert_eq!(default_ctx.tasks[1].completed, true);
+        assert!(default_ctx.tasks[1].completed);
 
         // Verify work context tasks
         let work_ctx = imported_manager.contexts.get("work").unwrap();
@@ -1791,7 +2199,7 @@ mod tests {
         assert_eq!(task.description, "Task with metadata");
         assert_eq!(task.time_horizon, TimeHorizon::MidTerm);
         assert_eq!(task.priority, Priority::High);
-        assert_eq!(task.completed, true);
+        assert!(task.completed);
         assert_eq!(task.created_at, "2024-01-15T10:30:00Z");
     }
 
@@ -1833,4 +2241,316 @@ mod tests {
         assert_eq!(manager.contexts.get("default").unwrap().tasks.len(), 0);
         assert_eq!(manager.contexts.get("work").unwrap().tasks.len(), 0);
     }
+
+    #[test]
+    fn test_data_format_from_str() {
+        use std::str::FromStr;
+
+        assert_eq!(DataFormat::from_str("native").unwrap(), DataFormat::Native);
+        assert_eq!(DataFormat::from_str("JSON").unwrap(), DataFormat::Native);
+        assert_eq!(
+            DataFormat::from_str("taskwarrior").unwrap(),
+            DataFormat::Taskwarrior
+        );
+        assert_eq!(DataFormat::from_str("tw").unwrap(), DataFormat::Taskwarrior);
+        assert!(DataFormat::from_str("csv").is_err());
+    }
+
+    #[test]
+    fn test_store_taskwarrior_export_import_round_trip() {
+        // Test that a Taskwarrior export can be imported back without losing tasks
+        use crate::context::ContextManager;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let store = super::Store::new(temp_dir.path().join("data.json"));
+        let export_path = temp_dir.path().join("tw").join("export.json");
+
+        let mut manager = ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+        let mut task = Task::new("Tagged".to_string(), TimeHorizon::MidTerm, Priority::High);
+        task.tags = vec!["sprint12".to_string()];
+        manager.contexts.get_mut("work").unwrap().add_task(task);
+
+        store.export_taskwarrior(&manager, &export_path).unwrap();
+
+        // The file is a plain JSON array, as `task import` expects
+        let contents = std::fs::read_to_string(&export_path).unwrap();
+        assert!(contents.trim_start().starts_with('['));
+
+        let (imported, report) = store.import_taskwarrior(&export_path).unwrap();
+        assert_eq!(report.imported, 1);
+
+        let restored = &imported.contexts["work"].tasks[0];
+        assert_eq!(restored.description, "Tagged");
+        assert_eq!(restored.time_horizon, TimeHorizon::MidTerm);
+        assert_eq!(restored.priority, Priority::High);
+        assert_eq!(restored.tags, vec!["sprint12"]);
+    }
+
+    #[test]
+    fn test_store_import_streaming_matches_import() {
+        // Test that the streaming import reads exactly what import() reads
+        use crate::context::ContextManager;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let store = super::Store::new(temp_dir.path().join("data.json"));
+        let export_path = temp_dir.path().join("export.json");
+
+        let mut manager = ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+        for i in 0..50 {
+            manager
+                .contexts
+                .get_mut("work")
+                .unwrap()
+                .add_task(Task::new(
+                    format!("Task {}", i),
+                    TimeHorizon::ShortTerm,
+                    Priority::Medium,
+                ));
+        }
+        store.export(&manager, &export_path).unwrap();
+
+        let mut last = None;
+        let streamed = store
+            .import_streaming(&export_path, |p| last = Some(*p))
+            .unwrap();
+        let regular = store.import(&export_path).unwrap();
+
+        assert_eq!(streamed.active_context, regular.active_context);
+        assert_eq!(streamed.contexts.len(), regular.contexts.len());
+        assert_eq!(
+            streamed.contexts["work"].tasks.len(),
+            regular.contexts["work"].tasks.len()
+        );
+
+        let last = last.unwrap();
+        assert_eq!(last.tasks, 50);
+        assert_eq!(last.contexts, 2);
+        assert!(last.total_bytes > 0);
+    }
+
+    #[test]
+    fn test_store_import_streaming_missing_file() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let store = super::Store::new(temp_dir.path().join("data.json"));
+        let result = store.import_streaming(&temp_dir.path().join("nope.json"), |_| {});
+        assert!(matches!(result, Err(crate::error::AppError::IoError(_))));
+    }
+
+    #[test]
+    fn test_export_filter_default_selects_everything() {
+        use crate::context::ContextManager;
+
+        let mut manager = ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+        let mut done = Task::new("Done".to_string(), TimeHorizon::LongTerm, Priority::Low);
+        done.mark_complete();
+        manager.active_context_mut().add_task(done);
+
+        let filter = ExportFilter::default();
+        assert!(filter.is_empty());
+
+        let subset = filter.apply(&manager).unwrap();
+        assert_eq!(subset.contexts.len(), 2);
+        assert_eq!(subset.active_context, "default");
+        assert_eq!(subset.contexts["default"].tasks.len(), 1);
+    }
+
+    #[test]
+    fn test_export_filter_context_horizon_open_only() {
+        use crate::context::ContextManager;
+
+        let mut manager = ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+        manager.create_context("secret".to_string()).unwrap();
+
+        let work = manager.contexts.get_mut("work").unwrap();
+        work.add_task(Task::new(
+            "Short open".to_string(),
+            TimeHorizon::ShortTerm,
+            Priority::High,
+        ));
+        let mut done = Task::new(
+            "Short done".to_string(),
+            TimeHorizon::ShortTerm,
+            Priority::High,
+        );
+        done.mark_complete();
+        work.add_task(done);
+        work.add_task(Task::new(
+            "Long open".to_string(),
+            TimeHorizon::LongTerm,
+            Priority::High,
+        ));
+        manager
+            .contexts
+            .get_mut("secret")
+            .unwrap()
+            .add_task(Task::new(
+                "Do not leak".to_string(),
+                TimeHorizon::ShortTerm,
+                Priority::High,
+            ));
+
+        let filter = ExportFilter {
+            contexts: Some(vec!["work".to_string()]),
+            horizon: Some(TimeHorizon::ShortTerm),
+            open_only: true,
+            matching: None,
+        };
+        let subset = filter.apply(&manager).unwrap();
+
+        // Only the work context, with only its open short-term task
+        assert_eq!(subset.contexts.len(), 1);
+        let tasks = &subset.contexts["work"].tasks;
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].description, "Short open");
+
+        // "default" was active but not selected, so "work" becomes active
+        assert_eq!(subset.active_context, "work");
+
+        // The source data is untouched
+        assert_eq!(manager.contexts["work"].tasks.len(), 3);
+    }
+
+    #[test]
+    fn test_export_filter_matching_expression() {
+        use crate::context::ContextManager;
+        use std::str::FromStr;
+
+        let mut manager = ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+        for (name, description, priority) in [
+            ("default", "Design API", Priority::High),
+            ("default", "Tidy desk", Priority::High),
+            ("work", "API docs", Priority::Low),
+        ] {
+            manager.contexts.get_mut(name).unwrap().add_task(Task::new(
+                description.to_string(),
+                TimeHorizon::MidTerm,
+                priority,
+            ));
+        }
+
+        let filter = ExportFilter {
+            matching: Some(
+                crate::filter::Filter::from_str("desc~api and priority>=medium").unwrap(),
+            ),
+            ..ExportFilter::default()
+        };
+        let subset = filter.apply(&manager).unwrap();
+
+        // Every context is kept; only matching tasks are
+        assert_eq!(subset.contexts.len(), 2);
+        assert_eq!(subset.contexts["default"].tasks.len(), 1);
+        assert_eq!(
+            subset.contexts["default"].tasks[0].description,
+            "Design API"
+        );
+        assert!(subset.contexts["work"].tasks.is_empty());
+    }
+
+    #[test]
+    fn test_views_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = Store::new(temp_dir.path().join("data.json"));
+
+        // No file yet: no views
+        assert!(store.load_views().unwrap().views.is_empty());
+
+        let mut views = crate::views::Views::default();
+        views.save(
+            crate::views::View::new("triage", "priority:high", Some("created"), None).unwrap(),
+        );
+        store.save_views(&views).unwrap();
+
+        assert_eq!(store.views_path(), temp_dir.path().join("views.json"));
+        assert_eq!(store.load_views().unwrap(), views);
+    }
+
+    #[test]
+    fn test_load_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let store = Store::new(temp_dir.path().join("data.json"));
+
+        // No file: defaults
+        assert_eq!(
+            store.load_config().unwrap(),
+            crate::config::Config::default()
+        );
+
+        std::fs::write(store.config_path(), r#"{ "urgency": { "age": 3.5 } }"#).unwrap();
+        let config = store.load_config().unwrap();
+        assert_eq!(config.urgency.age, 3.5);
+        assert_eq!(config.urgency.age_days, 365);
+
+        std::fs::write(store.config_path(), r#"{ "urgency": { "age": "lots" } }"#).unwrap();
+        assert!(matches!(
+            store.load_config(),
+            Err(crate::error::AppError::InvalidDataFormat(message)) if message.contains("config.json")
+        ));
+    }
+
+    #[test]
+    fn test_export_filter_unknown_context() {
+        use crate::context::ContextManager;
+
+        let manager = ContextManager::new();
+        let filter = ExportFilter {
+            contexts: Some(vec!["nope".to_string()]),
+            ..ExportFilter::default()
+        };
+        assert!(matches!(
+            filter.apply(&manager),
+            Err(crate::error::AppError::ContextNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_export_filter_empty_context_selection() {
+        use crate::context::ContextManager;
+
+        let manager = ContextManager::new();
+        let filter = ExportFilter {
+            contexts: Some(Vec::new()),
+            ..ExportFilter::default()
+        };
+        assert!(matches!(
+            filter.apply(&manager),
+            Err(crate::error::AppError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_export_filter_result_is_valid_storage_data() {
+        // A filtered export can be imported again like any other export
+        use crate::context::ContextManager;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let store = super::Store::new(temp_dir.path().join("data.json"));
+        let export_path = temp_dir.path().join("subset.json");
+
+        let mut manager = ContextManager::new();
+        manager.create_context("b".to_string()).unwrap();
+        manager.create_context("a".to_string()).unwrap();
+
+        let filter = ExportFilter {
+            contexts: Some(vec!["b".to_string(), "a".to_string()]),
+            ..ExportFilter::default()
+        };
+        store
+            .export(&filter.apply(&manager).unwrap(), &export_path)
+            .unwrap();
+
+        let imported = store.import(&export_path).unwrap();
+        assert_eq!(imported.contexts.len(), 2);
+        // First selected context by name becomes active
+        assert_eq!(imported.active_context, "a");
+    }
 }
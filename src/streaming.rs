@@ -0,0 +1,610 @@
+// Streaming module - incremental parsing of large export files
+// This module demonstrates serde's Visitor and DeserializeSeed traits and reader adapters
+//
+// Store::import() reads the whole file into a String and then deserializes it, so
+// for a few hundred megabytes of JSON the process briefly holds the raw text, the
+// parser's buffers and the final data at the same time. This module instead reads
+// the file through a buffered reader and walks the document with serde visitors:
+//
+// - Each task is deserialized on its own and pushed straight into its context
+// - Each task and context is validated as soon as it has been read
+// - A progress callback is invoked as tasks arrive
+//
+// Peak memory is therefore the size of the imported data itself plus a fixed-size
+// read buffer, instead of a multiple of the file size. A validation error stops
+// the import immediately, reporting the line and column where it happened.
+//
+// # DeserializeSeed
+//
+// serde's usual Deserialize trait creates values from nothing. DeserializeSeed is
+// the stateful variant: the "seed" carries state (here the progress tracker) into
+// the deserializer, which is what lets us validate and report
+// progress while parsing rather than afterwards.
+
+use crate::context::{Context, ContextManager};
+use crate::error::{AppError, Result};
+use crate::store::StorageData;
+use crate::task::Task;
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{BufReader, Read};
+use std::rc::Rc;
+
+/// Progress information passed to the import callback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImportProgress {
+    /// Bytes of the file consumed so far
+    pub bytes_read: u64,
+
+    /// Total size of the file in bytes (0 if unknown)
+    pub total_bytes: u64,
+
+    /// Contexts fully read so far
+    pub contexts: usize,
+
+    /// Tasks read so far, across all contexts
+    pub tasks: usize,
+}
+
+impl ImportProgress {
+    /// Percentage of the file consumed, or None if the size is unknown
+    pub fn percent(&self) -> Option<u64> {
+        // checked_div returns None when the total is 0 (unknown size)
+        (self.bytes_read.min(self.total_bytes) * 100).checked_div(self.total_bytes)
+    }
+}
+
+/// A reader adapter that counts the bytes passing through it
+///
+/// serde_json takes ownership of its reader, so the count lives in a shared
+/// Rc<Cell<u64>> that the progress tracker can read while parsing is underway.
+struct CountingReader<R> {
+    inner: R,
+    count: Rc<Cell<u64>>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.set(self.count.get() + n as u64);
+        Ok(n)
+    }
+}
+
+/// State shared by all the seeds while a document is being parsed
+struct Tracker<'a> {
+    /// Running totals reported to the callback
+    progress: ImportProgress,
+
+    /// Byte counter updated by CountingReader
+    bytes_read: Rc<Cell<u64>>,
+
+    /// Called after every task and every context
+    callback: &'a mut dyn FnMut(&ImportProgress),
+}
+
+impl Tracker<'_> {
+    /// Refreshes the byte count and notifies the callback
+    fn report(&mut self) {
+        self.progress.bytes_read = self.bytes_read.get();
+        (self.callback)(&self.progress);
+    }
+}
+
+/// Parses a StorageData document from a reader, validating as it goes
+///
+/// # Arguments
+///
+/// * `reader` - Source of the JSON document
+/// * `total_bytes` - Size of the document if known (used for percentages), or 0
+/// * `callback` - Called with the running progress after each task and context
+///
+/// # Returns
+///
+/// The imported ContextManager, or the first error found. On error nothing
+/// has been returned to the caller, so no application state is modified.
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::streaming::import_from_reader;
+///
+/// let json = r#"{"version":"1.0.0","active_context":"default",
+///                "contexts":{"default":{"name":"default","tasks":[]}}}"#;
+/// let mut calls = 0;
+/// let manager = import_from_reader(json.as_bytes(), json.len() as u64, |_| calls += 1)?;
+///
+/// assert_eq!(manager.active_context, "default");
+/// assert_eq!(calls, 1); // one context read
+/// # Ok::<(), rust_todo::error::AppError>(())
+/// ```
+pub fn import_from_reader<R: Read>(
+    reader: R,
+    total_bytes: u64,
+    mut callback: impl FnMut(&ImportProgress),
+) -> Result<ContextManager> {
+    let bytes_read = Rc::new(Cell::new(0));
+    let counting = CountingReader {
+        // serde_json reads from its reader a byte at a time, so buffering
+        // underneath the counter is essential for performance
+        inner: BufReader::new(reader),
+        count: Rc::clone(&bytes_read),
+    };
+
+    let mut tracker = Tracker {
+        progress: ImportProgress {
+            bytes_read: 0,
+            total_bytes,
+            contexts: 0,
+            tasks: 0,
+        },
+        bytes_read,
+        callback: &mut callback,
+    };
+
+    let mut deserializer = serde_json::Deserializer::from_reader(counting);
+    let data = StorageSeed {
+        tracker: &mut tracker,
+    }
+    .deserialize(&mut deserializer)?;
+
+    // Reject trailing garbage after the top-level object
+    deserializer.end()?;
+
+    // The active context can only be checked once all contexts are known
+    if !data.contexts.contains_key(&data.active_context) {
+        return Err(AppError::InvalidDataFormat(format!(
+            "Active context '{}' does not exist in contexts",
+            data.active_context
+        )));
+    }
+
+    Ok(ContextManager {
+        contexts: data.contexts,
+        active_context: data.active_context,
+    })
+}
+
+/// Seed for the top-level StorageData object
+struct StorageSeed<'t, 'a> {
+    tracker: &'t mut Tracker<'a>,
+}
+
+impl<'de> DeserializeSeed<'de> for StorageSeed<'_, '_> {
+    type Value = StorageData;
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for StorageSeed<'_, '_> {
+    type Value = StorageData;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a todo export object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let mut version = None;
+        let mut active_context = None;
+        let mut contexts = None;
+
+        // Keys may appear in any order; unknown keys are skipped without
+        // being buffered, so future fields don't break older versions
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "version" => version = Some(map.next_value::<String>()?),
+                "active_context" => active_context = Some(map.next_value::<String>()?),
+                "contexts" => {
+                    contexts = Some(map.next_value_seed(ContextsSeed {
+                        tracker: &mut *self.tracker,
+                    })?)
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(StorageData {
+            version: version.ok_or_else(|| de::Error::missing_field("version"))?,
+            contexts: contexts.ok_or_else(|| de::Error::missing_field("contexts"))?,
+            active_context: active_context
+                .ok_or_else(|| de::Error::missing_field("active_context"))?,
+        })
+    }
+}
+
+/// Seed for the `contexts` object (context name → context)
+struct ContextsSeed<'t, 'a> {
+    tracker: &'t mut Tracker<'a>,
+}
+
+impl<'de> DeserializeSeed<'de> for ContextsSeed<'_, '_> {
+    type Value = HashMap<String, Context>;
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ContextsSeed<'_, '_> {
+    type Value = HashMap<String, Context>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map of context names to contexts")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let mut contexts = HashMap::new();
+
+        while let Some(key) = map.next_key::<String>()? {
+            let context = map.next_value_seed(ContextSeed {
+                tracker: &mut *self.tracker,
+            })?;
+
+            // The key and the embedded name must agree, otherwise lookups by
+            // name and the name shown to the user would disagree
+            if context.name != key {
+                return Err(de::Error::custom(format!(
+                    "context key '{}' does not match its name '{}'",
+                    key, context.name
+                )));
+            }
+            if contexts.contains_key(&key) {
+                return Err(de::Error::custom(format!("duplicate context '{}'", key)));
+            }
+
+            contexts.insert(key, context);
+            self.tracker.progress.contexts += 1;
+            self.tracker.report();
+        }
+
+        Ok(contexts)
+    }
+}
+
+/// Seed for a single context object
+struct ContextSeed<'t, 'a> {
+    tracker: &'t mut Tracker<'a>,
+}
+
+impl<'de> DeserializeSeed<'de> for ContextSeed<'_, '_> {
+    type Value = Context;
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ContextSeed<'_, '_> {
+    type Value = Context;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a context object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let mut name = None;
+        let mut tasks = None;
+        let mut tombstones = Vec::new();
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "name" => name = Some(map.next_value::<String>()?),
+                "tasks" => {
+                    tasks = Some(map.next_value_seed(TasksSeed {
+                        tracker: &mut *self.tracker,
+                    })?)
+                }
+                "tombstones" => tombstones = map.next_value()?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(Context {
+            name: name.ok_or_else(|| de::Error::missing_field("name"))?,
+            tasks: tasks.ok_or_else(|| de::Error::missing_field("tasks"))?,
+            tombstones,
+        })
+    }
+}
+
+/// Seed for a context's `tasks` array
+struct TasksSeed<'t, 'a> {
+    tracker: &'t mut Tracker<'a>,
+}
+
+impl<'de> DeserializeSeed<'de> for TasksSeed<'_, '_> {
+    type Value = Vec<Task>;
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for TasksSeed<'_, '_> {
+    type Value = Vec<Task>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array of tasks")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let mut tasks = Vec::new();
+
+        // IDs only need to be unique within a context, as for Store::load():
+        // a renaming import merge keeps the IDs of the copied context
+        let mut seen_ids = HashSet::new();
+
+        // Each element is deserialized with Task's normal Deserialize impl,
+        // one at a time, then validated before the next one is read
+        while let Some(task) = seq.next_element::<Task>()? {
+            validate_task(&task).map_err(de::Error::custom)?;
+
+            if !seen_ids.insert(task.id.clone()) {
+                return Err(de::Error::custom(format!(
+                    "duplicate task id '{}'",
+                    task.id
+                )));
+            }
+
+            tasks.push(task);
+            self.tracker.progress.tasks += 1;
+            self.tracker.report();
+        }
+
+        Ok(tasks)
+    }
+}
+
+/// Checks the parts of a task that serde's type checks can't
+fn validate_task(task: &Task) -> std::result::Result<(), String> {
+    if task.id.trim().is_empty() {
+        return Err("task with an empty id".to_string());
+    }
+    if task.description.trim().is_empty() {
+        return Err(format!("task '{}' has an empty description", task.id));
+    }
+    if chrono::DateTime::parse_from_rfc3339(&task.created_at).is_err() {
+        return Err(format!(
+            "task '{}' has an invalid created_at timestamp '{}'",
+            task.id, task.created_at
+        ));
+    }
+    if let Some(updated_at) = &task.updated_at {
+        if chrono::DateTime::parse_from_rfc3339(updated_at).is_err() {
+            return Err(format!(
+                "task '{}' has an invalid updated_at timestamp '{}'",
+                task.id, updated_at
+            ));
+        }
+    }
+    if let Some(reviewed_at) = &task.reviewed_at {
+        if chrono::DateTime::parse_from_rfc3339(reviewed_at).is_err() {
+            return Err(format!(
+                "task '{}' has an invalid reviewed_at timestamp '{}'",
+                task.id, reviewed_at
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Priority, TimeHorizon};
+
+    fn task_json(id: &str, description: &str) -> String {
+        format!(
+            r#"{{"id":"{}","description":"{}","time_horizon":"ShortTerm","priority":"High","completed":false,"created_at":"2024-01-15T10:30:00Z"}}"#,
+            id, description
+        )
+    }
+
+    fn import_str(json: &str) -> Result<ContextManager> {
+        import_from_reader(json.as_bytes(), json.len() as u64, |_| {})
+    }
+
+    #[test]
+    fn test_import_matches_regular_format() {
+        // A document written by the normal serializer imports unchanged
+        let mut manager = ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+        manager.active_context_mut().add_task(Task::new(
+            "Default task".to_string(),
+            TimeHorizon::MidTerm,
+            Priority::Low,
+        ));
+        manager
+            .contexts
+            .get_mut("work")
+            .unwrap()
+            .add_task(Task::new(
+                "Work task".to_string(),
+                TimeHorizon::LongTerm,
+                Priority::High,
+            ));
+
+        let data = StorageData::new(manager.contexts.clone(), "work".to_string());
+        let json = serde_json::to_string_pretty(&data).unwrap();
+
+        let imported = import_str(&json).unwrap();
+        assert_eq!(imported.active_context, "work");
+        assert_eq!(imported.contexts.len(), 2);
+        assert_eq!(imported.contexts["work"].tasks[0].description, "Work task");
+        assert_eq!(
+            imported.contexts["default"].tasks[0].time_horizon,
+            TimeHorizon::MidTerm
+        );
+    }
+
+    #[test]
+    fn test_import_keys_in_any_order_and_unknown_keys() {
+        let json = format!(
+            r#"{{"contexts":{{"a":{{"tasks":[{}],"extra":[1,2],"name":"a"}}}},"future":{{"x":1}},"active_context":"a","version":"1.0.0"}}"#,
+            task_json("t1", "One")
+        );
+        let manager = import_str(&json).unwrap();
+        assert_eq!(manager.contexts["a"].tasks.len(), 1);
+    }
+
+    #[test]
+    fn test_import_reports_progress() {
+        let json = format!(
+            r#"{{"version":"1.0.0","active_context":"a","contexts":{{"a":{{"name":"a","tasks":[{},{}]}},"b":{{"name":"b","tasks":[{}]}}}}}}"#,
+            task_json("t1", "One"),
+            task_json("t2", "Two"),
+            task_json("t3", "Three")
+        );
+
+        let mut updates = Vec::new();
+        import_from_reader(json.as_bytes(), json.len() as u64, |p| updates.push(*p)).unwrap();
+
+        // Three task updates plus two context updates, monotonically increasing
+        assert_eq!(updates.len(), 5);
+        let last = updates.last().unwrap();
+        assert_eq!(last.tasks, 3);
+        assert_eq!(last.contexts, 2);
+        assert!(updates
+            .windows(2)
+            .all(|w| w[0].bytes_read <= w[1].bytes_read));
+        assert!(last.bytes_read <= json.len() as u64);
+    }
+
+    #[test]
+    fn test_import_rejects_duplicate_task_ids() {
+        let json = format!(
+            r#"{{"version":"1.0.0","active_context":"a","contexts":{{"a":{{"name":"a","tasks":[{},{}]}}}}}}"#,
+            task_json("same", "One"),
+            task_json("same", "Two")
+        );
+        let error = import_str(&json).unwrap_err().to_string();
+        assert!(error.contains("duplicate task id 'same'"));
+        // serde_json includes the position of the failure
+        assert!(error.contains("line 1"));
+    }
+
+    #[test]
+    fn test_import_accepts_a_renamed_merge() {
+        // Importing a backup into the data it came from copies the context
+        // under a new name with the same task IDs
+        let mut manager = ContextManager::new();
+        manager.active_context_mut().add_task(Task::new(
+            "Task".to_string(),
+            TimeHorizon::ShortTerm,
+            Priority::Low,
+        ));
+        let backup = manager.clone();
+        crate::merge::merge(&mut manager, backup, crate::merge::MergeStrategy::Rename).unwrap();
+        assert!(manager.contexts.contains_key("default-imported"));
+
+        let data = StorageData::new(manager.contexts.clone(), manager.active_context.clone());
+        let json = serde_json::to_string(&data).unwrap();
+        let imported = import_str(&json).unwrap();
+        assert_eq!(
+            imported.contexts["default"].tasks[0].id,
+            imported.contexts["default-imported"].tasks[0].id
+        );
+    }
+
+    #[test]
+    fn test_import_keeps_tombstones() {
+        let json = r#"{"version":"1.0.0","active_context":"a","contexts":{"a":{"name":"a","tasks":[],
+            "tombstones":[{"id":"gone","deleted_at":"2024-01-15T10:30:00Z"}]}}}"#;
+        let manager = import_str(json).unwrap();
+        assert_eq!(manager.contexts["a"].tombstones[0].id, "gone");
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_tasks() {
+        let empty_description = format!(
+            r#"{{"version":"1.0.0","active_context":"a","contexts":{{"a":{{"name":"a","tasks":[{}]}}}}}}"#,
+            task_json("t1", " ")
+        );
+        assert!(import_str(&empty_description).is_err());
+
+        let bad_date = r#"{"version":"1.0.0","active_context":"a","contexts":{"a":{"name":"a","tasks":[
+            {"id":"t1","description":"x","time_horizon":"ShortTerm","priority":"High","completed":false,"created_at":"yesterday"}]}}}"#;
+        assert!(import_str(bad_date).is_err());
+
+        let bad_priority = r#"{"version":"1.0.0","active_context":"a","contexts":{"a":{"name":"a","tasks":[
+            {"id":"t1","description":"x","time_horizon":"ShortTerm","priority":"Urgent","completed":false,"created_at":"2024-01-15T10:30:00Z"}]}}}"#;
+        assert!(import_str(bad_priority).is_err());
+    }
+
+    #[test]
+    fn test_import_rejects_structural_problems() {
+        // Context key and name disagree
+        let mismatch =
+            r#"{"version":"1.0.0","active_context":"a","contexts":{"a":{"name":"b","tasks":[]}}}"#;
+        assert!(import_str(mismatch).is_err());
+
+        // Active context missing
+        let missing_active = r#"{"version":"1.0.0","active_context":"zzz","contexts":{"a":{"name":"a","tasks":[]}}}"#;
+        assert!(matches!(
+            import_str(missing_active),
+            Err(AppError::InvalidDataFormat(_))
+        ));
+
+        // Missing required field
+        let missing_version = r#"{"active_context":"a","contexts":{"a":{"name":"a","tasks":[]}}}"#;
+        assert!(import_str(missing_version).is_err());
+
+        // Trailing data after the document
+        let trailing = r#"{"version":"1.0.0","active_context":"a","contexts":{"a":{"name":"a","tasks":[]}}} {}"#;
+        assert!(import_str(trailing).is_err());
+
+        // Truncated file
+        let truncated =
+            r#"{"version":"1.0.0","active_context":"a","contexts":{"a":{"name":"a","tasks":["#;
+        assert!(import_str(truncated).is_err());
+    }
+
+    #[test]
+    fn test_progress_percent() {
+        let progress = ImportProgress {
+            bytes_read: 50,
+            total_bytes: 200,
+            contexts: 0,
+            tasks: 0,
+        };
+        assert_eq!(progress.percent(), Some(25));
+
+        let unknown = ImportProgress {
+            total_bytes: 0,
+            ..progress
+        };
+        assert_eq!(unknown.percent(), None);
+    }
+}
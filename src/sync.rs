@@ -0,0 +1,763 @@
+// Sync module - three-way merge between two copies of the data file
+// This module demonstrates HashMap indexing, generic helper functions, and callbacks for decisions
+//
+// `todo sync <other-data.json>` keeps two data files in step, for example one on a
+// laptop and one on a workstation, shared through a synced folder. Both files can
+// be edited independently between syncs.
+//
+// # Three-Way Merge
+//
+// Comparing only the two files can't tell "added here" from "deleted there", or
+// which side changed a field. So after every sync the merged result is stored as
+// the *base* for that pair of files (see Store::sync_base_path). The next sync
+// compares each side against the base, field by field:
+//
+// - changed on one side only: the change is taken
+// - changed the same way on both sides: nothing to decide
+// - changed differently on both sides: a conflict
+//
+// The fields compared are description, time horizon, priority, completion, tags,
+// due date and the context a task lives in.
+//
+// # Deletions
+//
+// Deleting a task leaves a tombstone in its context (see Context::delete_task).
+// A task that is gone from one side is deleted on the other if it has not been
+// changed there since the base; otherwise the deletion is a conflict.
+//
+// Once a sync has written a tombstone to both files it is in the base, and the
+// base alone reveals the deletion from then on, so the next sync drops it.
+// Tombstones therefore only live until the second sync after the deletion.
+//
+// A context that is in the base but gone from one side was deleted there. It is
+// deleted on the other side too, unless tasks in it survive the merge (they were
+// added or edited there since the base) or it is the local active context.
+//
+// # Conflicts
+//
+// Every conflict is passed to a callback that decides which side wins. The CLI
+// either asks the user or applies a policy (see SyncPolicy). Without a base (the
+// first sync), completion is the one field that never conflicts: a task that is
+// completed on either side ends up completed.
+//
+// Contexts are created on both sides as needed; the active context of each
+// file is left as it was.
+
+use crate::context::{Context, ContextManager, Tombstone};
+use crate::error::{AppError, Result};
+use crate::task::{compare_timestamps, HistoryEntry, Task};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+/// One of the two copies being synced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    /// This store's data file
+    Local,
+    /// The file given to `todo sync`
+    Remote,
+}
+
+/// How sync conflicts are resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncPolicy {
+    /// Ask the user about every conflict (the default)
+    Ask,
+    /// Local values win
+    Ours,
+    /// Remote values win
+    Theirs,
+    /// Whichever copy of the task was modified last wins
+    Newest,
+}
+
+impl FromStr for SyncPolicy {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ask" => Ok(SyncPolicy::Ask),
+            "ours" | "local" => Ok(SyncPolicy::Ours),
+            "theirs" | "remote" => Ok(SyncPolicy::Theirs),
+            "newest" => Ok(SyncPolicy::Newest),
+            _ => Err(AppError::InvalidSyncPolicy(s.to_string())),
+        }
+    }
+}
+
+impl SyncPolicy {
+    /// Decides a conflict, or returns None if the user has to be asked
+    pub fn resolve(self, conflict: &SyncConflict) -> Option<Side> {
+        match self {
+            SyncPolicy::Ask => None,
+            SyncPolicy::Ours => Some(Side::Local),
+            SyncPolicy::Theirs => Some(Side::Remote),
+            SyncPolicy::Newest => {
+                if compare_timestamps(&conflict.remote_modified, &conflict.local_modified).is_gt() {
+                    Some(Side::Remote)
+                } else {
+                    Some(Side::Local)
+                }
+            }
+        }
+    }
+}
+
+/// A field that was changed differently on both sides
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SyncConflict {
+    /// ID of the task
+    pub id: String,
+
+    /// The task's local description (or remote, if deleted locally), for display
+    pub description: String,
+
+    /// The conflicting field, "context", or "deleted" for edit/delete conflicts
+    pub field: String,
+
+    /// Local value ("deleted" if the task was deleted locally)
+    pub local: String,
+
+    /// Remote value ("deleted" if the task was deleted remotely)
+    pub remote: String,
+
+    /// When the local copy was last modified or deleted
+    pub local_modified: String,
+
+    /// When the remote copy was last modified or deleted
+    pub remote_modified: String,
+}
+
+/// A conflict and the side that won it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResolvedConflict {
+    #[serde(flatten)]
+    pub conflict: SyncConflict,
+
+    /// The side whose value was kept
+    pub kept: Side,
+}
+
+/// Number of tasks a sync changed in one of the two files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct SideChanges {
+    pub added: usize,
+    pub updated: usize,
+    pub deleted: usize,
+}
+
+impl SideChanges {
+    /// Returns true if the file did not change
+    pub fn is_empty(&self) -> bool {
+        self.added == 0 && self.updated == 0 && self.deleted == 0
+    }
+}
+
+/// Summary of a sync
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SyncReport {
+    /// Changes brought into the local file
+    pub local: SideChanges,
+
+    /// Changes brought into the remote file
+    pub remote: SideChanges,
+
+    /// Every conflict, in the order it was resolved
+    pub conflicts: Vec<ResolvedConflict>,
+}
+
+/// A task and the name of the context it is in
+type Located<'a> = (&'a str, &'a Task);
+
+/// Merges two copies of the data using the base from the last sync
+///
+/// # Arguments
+///
+/// * `base` - The result of the previous sync of these two files, if any
+/// * `local` - This store's data
+/// * `remote` - The other file's data
+/// * `resolve` - Called for each conflict; returns the side to keep, or an
+///   error to abort the sync
+///
+/// # Returns
+///
+/// The merged data, to be written to both files (and kept as the next base),
+/// and a report of what changed on each side. The active context is taken
+/// from `local`.
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::context::ContextManager;
+/// use rust_todo::sync::{sync, SyncPolicy};
+/// use rust_todo::task::{Task, TimeHorizon, Priority};
+///
+/// let local = ContextManager::new();
+/// let mut remote = ContextManager::new();
+/// remote.active_context_mut().add_task(
+///     Task::new("From the laptop".to_string(), TimeHorizon::ShortTerm, Priority::Low),
+/// );
+///
+/// let (merged, report) = sync(None, &local, &remote, |c| {
+///     Ok(SyncPolicy::Ours.resolve(c).unwrap())
+/// })?;
+/// assert_eq!(merged.active_context().tasks.len(), 1);
+/// assert_eq!(report.local.added, 1);
+/// assert!(report.remote.is_empty());
+/// # Ok::<(), rust_todo::error::AppError>(())
+/// ```
+pub fn sync(
+    base: Option<&ContextManager>,
+    local: &ContextManager,
+    remote: &ContextManager,
+    mut resolve: impl FnMut(&SyncConflict) -> Result<Side>,
+) -> Result<(ContextManager, SyncReport)> {
+    let base_tasks = base.map(index_tasks).unwrap_or_default();
+    let local_tasks = index_tasks(local);
+    let remote_tasks = index_tasks(remote);
+    let base_tombstones = base.map(index_tombstones).unwrap_or_default();
+    let local_tombstones = index_tombstones(local);
+    let remote_tombstones = index_tombstones(remote);
+
+    // Start from every context known to either side, without tasks
+    let mut merged = ContextManager {
+        contexts: HashMap::new(),
+        active_context: local.active_context.clone(),
+    };
+    for name in local.contexts.keys().chain(remote.contexts.keys()) {
+        merged
+            .contexts
+            .entry(name.clone())
+            .or_insert_with(|| Context::new(name.clone()));
+    }
+
+    let mut report = SyncReport::default();
+    let mut decide = |conflict: SyncConflict| -> Result<Side> {
+        let kept = resolve(&conflict)?;
+        report.conflicts.push(ResolvedConflict { conflict, kept });
+        Ok(kept)
+    };
+
+    // Local order first, then tasks only the remote has, so the local
+    // file's ordering is preserved
+    for id in ordered_ids(&[local, remote]) {
+        let base_task = base_tasks.get(id).copied();
+
+        let result = match (local_tasks.get(id), remote_tasks.get(id)) {
+            (Some(&ours), Some(&theirs)) => Some(merge_task(base_task, ours, theirs, &mut decide)?),
+            (Some(&ours), None) => keep_unless_deleted(
+                base_task,
+                ours,
+                Side::Local,
+                remote_tombstones.get(id).copied(),
+                &mut decide,
+            )?,
+            (None, Some(&theirs)) => keep_unless_deleted(
+                base_task,
+                theirs,
+                Side::Remote,
+                local_tombstones.get(id).copied(),
+                &mut decide,
+            )?,
+            (None, None) => None,
+        };
+
+        if let Some((context, task)) = result {
+            merged
+                .contexts
+                .entry(context.clone())
+                .or_insert_with(|| Context::new(context))
+                .add_task(task);
+        }
+    }
+
+    // Carry over both sides' tombstones, except for tasks that survived and
+    // deletions the base already records, and add tombstones for deletions
+    // only the base could reveal
+    let alive: HashSet<String> = index_tasks(&merged).into_keys().map(String::from).collect();
+    let mut buried: HashSet<String> = HashSet::new();
+    for manager in [local, remote] {
+        for context in manager.contexts.values() {
+            for tombstone in &context.tombstones {
+                if !alive.contains(&tombstone.id)
+                    && !base_tombstones.contains_key(tombstone.id.as_str())
+                    && buried.insert(tombstone.id.clone())
+                {
+                    if let Some(target) = merged.contexts.get_mut(&context.name) {
+                        target.tombstones.push(tombstone.clone());
+                    }
+                }
+            }
+        }
+    }
+    for (id, (context, _)) in local_tasks.iter().chain(remote_tasks.iter()) {
+        if !alive.contains(*id) && buried.insert(id.to_string()) {
+            if let Some(target) = merged.contexts.get_mut(*context) {
+                target.tombstones.push(Tombstone {
+                    id: id.to_string(),
+                    deleted_at: chrono::Utc::now().to_rfc3339(),
+                });
+            }
+        }
+    }
+
+    // Drop contexts one side deleted since the base, once they are empty
+    if let Some(base) = base {
+        merged.contexts.retain(|name, context| {
+            let deleted = base.contexts.contains_key(name)
+                && !(local.contexts.contains_key(name) && remote.contexts.contains_key(name));
+            !deleted || !context.tasks.is_empty() || *name == local.active_context
+        });
+    }
+
+    let merged_tasks = index_tasks(&merged);
+    report.local = count_changes(&local_tasks, &merged_tasks);
+    report.remote = count_changes(&remote_tasks, &merged_tasks);
+
+    Ok((merged, report))
+}
+
+/// Maps each task ID to the task and the name of its context
+fn index_tasks(manager: &ContextManager) -> HashMap<&str, Located<'_>> {
+    let mut index = HashMap::new();
+    for (name, context) in &manager.contexts {
+        for task in &context.tasks {
+            index.insert(task.id.as_str(), (name.as_str(), task));
+        }
+    }
+    index
+}
+
+/// Maps each deleted task ID to its tombstone
+fn index_tombstones(manager: &ContextManager) -> HashMap<&str, &Tombstone> {
+    manager
+        .contexts
+        .values()
+        .flat_map(|context| context.tombstones.iter())
+        .map(|tombstone| (tombstone.id.as_str(), tombstone))
+        .collect()
+}
+
+/// Lists every task ID once, in context-name order and then file order
+fn ordered_ids<'a>(managers: &[&'a ContextManager]) -> Vec<&'a str> {
+    let mut seen = HashSet::new();
+    let mut ids = Vec::new();
+    for manager in managers {
+        let mut contexts: Vec<&Context> = manager.contexts.values().collect();
+        contexts.sort_by(|a, b| a.name.cmp(&b.name));
+        for context in contexts {
+            for task in &context.tasks {
+                if seen.insert(task.id.as_str()) {
+                    ids.push(task.id.as_str());
+                }
+            }
+        }
+    }
+    ids
+}
+
+/// Merges a task present on both sides, field by field
+fn merge_task(
+    base: Option<Located>,
+    (local_context, ours): Located,
+    (remote_context, theirs): Located,
+    decide: &mut impl FnMut(SyncConflict) -> Result<Side>,
+) -> Result<(String, Task)> {
+    let base_task = base.map(|(_, task)| task);
+    let mut field = |name: &str, show: &dyn Fn(&Task) -> String| -> Result<Side> {
+        let (l, r) = (show(ours), show(theirs));
+        if l == r {
+            return Ok(Side::Local);
+        }
+        match base_task.map(show) {
+            Some(b) if b == l => Ok(Side::Remote),
+            Some(b) if b == r => Ok(Side::Local),
+            // Without a base, a completion on either side wins
+            None if name == "completed" => Ok(if ours.completed {
+                Side::Local
+            } else {
+                Side::Remote
+            }),
+            _ => decide(SyncConflict {
+                id: ours.id.clone(),
+                description: ours.description.clone(),
+                field: name.to_string(),
+                local: l,
+                remote: r,
+                local_modified: ours.modified_at().to_string(),
+                remote_modified: theirs.modified_at().to_string(),
+            }),
+        }
+    };
+
+    let mut task = ours.clone();
+    if field("description", &|t| t.description.clone())? == Side::Remote {
+        task.description = theirs.description.clone();
+    }
+    if field("time_horizon", &|t| format!("{:?}", t.time_horizon))? == Side::Remote {
+        task.time_horizon = theirs.time_horizon;
+    }
+    if field("priority", &|t| format!("{:?}", t.priority))? == Side::Remote {
+        task.priority = theirs.priority;
+    }
+    if field("completed", &|t| t.completed.to_string())? == Side::Remote {
+        task.completed = theirs.completed;
+    }
+    if field("tags", &|t| t.tags.join(","))? == Side::Remote {
+        task.tags = theirs.tags.clone();
+    }
+    if field("due", &|t| {
+        t.due.map_or("none".to_string(), |d| d.to_string())
+    })? == Side::Remote
+    {
+        task.due = theirs.due;
+    }
+
+    // The context is compared like a field, against the base's context
+    let context = if local_context == remote_context {
+        local_context
+    } else {
+        match base.map(|(context, _)| context) {
+            Some(b) if b == local_context => remote_context,
+            Some(b) if b == remote_context => local_context,
+            _ => {
+                let side = decide(SyncConflict {
+                    id: ours.id.clone(),
+                    description: ours.description.clone(),
+                    field: "context".to_string(),
+                    local: local_context.to_string(),
+                    remote: remote_context.to_string(),
+                    local_modified: ours.modified_at().to_string(),
+                    remote_modified: theirs.modified_at().to_string(),
+                })?;
+                match side {
+                    Side::Local => local_context,
+                    Side::Remote => remote_context,
+                }
+            }
+        }
+    };
+
+    // Both timelines are kept, so `todo history` shows edits from either side
+    task.history = merge_history(&ours.history, &theirs.history);
+    if compare_timestamps(theirs.modified_at(), ours.modified_at()).is_gt() {
+        task.updated_at = theirs.updated_at.clone();
+    }
+
+    // A review on either side counts; keep the latest
+    if let Some(reviewed) = &theirs.reviewed_at {
+        if ours
+            .reviewed_at
+            .as_deref()
+            .is_none_or(|ours| compare_timestamps(reviewed, ours).is_gt())
+        {
+            task.reviewed_at = Some(reviewed.clone());
+        }
+    }
+
+    Ok((context.to_string(), task))
+}
+
+/// Decides the fate of a task that only one side still has
+///
+/// The other side either never had it (it is new and gets copied over), or
+/// deleted it. A deletion wins when the surviving copy is unchanged since the
+/// base; otherwise the user decides between the edit and the deletion.
+fn keep_unless_deleted(
+    base: Option<Located>,
+    (context, task): Located,
+    holder: Side,
+    tombstone: Option<&Tombstone>,
+    decide: &mut impl FnMut(SyncConflict) -> Result<Side>,
+) -> Result<Option<(String, Task)>> {
+    // Never seen by the other side: a new task
+    if base.is_none() && tombstone.is_none() {
+        return Ok(Some((context.to_string(), task.clone())));
+    }
+
+    let unchanged = base.is_some_and(|(base_context, base_task)| {
+        base_context == context && base_task.diff(task).is_empty()
+    });
+    if unchanged {
+        return Ok(None);
+    }
+
+    let deleted_at = tombstone.map_or_else(
+        || "unknown".to_string(),
+        |tombstone| tombstone.deleted_at.clone(),
+    );
+    let (local, remote, local_modified, remote_modified) = match holder {
+        Side::Local => (
+            "edited",
+            "deleted",
+            task.modified_at().to_string(),
+            deleted_at,
+        ),
+        Side::Remote => (
+            "deleted",
+            "edited",
+            deleted_at,
+            task.modified_at().to_string(),
+        ),
+    };
+    let kept = decide(SyncConflict {
+        id: task.id.clone(),
+        description: task.description.clone(),
+        field: "deleted".to_string(),
+        local: local.to_string(),
+        remote: remote.to_string(),
+        local_modified,
+        remote_modified,
+    })?;
+
+    Ok((kept == holder).then(|| (context.to_string(), task.clone())))
+}
+
+/// Combines two histories, dropping duplicates and ordering by time
+fn merge_history(ours: &[HistoryEntry], theirs: &[HistoryEntry]) -> Vec<HistoryEntry> {
+    let mut history = ours.to_vec();
+    for entry in theirs {
+        if !history.contains(entry) {
+            history.push(entry.clone());
+        }
+    }
+    // Stable sort keeps entries with the same timestamp in recorded order
+    history.sort_by(|a, b| compare_timestamps(&a.at, &b.at));
+    history
+}
+
+/// Counts how one side's tasks differ from the merged result
+fn count_changes(before: &HashMap<&str, Located>, after: &HashMap<&str, Located>) -> SideChanges {
+    let mut changes = SideChanges::default();
+    for (id, (context, task)) in after {
+        match before.get(id) {
+            None => changes.added += 1,
+            Some((old_context, old_task)) => {
+                if old_context != context || !old_task.diff(task).is_empty() {
+                    changes.updated += 1;
+                }
+            }
+        }
+    }
+    changes.deleted = before.keys().filter(|id| !after.contains_key(*id)).count();
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Priority, TimeHorizon};
+
+    /// A synced pair: base, local and remote all hold the same two tasks
+    fn synced() -> (ContextManager, String, String) {
+        let mut manager = ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+        let a = Task::new("Alpha".to_string(), TimeHorizon::ShortTerm, Priority::Low);
+        let b = Task::new("Beta".to_string(), TimeHorizon::MidTerm, Priority::High);
+        let (a_id, b_id) = (a.id.clone(), b.id.clone());
+        let work = manager.contexts.get_mut("work").unwrap();
+        work.add_task(a);
+        work.add_task(b);
+        (manager, a_id, b_id)
+    }
+
+    fn task<'a>(manager: &'a ContextManager, id: &str) -> Option<&'a Task> {
+        manager.contexts.values().find_map(|c| c.find_task(id))
+    }
+
+    fn task_mut<'a>(manager: &'a mut ContextManager, id: &str) -> &'a mut Task {
+        manager
+            .contexts
+            .values_mut()
+            .find_map(|c| c.find_task_mut(id))
+            .unwrap()
+    }
+
+    fn never_conflicts(conflict: &SyncConflict) -> Result<Side> {
+        panic!("unexpected conflict: {:?}", conflict)
+    }
+
+    #[test]
+    fn test_sync_policy_from_str() {
+        assert_eq!(SyncPolicy::from_str("ask").unwrap(), SyncPolicy::Ask);
+        assert_eq!(SyncPolicy::from_str("OURS").unwrap(), SyncPolicy::Ours);
+        assert_eq!(SyncPolicy::from_str("remote").unwrap(), SyncPolicy::Theirs);
+        assert_eq!(SyncPolicy::from_str("newest").unwrap(), SyncPolicy::Newest);
+        assert!(SyncPolicy::from_str("mine").is_err());
+    }
+
+    #[test]
+    fn test_sync_propagates_edits_both_ways() {
+        let (base, a, b) = synced();
+        let mut local = base.clone();
+        let mut remote = base.clone();
+
+        task_mut(&mut local, &a).update(None, None, Some(Priority::High), None);
+        task_mut(&mut remote, &b).mark_complete();
+        task_mut(&mut remote, &a).update(Some("Alpha v2".to_string()), None, None, None);
+
+        let (merged, report) = sync(Some(&base), &local, &remote, never_conflicts).unwrap();
+        let merged_a = task(&merged, &a).unwrap();
+        assert_eq!(merged_a.priority, Priority::High);
+        assert_eq!(merged_a.description, "Alpha v2");
+        assert!(task(&merged, &b).unwrap().completed);
+
+        // Both edits to Alpha are in its history
+        assert_eq!(merged_a.history.len(), 2);
+        assert_eq!(report.local.updated, 2);
+        assert_eq!(report.remote.updated, 1);
+    }
+
+    #[test]
+    fn test_sync_propagates_adds_and_deletes() {
+        let (base, a, b) = synced();
+        let mut local = base.clone();
+        let mut remote = base.clone();
+
+        local
+            .contexts
+            .get_mut("work")
+            .unwrap()
+            .delete_task(&a)
+            .unwrap();
+        remote.active_context_mut().add_task(Task::new(
+            "New remote".to_string(),
+            TimeHorizon::LongTerm,
+            Priority::Low,
+        ));
+
+        let (merged, report) = sync(Some(&base), &local, &remote, never_conflicts).unwrap();
+        assert!(task(&merged, &a).is_none());
+        assert!(task(&merged, &b).is_some());
+        assert_eq!(merged.active_context().tasks.len(), 1);
+        assert_eq!(merged.contexts["work"].tombstones[0].id, a);
+        assert_eq!(
+            report.remote,
+            SideChanges {
+                added: 0,
+                updated: 0,
+                deleted: 1
+            }
+        );
+        assert_eq!(report.local.added, 1);
+
+        // Syncing again against the result changes nothing, and drops the
+        // tombstone now that the base records the deletion
+        let (again, report) = sync(Some(&merged), &merged, &merged, never_conflicts).unwrap();
+        assert!(report.local.is_empty() && report.remote.is_empty());
+        assert!(again.contexts["work"].tombstones.is_empty());
+        assert!(task(&again, &a).is_none());
+    }
+
+    #[test]
+    fn test_sync_propagates_context_deletion() {
+        let (base, _, _) = synced();
+        let mut local = base.clone();
+        let remote = base.clone();
+        local.delete_context("work").unwrap();
+
+        let (merged, report) = sync(Some(&base), &local, &remote, never_conflicts).unwrap();
+        assert!(!merged.contexts.contains_key("work"));
+        assert_eq!(report.remote.deleted, 2);
+
+        // A context that gained a task on the other side is kept
+        let mut remote = base.clone();
+        remote.contexts.get_mut("work").unwrap().add_task(Task::new(
+            "New remote".to_string(),
+            TimeHorizon::ShortTerm,
+            Priority::Low,
+        ));
+        let (merged, _) = sync(Some(&base), &local, &remote, never_conflicts).unwrap();
+        assert_eq!(merged.contexts["work"].tasks.len(), 1);
+    }
+
+    #[test]
+    fn test_sync_conflicting_field_uses_callback() {
+        let (base, a, _) = synced();
+        let mut local = base.clone();
+        let mut remote = base.clone();
+        task_mut(&mut local, &a).update(None, None, Some(Priority::Medium), None);
+        task_mut(&mut remote, &a).update(None, None, Some(Priority::High), None);
+
+        let (merged, report) = sync(Some(&base), &local, &remote, |_| Ok(Side::Remote)).unwrap();
+        assert_eq!(task(&merged, &a).unwrap().priority, Priority::High);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].conflict.field, "priority");
+        assert_eq!(report.conflicts[0].conflict.local, "Medium");
+        assert_eq!(report.conflicts[0].kept, Side::Remote);
+
+        // The callback can abort the sync
+        let aborted = sync(Some(&base), &local, &remote, |c| {
+            Err(AppError::SyncConflict(c.id.clone()))
+        });
+        assert!(aborted.is_err());
+    }
+
+    #[test]
+    fn test_sync_edit_versus_delete() {
+        let (base, a, _) = synced();
+        let mut local = base.clone();
+        let mut remote = base.clone();
+        task_mut(&mut local, &a).mark_complete();
+        remote
+            .contexts
+            .get_mut("work")
+            .unwrap()
+            .delete_task(&a)
+            .unwrap();
+
+        let (kept, report) = sync(Some(&base), &local, &remote, |c| {
+            assert_eq!(c.field, "deleted");
+            assert_eq!(c.remote, "deleted");
+            Ok(Side::Local)
+        })
+        .unwrap();
+        assert!(task(&kept, &a).unwrap().completed);
+        assert!(kept.contexts["work"].tombstones.is_empty());
+        assert_eq!(report.remote.added, 1);
+
+        let (deleted, _) = sync(Some(&base), &local, &remote, |_| Ok(Side::Remote)).unwrap();
+        assert!(task(&deleted, &a).is_none());
+    }
+
+    #[test]
+    fn test_first_sync_without_base() {
+        let (local, a, _) = synced();
+        let mut remote = local.clone();
+        task_mut(&mut remote, &a).mark_complete();
+        task_mut(&mut remote, &a).update(None, Some(TimeHorizon::LongTerm), None, None);
+
+        // Completion merges without asking; the horizon has no base to compare to
+        let mut asked = Vec::new();
+        let (merged, _) = sync(None, &local, &remote, |c| {
+            asked.push(c.field.clone());
+            Ok(SyncPolicy::Newest.resolve(c).unwrap())
+        })
+        .unwrap();
+        assert_eq!(asked, vec!["time_horizon"]);
+        let merged_a = task(&merged, &a).unwrap();
+        assert!(merged_a.completed);
+        assert_eq!(merged_a.time_horizon, TimeHorizon::LongTerm);
+    }
+
+    #[test]
+    fn test_sync_moves_task_between_contexts() {
+        let (base, a, _) = synced();
+        let local = base.clone();
+        let mut remote = base.clone();
+        let moved = remote
+            .contexts
+            .get_mut("work")
+            .unwrap()
+            .remove_task(&a)
+            .unwrap();
+        remote.active_context_mut().add_task(moved);
+
+        let (merged, report) = sync(Some(&base), &local, &remote, never_conflicts).unwrap();
+        assert!(merged.contexts["default"].find_task(&a).is_some());
+        assert!(merged.contexts["work"].find_task(&a).is_none());
+        assert_eq!(report.local.updated, 1);
+    }
+}
@@ -2,6 +2,7 @@
 // This module demonstrates Rust's enum types, struct definitions, and trait implementations
 
 use crate::error::AppError;
+use chrono::{Duration, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
@@ -121,11 +122,16 @@ impl FromStr for Priority {
 /// - `priority`: How important the task is (low/medium/high)
 /// - `completed`: Whether the task has been finished
 /// - `created_at`: ISO 8601 timestamp of when the task was created
+/// - `updated_at`: ISO 8601 timestamp of the last modification, if any
+/// - `reviewed_at`: ISO 8601 timestamp of the last `todo review` answer, if any
+/// - `due`: The day the task is due, if it has a due date
+/// - `history`: Every change made to the task since it was created
+/// - `tags`: Free-form labels attached to the task (e.g., imported from Taskwarrior)
 ///
 /// # Future Extensibility
 ///
-/// Additional fields can be added later (due_date, tags, notes) without breaking
-/// existing JSON files by using #[serde(default)] on new fields.
+/// Additional fields can be added later (e.g., notes) without breaking
+/// existing JSON files by using #[serde(default)] on new fields, as `tags` does.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
     /// Unique identifier for the task (UUID v4 format)
@@ -145,6 +151,127 @@ pub struct Task {
 
     /// ISO 8601 timestamp of task creation
     pub created_at: String,
+
+    /// ISO 8601 timestamp of the last modification
+    ///
+    /// None means the task has not changed since it was created. Import merges
+    /// use this to decide which copy of a task is newer (see modified_at()).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+
+    /// ISO 8601 timestamp of when the task was last reviewed
+    ///
+    /// Set by `todo review`. Reviewing isn't a modification, so it leaves
+    /// `updated_at` and the history alone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewed_at: Option<String>,
+
+    /// The day the task is due, e.g. "2026-10-31"
+    ///
+    /// A calendar day rather than an instant, compared in local time. Tasks
+    /// written before due dates existed load without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+
+    /// Free-form labels attached to the task
+    ///
+    /// #[serde(default)] lets files written before tags existed load with an
+    /// empty list, and skip_serializing_if keeps untagged tasks unchanged on disk.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Changes made to the task, oldest first
+    ///
+    /// Creation is not recorded here since `created_at` already covers it.
+    /// Tasks written before history existed load with an empty history.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<HistoryEntry>,
+}
+
+/// Parses a due date: `YYYY-MM-DD`, `today` or `tomorrow` (in local time)
+///
+/// `none` parses to None, so `edit --due none` can remove a due date.
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use rust_todo::task::parse_due;
+///
+/// assert_eq!(parse_due("2026-10-31")?, NaiveDate::from_ymd_opt(2026, 10, 31));
+/// assert_eq!(parse_due("none")?, None);
+/// assert!(parse_due("someday").is_err());
+/// # Ok::<(), rust_todo::error::AppError>(())
+/// ```
+pub fn parse_due(s: &str) -> Result<Option<NaiveDate>, AppError> {
+    let today = Local::now().date_naive();
+    match s.trim().to_lowercase().as_str() {
+        "none" => Ok(None),
+        "today" => Ok(Some(today)),
+        "tomorrow" => Ok(Some(today + Duration::days(1))),
+        date => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| AppError::InvalidDueDate(s.to_string())),
+    }
+}
+
+/// Orders two RFC 3339 timestamps as instants
+///
+/// Timestamps in different offsets are compared by the instant they denote.
+/// If either fails to parse, they are compared as strings, which still orders
+/// timestamps written in the same offset correctly. Sorting, import merges and
+/// sync all order timestamps with this.
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::task::compare_timestamps;
+/// use std::cmp::Ordering;
+///
+/// // 10:00 in UTC+2 is 08:00 UTC, so it is earlier than 09:00 UTC
+/// assert_eq!(
+///     compare_timestamps("2026-10-18T10:00:00+02:00", "2026-10-18T09:00:00+00:00"),
+///     Ordering::Less
+/// );
+/// ```
+pub fn compare_timestamps(a: &str, b: &str) -> std::cmp::Ordering {
+    match (
+        chrono::DateTime::parse_from_rfc3339(a),
+        chrono::DateTime::parse_from_rfc3339(b),
+    ) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+/// One recorded change to a task: which field changed, how, and when
+///
+/// Serialized flat, e.g.
+/// `{"at":"2024-01-16T09:00:00+00:00","field":"priority","from":"Low","to":"High"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// ISO 8601 timestamp of the change
+    pub at: String,
+
+    /// The field and its old and new values
+    #[serde(flatten)]
+    pub change: FieldChange,
+}
+
+/// A single field that differs between two versions of a task
+///
+/// Values are rendered the same way they appear in JSON output
+/// (e.g., "ShortTerm", "High", "true"), with tags joined by commas.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// Name of the field, as in the serialized task
+    pub field: String,
+
+    /// The value before the change
+    pub from: String,
+
+    /// The value after the change
+    pub to: String,
 }
 
 impl Task {
@@ -178,7 +305,7 @@ impl Task {
     ///     Priority::High
     /// );
     /// assert_eq!(task.description, "Write documentation");
-    /// assert_eq!(task.completed, false);
+    /// assert!(!task.completed);
     /// ```
     pub fn new(description: String, time_horizon: TimeHorizon, priority: Priority) -> Self {
         // Generate a new UUID v4 (random UUID)
@@ -201,6 +328,11 @@ impl Task {
             priority,
             completed: false, // New tasks start as incomplete
             created_at,
+            updated_at: None,
+            reviewed_at: None,
+            due: None,
+            tags: Vec::new(),
+            history: Vec::new(),
         }
     }
 
@@ -235,14 +367,21 @@ impl Task {
     ///     Priority::High
     /// );
     ///
-    /// assert_eq!(task.completed, false);
+    /// assert!(!task.completed);
     /// task.mark_complete();
-    /// assert_eq!(task.completed, true);
+    /// assert!(task.completed);
     /// ```
     pub fn mark_complete(&mut self) {
-        // Simply set the completed field to true
+        // Completing a task twice is not a change, so it isn't recorded
+        if self.completed {
+            return;
+        }
+
+        // Keep a copy of the old state so the change can be recorded
         // The &mut self reference allows us to modify the task's state
+        let before = self.clone();
         self.completed = true;
+        self.record_changes(&before);
     }
 
     /// Updates task properties
@@ -267,6 +406,7 @@ impl Task {
     /// * `description` - Optional new description (None = no change)
     /// * `time_horizon` - Optional new time horizon (None = no change)
     /// * `priority` - Optional new priority (None = no change)
+    /// * `due` - Optional new due date (None = no change, Some(None) = remove it)
     ///
     /// # Example
     ///
@@ -280,7 +420,7 @@ impl Task {
     /// );
     ///
     /// // Update only the priority
-    /// task.update(None, None, Some(Priority::High));
+    /// task.update(None, None, Some(Priority::High), None);
     /// assert_eq!(task.priority, Priority::High);
     /// assert_eq!(task.description, "Original description");
     ///
@@ -288,7 +428,8 @@ impl Task {
     /// task.update(
     ///     Some("New description".to_string()),
     ///     Some(TimeHorizon::LongTerm),
-    ///     None
+    ///     None,
+    ///     None,
     /// );
     /// assert_eq!(task.description, "New description");
     /// assert_eq!(task.time_horizon, TimeHorizon::LongTerm);
@@ -299,7 +440,11 @@ impl Task {
         description: Option<String>,
         time_horizon: Option<TimeHorizon>,
         priority: Option<Priority>,
+        due: Option<Option<NaiveDate>>,
     ) {
+        // Keep a copy of the old state so the changes can be recorded
+        let before = self.clone();
+
         // Use if let to check if a new value was provided
         // if let Some(value) = option { ... } is Rust's way of handling Option types
         // It's more concise than match when we only care about the Some case
@@ -323,8 +468,151 @@ impl Task {
             self.priority = new_priority;
         }
 
+        // Update the due date if provided; Some(None) removes it
+        if let Some(new_due) = due {
+            self.due = new_due;
+        }
+
         // Note: Fields not provided (None) remain unchanged
         // This is the power of Option<T> - explicit optional parameters
+        self.record_changes(&before);
+    }
+
+    /// Returns the time of the last modification, or creation if never modified
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::task::{Task, TimeHorizon, Priority};
+    ///
+    /// let mut task = Task::new("Plan".to_string(), TimeHorizon::ShortTerm, Priority::Low);
+    /// assert_eq!(task.modified_at(), task.created_at);
+    ///
+    /// task.mark_complete();
+    /// assert_eq!(Some(task.modified_at()), task.updated_at.as_deref());
+    /// ```
+    pub fn modified_at(&self) -> &str {
+        self.updated_at.as_deref().unwrap_or(&self.created_at)
+    }
+
+    /// Returns when the task was completed, as recorded in its history
+    ///
+    /// None for open tasks, and for completed tasks whose completion predates
+    /// the history (or came from an import without one).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::task::{Task, TimeHorizon, Priority};
+    ///
+    /// let mut task = Task::new("Plan".to_string(), TimeHorizon::ShortTerm, Priority::Low);
+    /// assert_eq!(task.completed_at(), None);
+    ///
+    /// task.mark_complete();
+    /// assert_eq!(task.completed_at(), task.updated_at.as_deref());
+    /// ```
+    pub fn completed_at(&self) -> Option<&str> {
+        if !self.completed {
+            return None;
+        }
+        self.history
+            .iter()
+            .rev()
+            .find(|entry| entry.change.field == "completed" && entry.change.to == "true")
+            .map(|entry| entry.at.as_str())
+    }
+
+    /// Lists the user-visible fields that differ between two versions of a task
+    ///
+    /// Each FieldChange reads "`field` goes `from` self's value `to` other's
+    /// value". The ID and timestamps are not compared: they identify a task
+    /// rather than describe it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::task::{Task, TimeHorizon, Priority};
+    ///
+    /// let ours = Task::new("Plan".to_string(), TimeHorizon::ShortTerm, Priority::Low);
+    /// let mut theirs = ours.clone();
+    /// theirs.update(None, None, Some(Priority::High), None);
+    ///
+    /// let changes = ours.diff(&theirs);
+    /// assert_eq!(changes.len(), 1);
+    /// assert_eq!(changes[0].field, "priority");
+    /// assert_eq!(changes[0].from, "Low");
+    /// assert_eq!(changes[0].to, "High");
+    /// ```
+    pub fn diff(&self, other: &Task) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+        let mut compare = |field: &'static str, from: String, to: String| {
+            if from != to {
+                changes.push(FieldChange {
+                    field: field.to_string(),
+                    from,
+                    to,
+                });
+            }
+        };
+
+        compare(
+            "description",
+            self.description.clone(),
+            other.description.clone(),
+        );
+        compare(
+            "time_horizon",
+            format!("{:?}", self.time_horizon),
+            format!("{:?}", other.time_horizon),
+        );
+        compare(
+            "priority",
+            format!("{:?}", self.priority),
+            format!("{:?}", other.priority),
+        );
+        compare(
+            "completed",
+            self.completed.to_string(),
+            other.completed.to_string(),
+        );
+        compare("tags", self.tags.join(","), other.tags.join(","));
+        let due = |task: &Task| task.due.map_or("none".to_string(), |d| d.to_string());
+        compare("due", due(self), due(other));
+
+        changes
+    }
+
+    /// Appends every field that differs from `before` to the history
+    ///
+    /// All entries share one timestamp, which also becomes `updated_at`.
+    /// Nothing is recorded, and `updated_at` is left alone, if no field changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::task::{Task, TimeHorizon, Priority};
+    ///
+    /// let mut task = Task::new("Plan".to_string(), TimeHorizon::ShortTerm, Priority::Low);
+    /// let before = task.clone();
+    /// task.tags.push("q1".to_string());
+    /// task.record_changes(&before);
+    ///
+    /// assert_eq!(task.history.len(), 1);
+    /// assert_eq!(task.history[0].change.field, "tags");
+    /// ```
+    pub fn record_changes(&mut self, before: &Task) {
+        let changes = before.diff(self);
+        if changes.is_empty() {
+            return;
+        }
+
+        let at = chrono::Utc::now().to_rfc3339();
+        self.history
+            .extend(changes.into_iter().map(|change| HistoryEntry {
+                at: at.clone(),
+                change,
+            }));
+        self.updated_at = Some(at);
     }
 }
 
@@ -332,6 +620,33 @@ impl Task {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_due_date_serde_and_history() {
+        // Files written before due dates existed load without one
+        let mut task: Task = serde_json::from_str(
+            r#"{"id":"a","description":"x","time_horizon":"ShortTerm","priority":"Low",
+                "completed":false,"created_at":"2026-01-01T00:00:00+00:00"}"#,
+        )
+        .unwrap();
+        assert!(task.due.is_none());
+        assert!(!serde_json::to_string(&task).unwrap().contains("due"));
+
+        let due = NaiveDate::from_ymd_opt(2026, 10, 31);
+        task.update(None, None, None, Some(due));
+        assert!(serde_json::to_string(&task)
+            .unwrap()
+            .contains(r#""due":"2026-10-31""#));
+        let change = &task.history[0].change;
+        assert_eq!(
+            (change.field.as_str(), change.from.as_str()),
+            ("due", "none")
+        );
+
+        task.update(None, None, None, Some(None));
+        assert!(task.due.is_none());
+        assert_eq!(task.history[1].change.to, "none");
+    }
+
     #[test]
     fn test_time_horizon_from_str() {
         // Test valid inputs
@@ -449,7 +764,7 @@ mod tests {
         assert_eq!(task.description, "Write tests");
         assert_eq!(task.time_horizon, TimeHorizon::ShortTerm);
         assert_eq!(task.priority, Priority::High);
-        assert_eq!(task.completed, false);
+        assert!(!task.completed);
 
         // Verify ID is a valid UUID (36 characters with hyphens)
         assert_eq!(task.id.len(), 36);
@@ -516,7 +831,7 @@ mod tests {
         assert_eq!(task.description, "Test task");
         assert_eq!(task.time_horizon, TimeHorizon::LongTerm);
         assert_eq!(task.priority, Priority::High);
-        assert_eq!(task.completed, true);
+        assert!(task.completed);
         assert_eq!(task.created_at, "2024-01-15T10:30:00Z");
     }
 
@@ -578,17 +893,17 @@ mod tests {
         );
 
         // Initially, task should not be completed
-        assert_eq!(task.completed, false);
+        assert!(!task.completed);
 
         // Mark the task as complete
         task.mark_complete();
 
         // Now it should be completed
-        assert_eq!(task.completed, true);
+        assert!(task.completed);
 
         // Marking complete again should have no effect (idempotent)
         task.mark_complete();
-        assert_eq!(task.completed, true);
+        assert!(task.completed);
     }
 
     #[test]
@@ -604,7 +919,7 @@ mod tests {
         let original_priority = task.priority;
 
         // Update only the description
-        task.update(Some("New description".to_string()), None, None);
+        task.update(Some("New description".to_string()), None, None, None);
 
         // Description should be updated
         assert_eq!(task.description, "New description");
@@ -627,7 +942,7 @@ mod tests {
         let original_priority = task.priority;
 
         // Update only the time horizon
-        task.update(None, Some(TimeHorizon::LongTerm), None);
+        task.update(None, Some(TimeHorizon::LongTerm), None, None);
 
         // Time horizon should be updated
         assert_eq!(task.time_horizon, TimeHorizon::LongTerm);
@@ -646,7 +961,7 @@ mod tests {
         let original_horizon = task.time_horizon;
 
         // Update only the priority
-        task.update(None, None, Some(Priority::High));
+        task.update(None, None, Some(Priority::High), None);
 
         // Priority should be updated
         assert_eq!(task.priority, Priority::High);
@@ -670,6 +985,7 @@ mod tests {
             Some("Updated description".to_string()),
             Some(TimeHorizon::LongTerm),
             Some(Priority::High),
+            None,
         );
 
         // All fields should be updated
@@ -692,7 +1008,7 @@ mod tests {
         let original_priority = task.priority;
 
         // Call update with no changes
-        task.update(None, None, None);
+        task.update(None, None, None, None);
 
         // All fields should remain unchanged
         assert_eq!(task.description, original_description);
@@ -718,6 +1034,7 @@ mod tests {
             Some("New description".to_string()),
             Some(TimeHorizon::LongTerm),
             Some(Priority::High),
+            None,
         );
 
         // ID, created_at, and completed should remain unchanged
@@ -725,4 +1042,91 @@ mod tests {
         assert_eq!(task.created_at, original_created_at);
         assert_eq!(task.completed, original_completed);
     }
+
+    #[test]
+    fn test_task_tags_default_and_skip() {
+        // Tasks written before tags existed still load, with no tags
+        let json = r#"{
+            "id": "123e4567-e89b-12d3-a456-426614174000",
+            "description": "Old task",
+            "time_horizon": "ShortTerm",
+            "priority": "Low",
+            "completed": false,
+            "created_at": "2024-01-15T10:30:00Z"
+        }"#;
+        let task: Task = serde_json::from_str(json).unwrap();
+        assert!(task.tags.is_empty());
+
+        // Untagged tasks don't write a tags field; tagged tasks do
+        let json = serde_json::to_string(&task).unwrap();
+        assert!(!json.contains("tags"));
+
+        let mut tagged = task.clone();
+        tagged.tags.push("docs".to_string());
+        let json = serde_json::to_string(&tagged).unwrap();
+        assert!(json.contains("\"tags\":[\"docs\"]"));
+    }
+
+    #[test]
+    fn test_update_records_history() {
+        let mut task = Task::new("Draft".to_string(), TimeHorizon::ShortTerm, Priority::Low);
+        assert!(task.history.is_empty());
+        assert!(task.updated_at.is_none());
+
+        task.update(
+            Some("Final".to_string()),
+            Some(TimeHorizon::MidTerm),
+            Some(Priority::Low),
+            None,
+        );
+
+        // Only the fields that actually changed are recorded, with one timestamp
+        let fields: Vec<&str> = task
+            .history
+            .iter()
+            .map(|e| e.change.field.as_str())
+            .collect();
+        assert_eq!(fields, vec!["description", "time_horizon"]);
+        assert_eq!(task.history[0].change.from, "Draft");
+        assert_eq!(task.history[0].change.to, "Final");
+        assert_eq!(task.history[1].change.to, "MidTerm");
+        assert_eq!(task.history[0].at, task.history[1].at);
+        assert_eq!(
+            task.updated_at.as_deref(),
+            Some(task.history[0].at.as_str())
+        );
+
+        // An update that changes nothing leaves the history alone
+        task.update(None, Some(TimeHorizon::MidTerm), None, None);
+        assert_eq!(task.history.len(), 2);
+    }
+
+    #[test]
+    fn test_mark_complete_records_once() {
+        let mut task = Task::new("Ship".to_string(), TimeHorizon::ShortTerm, Priority::High);
+        task.mark_complete();
+        task.mark_complete();
+
+        assert_eq!(task.history.len(), 1);
+        assert_eq!(task.history[0].change.field, "completed");
+        assert_eq!(task.history[0].change.to, "true");
+    }
+
+    #[test]
+    fn test_history_serde() {
+        let mut task = Task::new("Ship".to_string(), TimeHorizon::ShortTerm, Priority::High);
+
+        // Unchanged tasks keep the old on-disk shape
+        let json = serde_json::to_string(&task).unwrap();
+        assert!(!json.contains("history"));
+        assert!(!json.contains("updated_at"));
+
+        // History entries are stored flat and round-trip
+        task.update(None, None, Some(Priority::Low), None);
+        let json = serde_json::to_string(&task).unwrap();
+        assert!(json.contains(r#""field":"priority","from":"High","to":"Low""#));
+        let loaded: Task = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.history, task.history);
+        assert_eq!(loaded.updated_at, task.updated_at);
+    }
 }
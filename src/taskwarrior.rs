@@ -0,0 +1,506 @@
+// Taskwarrior module - converts between Taskwarrior's JSON export format and our data model
+// This module demonstrates serde field mapping, Option handling, and date format conversion
+//
+// Taskwarrior (https://taskwarrior.org) exports tasks with `task export` as a JSON array
+// of flat task objects. Each object carries its project, priority, status and tags
+// directly, whereas we group tasks into named contexts. This module translates between
+// the two shapes so users can migrate (and round-trip) their data.
+//
+// # Field Mapping
+//
+// | Taskwarrior          | rust-todo                         |
+// |----------------------|-----------------------------------|
+// | `uuid`               | `Task::id`                        |
+// | `description`        | `Task::description`               |
+// | `project`            | `Context` name (none → "default") |
+// | `priority` H/M/L     | `Priority` High/Medium/Low        |
+// | `status`             | `Task::completed`                 |
+// | `entry`              | `Task::created_at`                |
+// | `tags`               | `Task::tags`                      |
+// | `horizon` (UDA)      | `TimeHorizon`                     |
+//
+// Taskwarrior has no notion of time horizons, so we store ours in a user defined
+// attribute (UDA) called `horizon`. Taskwarrior keeps unknown attributes on import,
+// which lets a task survive a full round trip. Tasks without it default to short-term.
+
+use crate::context::{Context, ContextManager};
+use crate::error::{AppError, Result};
+use crate::task::{Priority, Task, TimeHorizon};
+use serde::{Deserialize, Serialize};
+
+/// Name of the context used for Taskwarrior tasks that have no project
+///
+/// On export, tasks in this context are written without a project so that
+/// they map back to "no project" in Taskwarrior.
+pub const DEFAULT_CONTEXT: &str = "default";
+
+/// Date format used by Taskwarrior for all timestamps (e.g., "20240115T103000Z")
+const TASKWARRIOR_DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// A single annotation attached to a Taskwarrior task
+///
+/// We don't have an equivalent field, so annotations are only parsed to
+/// validate the document and count what could not be imported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskwarriorAnnotation {
+    /// Timestamp of the annotation in Taskwarrior date format
+    pub entry: String,
+
+    /// The annotation text
+    pub description: String,
+}
+
+/// A single task in Taskwarrior's JSON export format
+///
+/// Only the fields we can map are declared. serde ignores any other
+/// attributes (urgency, id, modified, ...) when deserializing.
+///
+/// Optional fields use `skip_serializing_if` so exported objects look like
+/// the ones Taskwarrior itself produces, without `null` values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskwarriorTask {
+    /// Task UUID - becomes our task ID
+    pub uuid: String,
+
+    /// The task's description text
+    pub description: String,
+
+    /// One of "pending", "completed", "deleted", "waiting" or "recurring"
+    pub status: String,
+
+    /// Creation timestamp in Taskwarrior date format
+    pub entry: String,
+
+    /// Project name - becomes our context name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+
+    /// Priority: "H", "M" or "L"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+
+    /// Free-form tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Annotations (not imported, see TaskwarriorAnnotation)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<TaskwarriorAnnotation>,
+
+    /// Our time horizon, stored as a Taskwarrior UDA ("short", "mid" or "long")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub horizon: Option<String>,
+}
+
+/// Summary of a Taskwarrior import
+///
+/// Taskwarrior data can contain things we have no place for. Rather than
+/// failing, the import skips them and reports how many were dropped so the
+/// user can decide whether that is acceptable.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Number of tasks imported
+    pub imported: usize,
+
+    /// Number of tasks skipped because their status is "deleted"
+    pub skipped_deleted: usize,
+
+    /// Number of annotations dropped (we have no annotation field)
+    pub dropped_annotations: usize,
+}
+
+/// Parses a Taskwarrior export document
+///
+/// `task export` writes a JSON array by default, but with `rc.json.array=off`
+/// it writes one JSON object per line. Both forms are accepted.
+///
+/// # Errors
+///
+/// Returns AppError::JsonError if the document (or any line) is not valid JSON
+/// or does not have the Taskwarrior task shape.
+pub fn parse_export(contents: &str) -> Result<Vec<TaskwarriorTask>> {
+    // An array export starts with '[' once leading whitespace is skipped
+    if contents.trim_start().starts_with('[') {
+        return Ok(serde_json::from_str(contents)?);
+    }
+
+    // Otherwise treat the document as one task object per non-empty line
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(AppError::from))
+        .collect()
+}
+
+/// Converts Taskwarrior tasks into a ContextManager
+///
+/// Each distinct project becomes a context; tasks without a project go to
+/// the "default" context. Deleted tasks are skipped. The "default" context
+/// always exists afterwards and is made active, so the ContextManager
+/// invariants hold even for an empty export.
+///
+/// # Errors
+///
+/// Returns an error if a task has an unknown priority, horizon or status,
+/// or an `entry` timestamp that is not in Taskwarrior date format.
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::taskwarrior::{from_taskwarrior, parse_export};
+/// use rust_todo::task::Priority;
+///
+/// let json = r#"[{"uuid":"a1b2c3d4-0000-0000-0000-000000000000","description":"Ship it",
+///                "status":"pending","entry":"20240115T103000Z","project":"work","priority":"H"}]"#;
+/// let (manager, report) = from_taskwarrior(parse_export(json)?)?;
+///
+/// assert_eq!(report.imported, 1);
+/// let task = &manager.contexts["work"].tasks[0];
+/// assert_eq!(task.priority, Priority::High);
+/// assert_eq!(task.created_at, "2024-01-15T10:30:00+00:00");
+/// # Ok::<(), rust_todo::error::AppError>(())
+/// ```
+pub fn from_taskwarrior(tasks: Vec<TaskwarriorTask>) -> Result<(ContextManager, ImportReport)> {
+    let mut manager = ContextManager::new();
+    let mut report = ImportReport::default();
+
+    for tw_task in tasks {
+        // Deleted tasks only exist in Taskwarrior for undo purposes
+        let completed = match tw_task.status.as_str() {
+            "pending" | "waiting" | "recurring" => false,
+            "completed" => true,
+            "deleted" => {
+                report.skipped_deleted += 1;
+                continue;
+            }
+            other => {
+                return Err(AppError::InvalidDataFormat(format!(
+                    "Unknown Taskwarrior status '{}' for task {}",
+                    other, tw_task.uuid
+                )))
+            }
+        };
+
+        // Taskwarrior tasks without a priority get our default priority
+        let priority = match tw_task.priority.as_deref() {
+            Some(p) => p.parse::<Priority>()?,
+            None => Priority::Medium,
+        };
+
+        let time_horizon = match tw_task.horizon.as_deref() {
+            Some(h) => h.parse::<TimeHorizon>()?,
+            None => TimeHorizon::ShortTerm,
+        };
+
+        report.dropped_annotations += tw_task.annotations.len();
+
+        let task = Task {
+            id: tw_task.uuid,
+            description: tw_task.description,
+            time_horizon,
+            priority,
+            completed,
+            created_at: taskwarrior_date_to_rfc3339(&tw_task.entry)?,
+            updated_at: None,
+            reviewed_at: None,
+            due: None,
+            tags: tw_task.tags,
+            history: Vec::new(),
+        };
+
+        // Route the task to the context named after its project,
+        // creating the context the first time we see the project
+        let context_name = tw_task
+            .project
+            .unwrap_or_else(|| DEFAULT_CONTEXT.to_string());
+        manager
+            .contexts
+            .entry(context_name.clone())
+            .or_insert_with(|| Context::new(context_name))
+            .add_task(task);
+
+        report.imported += 1;
+    }
+
+    Ok((manager, report))
+}
+
+/// Converts a ContextManager into Taskwarrior tasks
+///
+/// Contexts are written in name order and tasks in their stored order, so
+/// the output is stable between runs. The context name becomes the project,
+/// except for "default" which maps back to "no project".
+///
+/// # Example
+///
+/// ```
+/// use rust_todo::context::ContextManager;
+/// use rust_todo::task::{Task, TimeHorizon, Priority};
+/// use rust_todo::taskwarrior::to_taskwarrior;
+///
+/// let mut manager = ContextManager::new();
+/// manager.active_context_mut().add_task(
+///     Task::new("Write tests".to_string(), TimeHorizon::MidTerm, Priority::Low),
+/// );
+///
+/// let tasks = to_taskwarrior(&manager);
+/// assert_eq!(tasks[0].priority.as_deref(), Some("L"));
+/// assert_eq!(tasks[0].horizon.as_deref(), Some("mid"));
+/// assert_eq!(tasks[0].project, None);
+/// ```
+pub fn to_taskwarrior(manager: &ContextManager) -> Vec<TaskwarriorTask> {
+    // HashMap iteration order is random, so sort the context names first
+    let mut names: Vec<&String> = manager.contexts.keys().collect();
+    names.sort();
+
+    let mut tasks = Vec::new();
+    for name in names {
+        let context = &manager.contexts[name];
+        let project = if name == DEFAULT_CONTEXT {
+            None
+        } else {
+            Some(name.clone())
+        };
+
+        for task in &context.tasks {
+            tasks.push(TaskwarriorTask {
+                uuid: task.id.clone(),
+                description: task.description.clone(),
+                status: if task.completed {
+                    "completed".to_string()
+                } else {
+                    "pending".to_string()
+                },
+                entry: rfc3339_to_taskwarrior_date(&task.created_at),
+                project: project.clone(),
+                priority: Some(priority_code(task.priority).to_string()),
+                tags: task.tags.clone(),
+                annotations: Vec::new(),
+                horizon: Some(horizon_code(task.time_horizon).to_string()),
+            });
+        }
+    }
+
+    tasks
+}
+
+/// Returns the Taskwarrior priority letter for a Priority
+fn priority_code(priority: Priority) -> &'static str {
+    match priority {
+        Priority::High => "H",
+        Priority::Medium => "M",
+        Priority::Low => "L",
+    }
+}
+
+/// Returns the value stored in the `horizon` UDA for a TimeHorizon
+///
+/// These are the short names accepted by TimeHorizon::from_str().
+fn horizon_code(horizon: TimeHorizon) -> &'static str {
+    match horizon {
+        TimeHorizon::ShortTerm => "short",
+        TimeHorizon::MidTerm => "mid",
+        TimeHorizon::LongTerm => "long",
+    }
+}
+
+/// Converts a Taskwarrior timestamp ("20240115T103000Z") to RFC 3339
+///
+/// Taskwarrior timestamps are always UTC, matching the timestamps that
+/// Task::new() produces.
+fn taskwarrior_date_to_rfc3339(value: &str) -> Result<String> {
+    let naive = chrono::NaiveDateTime::parse_from_str(value, TASKWARRIOR_DATE_FORMAT)
+        .map_err(|e| AppError::InvalidDataFormat(format!("Invalid date '{}': {}", value, e)))?;
+    Ok(naive.and_utc().to_rfc3339())
+}
+
+/// Converts an RFC 3339 timestamp to Taskwarrior date format
+///
+/// If the timestamp can't be parsed (e.g., it was hand-edited), the
+/// original string is returned unchanged rather than failing the export.
+fn rfc3339_to_taskwarrior_date(value: &str) -> String {
+    match chrono::DateTime::parse_from_rfc3339(value) {
+        Ok(date) => date
+            .with_timezone(&chrono::Utc)
+            .format(TASKWARRIOR_DATE_FORMAT)
+            .to_string(),
+        Err(_) => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A trimmed-down `task export` document covering the mapped fields
+    const SAMPLE_EXPORT: &str = r#"[
+        {
+            "id": 1,
+            "uuid": "5f0e3b1a-7c1e-4a53-9d0c-1e2f3a4b5c6d",
+            "description": "Write migration guide",
+            "entry": "20240115T103000Z",
+            "modified": "20240116T090000Z",
+            "project": "work",
+            "priority": "H",
+            "status": "pending",
+            "tags": ["docs", "sprint12"],
+            "annotations": [
+                {"entry": "20240116T090000Z", "description": "Ask Sam for review"}
+            ],
+            "urgency": 8.2
+        },
+        {
+            "id": 0,
+            "uuid": "0a1b2c3d-4e5f-6789-abcd-ef0123456789",
+            "description": "Buy milk",
+            "entry": "20240110T080000Z",
+            "end": "20240111T080000Z",
+            "status": "completed"
+        },
+        {
+            "id": 0,
+            "uuid": "deadbeef-0000-0000-0000-000000000000",
+            "description": "Old idea",
+            "entry": "20231201T120000Z",
+            "status": "deleted"
+        }
+    ]"#;
+
+    #[test]
+    fn test_parse_export_array() {
+        let tasks = parse_export(SAMPLE_EXPORT).unwrap();
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].project.as_deref(), Some("work"));
+        assert_eq!(tasks[0].tags, vec!["docs", "sprint12"]);
+    }
+
+    #[test]
+    fn test_parse_export_line_per_task() {
+        let contents = concat!(
+            r#"{"uuid":"a","description":"One","status":"pending","entry":"20240115T103000Z"}"#,
+            "\n\n",
+            r#"{"uuid":"b","description":"Two","status":"pending","entry":"20240115T103000Z"}"#,
+            "\n"
+        );
+        let tasks = parse_export(contents).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[1].description, "Two");
+    }
+
+    #[test]
+    fn test_parse_export_invalid() {
+        assert!(parse_export("[{\"uuid\": 42}]").is_err());
+        assert!(parse_export("not json").is_err());
+    }
+
+    #[test]
+    fn test_from_taskwarrior_mapping() {
+        let (manager, report) = from_taskwarrior(parse_export(SAMPLE_EXPORT).unwrap()).unwrap();
+
+        assert_eq!(
+            report,
+            ImportReport {
+                imported: 2,
+                skipped_deleted: 1,
+                dropped_annotations: 1,
+            }
+        );
+
+        // Project maps to context, missing project maps to "default"
+        assert_eq!(manager.contexts.len(), 2);
+        assert_eq!(manager.active_context, DEFAULT_CONTEXT);
+
+        let work = &manager.contexts["work"].tasks[0];
+        assert_eq!(work.id, "5f0e3b1a-7c1e-4a53-9d0c-1e2f3a4b5c6d");
+        assert_eq!(work.priority, Priority::High);
+        assert_eq!(work.time_horizon, TimeHorizon::ShortTerm);
+        assert!(!work.completed);
+        assert_eq!(work.created_at, "2024-01-15T10:30:00+00:00");
+        assert_eq!(work.tags, vec!["docs", "sprint12"]);
+
+        let milk = &manager.contexts[DEFAULT_CONTEXT].tasks[0];
+        assert!(milk.completed);
+        assert_eq!(milk.priority, Priority::Medium);
+    }
+
+    #[test]
+    fn test_from_taskwarrior_rejects_unknown_values() {
+        let bad_status =
+            r#"[{"uuid":"a","description":"x","status":"paused","entry":"20240115T103000Z"}]"#;
+        assert!(from_taskwarrior(parse_export(bad_status).unwrap()).is_err());
+
+        let bad_priority = r#"[{"uuid":"a","description":"x","status":"pending","entry":"20240115T103000Z","priority":"X"}]"#;
+        assert!(from_taskwarrior(parse_export(bad_priority).unwrap()).is_err());
+
+        let bad_date =
+            r#"[{"uuid":"a","description":"x","status":"pending","entry":"2024-01-15"}]"#;
+        assert!(from_taskwarrior(parse_export(bad_date).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_from_taskwarrior_empty() {
+        let (manager, report) = from_taskwarrior(Vec::new()).unwrap();
+        assert_eq!(report.imported, 0);
+        assert_eq!(manager.contexts.len(), 1);
+        assert!(manager.contexts.contains_key(DEFAULT_CONTEXT));
+    }
+
+    #[test]
+    fn test_to_taskwarrior_mapping() {
+        let mut manager = ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+
+        let mut task = Task::new("Deploy".to_string(), TimeHorizon::LongTerm, Priority::High);
+        task.created_at = "2024-01-15T10:30:00+00:00".to_string();
+        task.tags = vec!["ops".to_string()];
+        task.mark_complete();
+        manager.contexts.get_mut("work").unwrap().add_task(task);
+
+        let tasks = to_taskwarrior(&manager);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].project.as_deref(), Some("work"));
+        assert_eq!(tasks[0].status, "completed");
+        assert_eq!(tasks[0].entry, "20240115T103000Z");
+        assert_eq!(tasks[0].priority.as_deref(), Some("H"));
+        assert_eq!(tasks[0].horizon.as_deref(), Some("long"));
+        assert_eq!(tasks[0].tags, vec!["ops"]);
+
+        // Optional fields are omitted rather than written as null
+        let json = serde_json::to_string(&tasks[0]).unwrap();
+        assert!(!json.contains("null"));
+        assert!(!json.contains("annotations"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut manager = ContextManager::new();
+        manager.create_context("home".to_string()).unwrap();
+        manager.active_context_mut().add_task(Task::new(
+            "Default task".to_string(),
+            TimeHorizon::MidTerm,
+            Priority::Low,
+        ));
+        manager
+            .contexts
+            .get_mut("home")
+            .unwrap()
+            .add_task(Task::new(
+                "Home task".to_string(),
+                TimeHorizon::LongTerm,
+                Priority::High,
+            ));
+
+        let json = serde_json::to_string(&to_taskwarrior(&manager)).unwrap();
+        let (imported, _) = from_taskwarrior(parse_export(&json).unwrap()).unwrap();
+
+        for (name, context) in &manager.contexts {
+            let original = &context.tasks[0];
+            let restored = &imported.contexts[name].tasks[0];
+            assert_eq!(restored.id, original.id);
+            assert_eq!(restored.description, original.description);
+            assert_eq!(restored.time_horizon, original.time_horizon);
+            assert_eq!(restored.priority, original.priority);
+            assert_eq!(restored.completed, original.completed);
+        }
+    }
+}
@@ -0,0 +1,688 @@
+// TUI module - a full-screen terminal interface
+// This module demonstrates a state machine driven by key events, and ratatui widgets
+//
+// `todo tui` shows the active context as a board with one column per time
+// horizon, a context switcher on the left and a search box on top. Every
+// change is saved through Store as soon as it is made, so quitting (or
+// crashing) never loses work.
+//
+// # Keys
+//
+// | Key              | Action                                           |
+// |------------------|--------------------------------------------------|
+// | ←/→ or h/l       | Move between horizon columns                     |
+// | ↑/↓ or j/k       | Move within a column                             |
+// | space or c       | Complete the selected task                       |
+// | e                | Edit the selected task's description             |
+// | d                | Delete the selected task (asks first)            |
+// | p                | Bump priority: low → medium → high → low         |
+// | n                | New task in the current column                   |
+// | a                | Show or hide completed tasks (like `list --all`) |
+// | /                | Search descriptions; Esc clears the search       |
+// | Tab              | Focus the contexts sidebar (Enter switches)      |
+// | q or Ctrl-C      | Quit                                             |
+//
+// The colours follow display.rs: cyan horizon headers, red/yellow/dim
+// priorities, a green check for completed tasks and a green dot for the
+// active context.
+//
+// The key handling (App::on_key) is separate from drawing (App::render), so
+// it can be tested without a terminal.
+
+use crate::context::ContextManager;
+use crate::error::{AppError, Result};
+use crate::store::Store;
+use crate::task::{Priority, Task, TimeHorizon};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+use std::io::IsTerminal;
+
+/// The board columns, in display order, with the headers display.rs uses
+const COLUMNS: [(TimeHorizon, &str); 3] = [
+    (TimeHorizon::ShortTerm, "SHORT-TERM"),
+    (TimeHorizon::MidTerm, "MID-TERM"),
+    (TimeHorizon::LongTerm, "LONG-TERM"),
+];
+
+/// Which part of the screen receives navigation keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Board,
+    Contexts,
+}
+
+/// What typed keys currently mean
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mode {
+    /// Single-key commands
+    Normal,
+    /// Typing into the search box
+    Search,
+    /// Typing a new description for the task with this ID
+    Edit(String),
+    /// Typing the description of a new task
+    Add,
+    /// Waiting for y/n before deleting the task with this ID
+    ConfirmDelete(String),
+}
+
+/// The interface state: the data, what is selected, and the current mode
+pub struct App<'a> {
+    store: &'a Store,
+    manager: &'a mut ContextManager,
+    focus: Focus,
+    mode: Mode,
+    column: usize,
+    selected: [usize; 3],
+    context_cursor: usize,
+    search: String,
+    input: String,
+    show_completed: bool,
+    status: String,
+    quit: bool,
+}
+
+impl<'a> App<'a> {
+    /// Creates the interface for `manager`, saving changes through `store`
+    pub fn new(store: &'a Store, manager: &'a mut ContextManager) -> Self {
+        App {
+            store,
+            manager,
+            focus: Focus::Board,
+            mode: Mode::Normal,
+            column: 0,
+            selected: [0; 3],
+            context_cursor: 0,
+            search: String::new(),
+            input: String::new(),
+            show_completed: false,
+            status: "Press q to quit, Tab for contexts, / to search".to_string(),
+            quit: false,
+        }
+    }
+
+    /// Returns true once the user has asked to quit
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// The tasks shown in a column: open tasks first, then by priority
+    fn column_tasks(&self, column: usize) -> Vec<&Task> {
+        let horizon = COLUMNS[column].0;
+        let needle = self.search.to_lowercase();
+
+        let mut tasks: Vec<&Task> = self
+            .manager
+            .active_context()
+            .tasks
+            .iter()
+            .filter(|task| task.time_horizon == horizon)
+            .filter(|task| self.show_completed || !task.completed)
+            .filter(|task| needle.is_empty() || task.description.to_lowercase().contains(&needle))
+            .collect();
+
+        // sort_by_key is stable, so equal tasks keep their creation order
+        tasks.sort_by_key(|task| (task.completed, std::cmp::Reverse(task.priority)));
+        tasks
+    }
+
+    /// The full ID of the selected task, if the current column has any
+    fn selected_id(&self) -> Option<String> {
+        let tasks = self.column_tasks(self.column);
+        let index = self.selected[self.column].min(tasks.len().checked_sub(1)?);
+        Some(tasks[index].id.clone())
+    }
+
+    /// Context names in sidebar order
+    fn context_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.manager.contexts.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Keeps selections inside their (possibly shrunk) columns
+    fn clamp_selection(&mut self) {
+        for column in 0..COLUMNS.len() {
+            let len = self.column_tasks(column).len();
+            self.selected[column] = self.selected[column].min(len.saturating_sub(1));
+        }
+    }
+
+    /// Saves the data and reports the action in the status line
+    fn save(&mut self, status: String) -> Result<()> {
+        self.store.save(self.manager)?;
+        self.status = status;
+        self.clamp_selection();
+        Ok(())
+    }
+
+    /// Applies a change to the selected task, then saves
+    fn with_selected(&mut self, change: impl FnOnce(&mut Task) -> String) -> Result<()> {
+        let Some(id) = self.selected_id() else {
+            self.status = "No task selected".to_string();
+            return Ok(());
+        };
+        let task = self
+            .manager
+            .active_context_mut()
+            .find_task_mut(&id)
+            .ok_or(AppError::TaskNotFound(id))?;
+        let status = change(task);
+        self.save(status)
+    }
+
+    /// Handles one key press
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ratatui::crossterm::event::{KeyCode, KeyEvent};
+    /// use rust_todo::context::ContextManager;
+    /// use rust_todo::store::Store;
+    /// use rust_todo::tui::App;
+    ///
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let store = Store::new(dir.path().join("data.json"));
+    /// let mut manager = ContextManager::new();
+    ///
+    /// let mut app = App::new(&store, &mut manager);
+    /// app.on_key(KeyEvent::from(KeyCode::Char('q'))).unwrap();
+    /// assert!(app.should_quit());
+    /// ```
+    pub fn on_key(&mut self, key: KeyEvent) -> Result<()> {
+        // Raw mode delivers Ctrl-C as a key rather than a signal
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return Ok(());
+        }
+
+        match self.mode.clone() {
+            Mode::Normal => match self.focus {
+                Focus::Board => self.on_board_key(key.code),
+                Focus::Contexts => self.on_contexts_key(key.code),
+            },
+            Mode::Search => {
+                match key.code {
+                    KeyCode::Esc => self.search.clear(),
+                    KeyCode::Backspace => {
+                        self.search.pop();
+                    }
+                    KeyCode::Char(c) => self.search.push(c),
+                    _ => {}
+                }
+                if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+                    self.mode = Mode::Normal;
+                }
+                self.clamp_selection();
+                Ok(())
+            }
+            Mode::Edit(_) | Mode::Add => self.on_input_key(key.code),
+            Mode::ConfirmDelete(id) => {
+                self.mode = Mode::Normal;
+                if key.code != KeyCode::Char('y') {
+                    self.status = "Delete cancelled".to_string();
+                    return Ok(());
+                }
+                let removed = self.manager.active_context_mut().delete_task(&id)?;
+                self.save(format!("✓ Deleted: {}", removed.description))
+            }
+        }
+    }
+
+    /// Single-key commands on the board
+    fn on_board_key(&mut self, code: KeyCode) -> Result<()> {
+        let len = self.column_tasks(self.column).len();
+        let selected = &mut self.selected[self.column];
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Left | KeyCode::Char('h') => self.column = self.column.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.column = (self.column + 1).min(COLUMNS.len() - 1)
+            }
+            KeyCode::Up | KeyCode::Char('k') => *selected = selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                *selected = (*selected + 1).min(len.saturating_sub(1))
+            }
+            KeyCode::Tab => self.focus = Focus::Contexts,
+            KeyCode::Char('/') => self.mode = Mode::Search,
+            KeyCode::Char('a') => {
+                self.show_completed = !self.show_completed;
+                self.clamp_selection();
+            }
+            KeyCode::Char(' ') | KeyCode::Char('c') => {
+                return self.with_selected(|task| {
+                    if task.completed {
+                        return format!("Already completed: {}", task.description);
+                    }
+                    task.mark_complete();
+                    format!("✓ Completed: {}", task.description)
+                })
+            }
+            KeyCode::Char('p') => {
+                return self.with_selected(|task| {
+                    let next = match task.priority {
+                        Priority::Low => Priority::Medium,
+                        Priority::Medium => Priority::High,
+                        Priority::High => Priority::Low,
+                    };
+                    task.update(None, None, Some(next), None);
+                    format!("✓ Priority {:?}: {}", next, task.description)
+                })
+            }
+            KeyCode::Char('e') => {
+                if let Some(id) = self.selected_id() {
+                    let task = self.manager.active_context().find_task(&id);
+                    self.input = task.map(|t| t.description.clone()).unwrap_or_default();
+                    self.mode = Mode::Edit(id);
+                }
+            }
+            KeyCode::Char('n') => {
+                self.input.clear();
+                self.mode = Mode::Add;
+            }
+            KeyCode::Char('d') => {
+                if let Some(id) = self.selected_id() {
+                    self.mode = Mode::ConfirmDelete(id);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Navigation in the contexts sidebar
+    fn on_contexts_key(&mut self, code: KeyCode) -> Result<()> {
+        let names = self.context_names();
+        match code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Tab | KeyCode::Esc => self.focus = Focus::Board,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.context_cursor = self.context_cursor.saturating_sub(1)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.context_cursor = (self.context_cursor + 1).min(names.len() - 1)
+            }
+            KeyCode::Enter => {
+                let name = &names[self.context_cursor.min(names.len() - 1)];
+                self.manager.switch_context(name)?;
+                self.selected = [0; 3];
+                self.focus = Focus::Board;
+                return self.save(format!("✓ Switched to context: {}", name));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Typing a description for an edited or new task
+    fn on_input_key(&mut self, code: KeyCode) -> Result<()> {
+        match code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Enter => {
+                let description = self.input.trim().to_string();
+                let mode = std::mem::replace(&mut self.mode, Mode::Normal);
+                if description.is_empty() {
+                    self.status = "Description can't be empty".to_string();
+                    return Ok(());
+                }
+
+                return match mode {
+                    Mode::Edit(id) => {
+                        let task = self
+                            .manager
+                            .active_context_mut()
+                            .find_task_mut(&id)
+                            .ok_or(AppError::TaskNotFound(id))?;
+                        task.update(Some(description.clone()), None, None, None);
+                        self.save(format!("✓ Updated: {}", description))
+                    }
+                    _ => {
+                        let horizon = COLUMNS[self.column].0;
+                        let task = Task::new(description.clone(), horizon, Priority::Medium);
+                        self.manager.active_context_mut().add_task(task);
+                        self.save(format!("✓ Added: {}", description))
+                    }
+                };
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Draws the whole screen
+    pub fn render(&self, frame: &mut Frame) {
+        let [sidebar, main] =
+            Layout::horizontal([Constraint::Length(24), Constraint::Min(0)]).areas(frame.area());
+        let [search, board, status] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(main);
+
+        self.render_contexts(frame, sidebar);
+        self.render_search(frame, search);
+        let columns = Layout::horizontal([Constraint::Ratio(1, 3); 3]).split(board);
+        for (column, area) in columns.iter().enumerate() {
+            self.render_column(frame, column, *area);
+        }
+        frame.render_widget(Paragraph::new(self.status_line()), status);
+    }
+
+    fn render_contexts(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .context_names()
+            .into_iter()
+            .map(|name| {
+                if name == self.manager.active_context {
+                    let active = Style::new().fg(Color::Green).add_modifier(Modifier::BOLD);
+                    ListItem::new(Line::from(vec![
+                        Span::styled("● ", active),
+                        Span::styled(name, active),
+                    ]))
+                } else {
+                    ListItem::new(Line::from(vec![
+                        Span::styled("○ ", Style::new().add_modifier(Modifier::DIM)),
+                        Span::raw(name),
+                    ]))
+                }
+            })
+            .collect();
+
+        let mut state = ListState::default();
+        if self.focus == Focus::Contexts {
+            state.select(Some(self.context_cursor));
+        }
+        let list = List::new(items)
+            .block(focused_block(" Contexts ", self.focus == Focus::Contexts))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_search(&self, frame: &mut Frame, area: Rect) {
+        let searching = self.mode == Mode::Search;
+        let text = if searching {
+            format!("{}█", self.search)
+        } else if self.search.is_empty() {
+            "press / to search".to_string()
+        } else {
+            self.search.clone()
+        };
+        let style = if searching || !self.search.is_empty() {
+            Style::new()
+        } else {
+            Style::new().add_modifier(Modifier::DIM)
+        };
+        let search =
+            Paragraph::new(Span::styled(text, style)).block(focused_block(" Search ", searching));
+        frame.render_widget(search, area);
+    }
+
+    fn render_column(&self, frame: &mut Frame, column: usize, area: Rect) {
+        let tasks = self.column_tasks(column);
+        let title = format!(" {} ({}) ", COLUMNS[column].1, tasks.len());
+        let focused = self.focus == Focus::Board && self.column == column;
+
+        let items: Vec<ListItem> = tasks.iter().map(|task| task_item(task)).collect();
+        let mut state = ListState::default();
+        if focused && !tasks.is_empty() {
+            state.select(Some(self.selected[column]));
+        }
+
+        let block = focused_block("", focused).title(Line::styled(
+            title,
+            Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ));
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    /// The bottom line: a prompt while typing, otherwise the last status
+    fn status_line(&self) -> Line<'_> {
+        let prompt = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        match &self.mode {
+            Mode::Edit(_) => Line::from(vec![
+                Span::styled("Edit: ", prompt),
+                Span::raw(format!("{}█", self.input)),
+            ]),
+            Mode::Add => Line::from(vec![
+                Span::styled(format!("New {} task: ", COLUMNS[self.column].1), prompt),
+                Span::raw(format!("{}█", self.input)),
+            ]),
+            Mode::ConfirmDelete(id) => {
+                let description = self
+                    .manager
+                    .active_context()
+                    .find_task(id)
+                    .map(|task| task.description.as_str())
+                    .unwrap_or_default();
+                Line::from(vec![
+                    Span::styled(
+                        "Delete ",
+                        Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(format!("'{}'? (y/n)", description)),
+                ])
+            }
+            Mode::Normal | Mode::Search => Line::styled(
+                self.status.as_str(),
+                Style::new().add_modifier(Modifier::DIM),
+            ),
+        }
+    }
+}
+
+/// A bordered block, highlighted in cyan when it has focus
+fn focused_block(title: &str, focused: bool) -> Block<'_> {
+    let border = if focused {
+        Style::new().fg(Color::Cyan)
+    } else {
+        Style::new().add_modifier(Modifier::DIM)
+    };
+    Block::bordered().title(title).border_style(border)
+}
+
+/// One task, styled like display::format_task_line
+fn task_item(task: &Task) -> ListItem<'static> {
+    let checkbox = if task.completed {
+        Span::styled("[✓] ", Style::new().fg(Color::Green))
+    } else {
+        Span::raw("[ ] ")
+    };
+    let priority = match task.priority {
+        Priority::High => Span::styled(
+            "HIGH ",
+            Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        Priority::Medium => Span::styled("MED  ", Style::new().fg(Color::Yellow)),
+        Priority::Low => Span::styled("LOW  ", Style::new().add_modifier(Modifier::DIM)),
+    };
+    let description = if task.completed {
+        Span::styled(
+            task.description.clone(),
+            Style::new().add_modifier(Modifier::DIM | Modifier::CROSSED_OUT),
+        )
+    } else {
+        Span::raw(task.description.clone())
+    };
+
+    ListItem::new(Line::from(vec![checkbox, priority, description]))
+}
+
+/// Runs the interface until the user quits
+///
+/// The terminal is restored on the way out, including when an error ends
+/// the session early.
+pub fn run(store: &Store, manager: &mut ContextManager) -> Result<()> {
+    if !std::io::stdout().is_terminal() {
+        return Err(AppError::InvalidRequest(
+            "todo tui needs an interactive terminal".to_string(),
+        ));
+    }
+
+    let mut terminal = ratatui::try_init()?;
+    let mut app = App::new(store, manager);
+
+    let result = (|| -> Result<()> {
+        while !app.should_quit() {
+            terminal.draw(|frame| app.render(frame))?;
+            if let Event::Key(key) = event::read()? {
+                // Windows also reports key releases; act on presses only
+                if key.kind == KeyEventKind::Press {
+                    app.on_key(key)?;
+                }
+            }
+        }
+        Ok(())
+    })();
+
+    ratatui::try_restore()?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn press(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            let code = match c {
+                '\n' => KeyCode::Enter,
+                '\t' => KeyCode::Tab,
+                '\u{1b}' => KeyCode::Esc,
+                c => KeyCode::Char(c),
+            };
+            app.on_key(KeyEvent::from(code)).unwrap();
+        }
+    }
+
+    fn manager_with_tasks() -> ContextManager {
+        let mut manager = ContextManager::new();
+        let context = manager.active_context_mut();
+        context.add_task(Task::new(
+            "Low".to_string(),
+            TimeHorizon::ShortTerm,
+            Priority::Low,
+        ));
+        context.add_task(Task::new(
+            "High".to_string(),
+            TimeHorizon::ShortTerm,
+            Priority::High,
+        ));
+        context.add_task(Task::new(
+            "Later".to_string(),
+            TimeHorizon::LongTerm,
+            Priority::Medium,
+        ));
+        manager
+    }
+
+    #[test]
+    fn test_actions_are_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::new(dir.path().join("data.json"));
+        let mut manager = manager_with_tasks();
+        let mut app = App::new(&store, &mut manager);
+
+        // High priority sorts first; complete it, then bump "Low" to medium
+        press(&mut app, " p");
+        // "Later" is in the long-term column; delete it, confirming
+        press(&mut app, "lldy");
+        // Add a mid-term task
+        press(&mut app, "hnShip it\n");
+
+        let saved = store.load().unwrap();
+        let tasks = &saved.active_context().tasks;
+        assert!(
+            tasks
+                .iter()
+                .find(|t| t.description == "High")
+                .unwrap()
+                .completed
+        );
+        assert_eq!(
+            tasks
+                .iter()
+                .find(|t| t.description == "Low")
+                .unwrap()
+                .priority,
+            Priority::Medium
+        );
+        assert!(!tasks.iter().any(|t| t.description == "Later"));
+        let added = tasks.iter().find(|t| t.description == "Ship it").unwrap();
+        assert_eq!(added.time_horizon, TimeHorizon::MidTerm);
+    }
+
+    #[test]
+    fn test_edit_and_cancel() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::new(dir.path().join("data.json"));
+        let mut manager = manager_with_tasks();
+        let mut app = App::new(&store, &mut manager);
+
+        // Replace "High" with "Higher"
+        press(&mut app, "eer\n");
+        // Start deleting, then decline
+        press(&mut app, "dn");
+        drop(app);
+
+        let tasks = &manager.active_context().tasks;
+        assert!(tasks.iter().any(|t| t.description == "Higher"));
+        assert_eq!(tasks.len(), 3);
+    }
+
+    #[test]
+    fn test_search_and_context_switch() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::new(dir.path().join("data.json"));
+        let mut manager = manager_with_tasks();
+        manager.create_context("work".to_string()).unwrap();
+        let mut app = App::new(&store, &mut manager);
+
+        press(&mut app, "/low\n");
+        assert_eq!(app.column_tasks(0).len(), 1);
+        press(&mut app, "/\u{1b}");
+        assert_eq!(app.column_tasks(0).len(), 2);
+
+        // Contexts are sorted: default, work
+        press(&mut app, "\tj\n");
+        assert_eq!(app.manager.active_context, "work");
+        assert_eq!(store.load().unwrap().active_context, "work");
+    }
+
+    #[test]
+    fn test_render_shows_columns_and_contexts() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::new(dir.path().join("data.json"));
+        let mut manager = manager_with_tasks();
+        let app = App::new(&store, &mut manager);
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("SHORT-TERM (2)"));
+        assert!(screen.contains("LONG-TERM (1)"));
+        assert!(screen.contains("● default"));
+        assert!(screen.contains("HIGH High"));
+    }
+}
@@ -0,0 +1,339 @@
+// Urgency module - a single number for "what should I do next?"
+// This module demonstrates serde defaults for partially written config, and
+// floating point scoring
+//
+// Three priority levels leave many ties, and a low priority task that has sat
+// in the short-term list for months is arguably more pressing than a fresh
+// one. The urgency of an open task adds up several factors:
+//
+// | Factor     | Default weight                                        |
+// |------------|-------------------------------------------------------|
+// | `priority` | high 6.0, medium 3.9, low 1.8                         |
+// | `horizon`  | short-term 4.0, mid-term 2.0, long-term 0.0           |
+// | `age`      | up to 2.0, growing linearly over 365 days             |
+// | `due`      | up to 8.0, growing linearly over the 14 days before   |
+// |            | the due date; due today or overdue gets all of it     |
+// | `blocked`  | -5.0 for tasks tagged `blocked`                       |
+//
+// `todo next` lists the most urgent open tasks of all contexts with this
+// breakdown, and `todo list --sort urgency` sorts by it (most urgent first).
+//
+// # Configuration
+//
+// The weights are read from the `urgency` section of `config.json`, next to
+// the data file (see the config module). Every field is optional; missing
+// ones keep their defaults:
+//
+// ```json
+// {
+//   "urgency": {
+//     "priority_high": 8.0,
+//     "age": 4.0,
+//     "age_days": 90,
+//     "due_days": 7,
+//     "blocked_tag": "waiting"
+//   }
+// }
+// ```
+//
+// Due dates are days, so the due factor counts whole days from today in
+// local time. Tasks have no dependencies, so "blocked" means carrying the
+// blocked tag.
+
+use crate::task::{Priority, Task, TimeHorizon};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Weights of the urgency factors, as set in the config file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UrgencyWeights {
+    pub priority_high: f64,
+    pub priority_medium: f64,
+    pub priority_low: f64,
+
+    pub horizon_short: f64,
+    pub horizon_mid: f64,
+    pub horizon_long: f64,
+
+    /// Weight reached by tasks `age_days` old or older
+    pub age: f64,
+
+    /// Age in days at which the age factor reaches its full weight
+    pub age_days: u32,
+
+    /// Weight reached by tasks due today or overdue
+    pub due: f64,
+
+    /// Days before the due date at which the due factor starts growing
+    pub due_days: u32,
+
+    /// Added for blocked tasks; normally negative
+    pub blocked: f64,
+
+    /// The tag marking a task as blocked (compared ignoring case)
+    pub blocked_tag: String,
+}
+
+impl Default for UrgencyWeights {
+    fn default() -> Self {
+        UrgencyWeights {
+            priority_high: 6.0,
+            priority_medium: 3.9,
+            priority_low: 1.8,
+            horizon_short: 4.0,
+            horizon_mid: 2.0,
+            horizon_long: 0.0,
+            age: 2.0,
+            age_days: 365,
+            due: 8.0,
+            due_days: 14,
+            blocked: -5.0,
+            blocked_tag: "blocked".to_string(),
+        }
+    }
+}
+
+/// A task's urgency, factor by factor
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Urgency {
+    pub priority: f64,
+    pub horizon: f64,
+    pub age: f64,
+    pub due: f64,
+    pub blocked: f64,
+}
+
+impl Urgency {
+    /// Computes a task's urgency at the instant `now`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use rust_todo::task::{Priority, Task, TimeHorizon};
+    /// use rust_todo::urgency::{Urgency, UrgencyWeights};
+    ///
+    /// let weights = UrgencyWeights::default();
+    /// let mut task = Task::new("Ship it".to_string(), TimeHorizon::ShortTerm, Priority::High);
+    ///
+    /// let urgency = Urgency::of(&task, &weights, Utc::now());
+    /// assert_eq!(urgency.priority, 6.0);
+    /// assert_eq!(urgency.horizon, 4.0);
+    ///
+    /// task.tags.push("Blocked".to_string());
+    /// assert!(Urgency::of(&task, &weights, Utc::now()).total() < urgency.total());
+    /// ```
+    pub fn of(task: &Task, weights: &UrgencyWeights, now: DateTime<Utc>) -> Self {
+        let priority = match task.priority {
+            Priority::High => weights.priority_high,
+            Priority::Medium => weights.priority_medium,
+            Priority::Low => weights.priority_low,
+        };
+        let horizon = match task.time_horizon {
+            TimeHorizon::ShortTerm => weights.horizon_short,
+            TimeHorizon::MidTerm => weights.horizon_mid,
+            TimeHorizon::LongTerm => weights.horizon_long,
+        };
+
+        // Linear in age, capped at the full weight; unparseable dates count as new
+        let age = match DateTime::parse_from_rfc3339(&task.created_at) {
+            Ok(created) if weights.age_days > 0 => {
+                let days = (now - created.with_timezone(&Utc)).num_seconds() as f64 / 86_400.0;
+                weights.age * (days / weights.age_days as f64).clamp(0.0, 1.0)
+            }
+            Ok(_) => weights.age,
+            Err(_) => 0.0,
+        };
+
+        // Nothing until `due_days` before the due date, then linear up to
+        // the full weight on the day itself
+        let due = match task.due {
+            Some(due) => {
+                let today = now.with_timezone(&Local).date_naive();
+                let days_left = (due - today).num_days() as f64;
+                let closeness = if weights.due_days > 0 {
+                    1.0 - days_left / weights.due_days as f64
+                } else if days_left <= 0.0 {
+                    1.0
+                } else {
+                    0.0
+                };
+                weights.due * closeness.clamp(0.0, 1.0)
+            }
+            None => 0.0,
+        };
+
+        let is_blocked = task
+            .tags
+            .iter()
+            .any(|tag| tag.eq_ignore_ascii_case(&weights.blocked_tag));
+        let blocked = if is_blocked { weights.blocked } else { 0.0 };
+
+        Urgency {
+            priority,
+            horizon,
+            age,
+            due,
+            blocked,
+        }
+    }
+
+    /// The urgency score: the sum of the factors
+    pub fn total(&self) -> f64 {
+        self.priority + self.horizon + self.age + self.due + self.blocked
+    }
+}
+
+/// One task ranked by `todo next`
+#[derive(Debug, Clone)]
+pub struct Ranked<'a> {
+    /// Name of the context the task is in
+    pub context: &'a str,
+    pub task: &'a Task,
+    pub urgency: Urgency,
+}
+
+/// Ranks the open tasks of every context, most urgent first
+///
+/// Tasks with equal urgency are ordered by context name and then by their
+/// manual order, so the ranking is stable.
+///
+/// # Example
+///
+/// ```
+/// use chrono::Utc;
+/// use rust_todo::context::ContextManager;
+/// use rust_todo::task::{Priority, Task, TimeHorizon};
+/// use rust_todo::urgency::{rank, UrgencyWeights};
+///
+/// let mut manager = ContextManager::new();
+/// let context = manager.active_context_mut();
+/// context.add_task(Task::new("Someday".to_string(), TimeHorizon::LongTerm, Priority::Low));
+/// context.add_task(Task::new("Today".to_string(), TimeHorizon::ShortTerm, Priority::High));
+///
+/// let ranked = rank(&manager, &UrgencyWeights::default(), Utc::now());
+/// assert_eq!(ranked[0].task.description, "Today");
+/// ```
+pub fn rank<'a>(
+    manager: &'a crate::context::ContextManager,
+    weights: &UrgencyWeights,
+    now: DateTime<Utc>,
+) -> Vec<Ranked<'a>> {
+    let mut names: Vec<&String> = manager.contexts.keys().collect();
+    names.sort();
+
+    let mut ranked: Vec<Ranked> = names
+        .into_iter()
+        .map(|name| &manager.contexts[name])
+        .flat_map(|context| {
+            context
+                .tasks
+                .iter()
+                .filter(|task| !task.completed)
+                .map(move |task| Ranked {
+                    context: &context.name,
+                    task,
+                    urgency: Urgency::of(task, weights, now),
+                })
+        })
+        .collect();
+
+    // Stable, so ties keep the context and manual order
+    ranked.sort_by(|a, b| b.urgency.total().total_cmp(&a.urgency.total()));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn task_created(days_ago: i64, now: DateTime<Utc>) -> Task {
+        let mut task = Task::new("Task".to_string(), TimeHorizon::MidTerm, Priority::Medium);
+        task.created_at = (now - Duration::days(days_ago)).to_rfc3339();
+        task
+    }
+
+    #[test]
+    fn test_age_grows_linearly_and_caps() {
+        let now = Utc::now();
+        let weights = UrgencyWeights {
+            age: 4.0,
+            age_days: 100,
+            ..UrgencyWeights::default()
+        };
+
+        assert_eq!(Urgency::of(&task_created(0, now), &weights, now).age, 0.0);
+        assert!((Urgency::of(&task_created(25, now), &weights, now).age - 1.0).abs() < 1e-9);
+        assert_eq!(Urgency::of(&task_created(500, now), &weights, now).age, 4.0);
+
+        let mut broken = task_created(10, now);
+        broken.created_at = "yesterday".to_string();
+        assert_eq!(Urgency::of(&broken, &weights, now).age, 0.0);
+    }
+
+    #[test]
+    fn test_due_grows_as_the_due_date_nears() {
+        let now = Utc::now();
+        let today = now.with_timezone(&Local).date_naive();
+        let weights = UrgencyWeights {
+            due: 4.0,
+            due_days: 8,
+            ..UrgencyWeights::default()
+        };
+        let due_in = |days: i64| {
+            let mut task = task_created(0, now);
+            task.due = Some(today + Duration::days(days));
+            Urgency::of(&task, &weights, now).due
+        };
+
+        assert_eq!(Urgency::of(&task_created(0, now), &weights, now).due, 0.0);
+        assert_eq!(due_in(30), 0.0);
+        assert_eq!(due_in(8), 0.0);
+        assert_eq!(due_in(2), 3.0);
+        assert_eq!(due_in(0), 4.0);
+        assert_eq!(due_in(-3), 4.0);
+    }
+
+    #[test]
+    fn test_partial_config_keeps_defaults() {
+        let weights: UrgencyWeights =
+            serde_json::from_str(r#"{ "priority_high": 10.0, "blocked_tag": "waiting" }"#).unwrap();
+        assert_eq!(weights.priority_high, 10.0);
+        assert_eq!(weights.priority_low, 1.8);
+        assert_eq!(weights.blocked_tag, "waiting");
+
+        let now = Utc::now();
+        let mut task = task_created(0, now);
+        task.tags.push("waiting".to_string());
+        assert_eq!(Urgency::of(&task, &weights, now).blocked, -5.0);
+    }
+
+    #[test]
+    fn test_rank_skips_completed_and_orders_by_total() {
+        let now = Utc::now();
+        let mut manager = crate::context::ContextManager::new();
+        manager.create_context("work".to_string()).unwrap();
+
+        let mut old = task_created(365, now);
+        old.description = "Old".to_string();
+        let mut done = task_created(0, now);
+        done.description = "Done".to_string();
+        done.priority = Priority::High;
+        done.mark_complete();
+        let mut fresh = task_created(0, now);
+        fresh.description = "Fresh".to_string();
+
+        manager.active_context_mut().add_task(fresh);
+        manager.active_context_mut().add_task(done);
+        manager.contexts.get_mut("work").unwrap().add_task(old);
+
+        let ranked = rank(&manager, &UrgencyWeights::default(), now);
+        let order: Vec<(&str, &str)> = ranked
+            .iter()
+            .map(|r| (r.context, r.task.description.as_str()))
+            .collect();
+        assert_eq!(order, [("work", "Old"), ("default", "Fresh")]);
+    }
+}
@@ -0,0 +1,291 @@
+// Views module - saved filters with a sort order
+// This module demonstrates serde defaults and lookups with fallbacks
+//
+// A view is a named filter expression (see the filter module) plus optional
+// sort keys (see the sort module):
+//
+// ```bash
+// todo view save triage "priority:high not completed" --sort created
+// todo view triage                   # run it
+// todo export triage.json --view triage
+// todo report --html triage.html --view triage
+// ```
+//
+// Views are global by default, so they can be run in any context. A view
+// saved with `--context work` belongs to that context: it is only visible
+// while `work` is active, and it takes precedence there over a global view
+// with the same name.
+//
+// Views are kept in `views.json` next to the data file rather than in the
+// data file itself, so export, import and sync are unaffected by them. The
+// filter and sort key are stored as text and checked when the view is saved.
+
+use crate::context::Context;
+use crate::error::{AppError, Result};
+use crate::filter::Filter;
+use crate::sort::SortOrder;
+use crate::task::Task;
+use crate::urgency::UrgencyWeights;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Name of the views file, kept next to the data file
+pub const VIEWS_FILE: &str = "views.json";
+
+/// Names taken by the `view` subcommands
+const RESERVED_NAMES: [&str; 3] = ["save", "list", "delete"];
+
+/// A saved filter and sort order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct View {
+    /// Name the view is run by
+    pub name: String,
+
+    /// Filter expression, e.g. "priority:high not completed"
+    pub filter: String,
+
+    /// Sort keys, e.g. "created" or "priority,-created"; None keeps the
+    /// stored order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+
+    /// The context the view belongs to, or None for a global view
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+}
+
+impl View {
+    /// Creates a view, checking its name, filter and sort keys
+    ///
+    /// # Errors
+    ///
+    /// - AppError::InvalidRequest for an empty name or one of the `view`
+    ///   subcommand names (save, list, delete)
+    /// - AppError::InvalidFilter if the filter doesn't parse
+    /// - AppError::InvalidSortKey for an unknown or repeated sort key
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::views::View;
+    ///
+    /// let view = View::new("triage", "priority:high not completed", Some("created"), None);
+    /// assert!(view.is_ok());
+    ///
+    /// assert!(View::new("list", "tag:x", None, None).is_err());
+    /// assert!(View::new("broken", "priority:", None, None).is_err());
+    /// ```
+    pub fn new(
+        name: &str,
+        filter: &str,
+        sort: Option<&str>,
+        context: Option<&str>,
+    ) -> Result<Self> {
+        if name.trim().is_empty() {
+            return Err(AppError::InvalidRequest("a view needs a name".to_string()));
+        }
+        if RESERVED_NAMES.contains(&name) {
+            return Err(AppError::InvalidRequest(format!(
+                "'{}' is a view command; choose another name",
+                name
+            )));
+        }
+
+        let view = View {
+            name: name.to_string(),
+            filter: filter.to_string(),
+            sort: sort.map(str::to_string),
+            context: context.map(str::to_string),
+        };
+        view.parsed_filter()?;
+        view.sort_order()?;
+        Ok(view)
+    }
+
+    /// Parses the view's filter
+    pub fn parsed_filter(&self) -> Result<Filter> {
+        Filter::from_str(&self.filter)
+    }
+
+    /// Parses the view's sort keys, if it has any
+    pub fn sort_order(&self) -> Result<Option<SortOrder>> {
+        self.sort.as_deref().map(SortOrder::from_str).transpose()
+    }
+
+    /// Selects the tasks of `context` the view shows, in the view's order
+    ///
+    /// The filter alone decides which tasks are shown: completed tasks are
+    /// included unless the filter excludes them. `weights` are used if the
+    /// view sorts by urgency.
+    pub fn select<'a>(
+        &self,
+        context: &'a Context,
+        weights: &UrgencyWeights,
+    ) -> Result<Vec<&'a Task>> {
+        let filter = self.parsed_filter()?;
+        let mut tasks: Vec<&Task> = context
+            .tasks
+            .iter()
+            .filter(|task| filter.matches(task))
+            .collect();
+        if let Some(order) = self.sort_order()? {
+            order.with_weights(weights.clone()).sort(&mut tasks);
+        }
+        Ok(tasks)
+    }
+}
+
+/// All saved views, as stored in the views file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Views {
+    #[serde(default)]
+    pub views: Vec<View>,
+}
+
+impl Views {
+    /// Finds the view `name` as seen from the active context
+    ///
+    /// A view saved for the active context wins over a global one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_todo::views::{View, Views};
+    ///
+    /// let mut views = Views::default();
+    /// views.save(View::new("mine", "tag:x", None, None).unwrap());
+    /// views.save(View::new("mine", "tag:y", None, Some("work")).unwrap());
+    ///
+    /// assert_eq!(views.find("mine", "work").unwrap().filter, "tag:y");
+    /// assert_eq!(views.find("mine", "home").unwrap().filter, "tag:x");
+    /// assert!(views.find("other", "work").is_none());
+    /// ```
+    pub fn find(&self, name: &str, active_context: &str) -> Option<&View> {
+        let in_context = self
+            .views
+            .iter()
+            .find(|view| view.name == name && view.context.as_deref() == Some(active_context));
+        in_context.or_else(|| {
+            self.views
+                .iter()
+                .find(|view| view.name == name && view.context.is_none())
+        })
+    }
+
+    /// Adds a view, replacing one with the same name and scope
+    ///
+    /// # Returns
+    ///
+    /// True if an existing view was replaced.
+    pub fn save(&mut self, view: View) -> bool {
+        match self
+            .views
+            .iter_mut()
+            .find(|existing| existing.name == view.name && existing.context == view.context)
+        {
+            Some(existing) => {
+                *existing = view;
+                true
+            }
+            None => {
+                self.views.push(view);
+                false
+            }
+        }
+    }
+
+    /// Removes the view `name` as seen from the active context
+    ///
+    /// Like find(), a view of the active context is removed in preference
+    /// to a global one.
+    ///
+    /// # Errors
+    ///
+    /// Returns AppError::ViewNotFound if no such view is visible.
+    pub fn remove(&mut self, name: &str, active_context: &str) -> Result<View> {
+        let found = self
+            .find(name, active_context)
+            .cloned()
+            .ok_or_else(|| AppError::ViewNotFound(name.to_string()))?;
+        self.views.retain(|view| *view != found);
+        Ok(found)
+    }
+
+    /// Lists the views visible from the active context, sorted by name
+    pub fn visible(&self, active_context: &str) -> Vec<&View> {
+        let mut visible: Vec<&View> = self
+            .views
+            .iter()
+            .filter(|view| match &view.context {
+                Some(context) => context == active_context,
+                None => true,
+            })
+            .collect();
+        visible.sort_by(|a, b| a.name.cmp(&b.name).then(b.context.cmp(&a.context)));
+        visible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::{Priority, TimeHorizon};
+
+    #[test]
+    fn test_select_filters_and_sorts() {
+        let mut context = Context::new("work".to_string());
+        for (description, priority, created_at) in [
+            ("Old urgent", Priority::High, "2026-01-01T09:00:00+00:00"),
+            ("Minor", Priority::Low, "2026-01-02T09:00:00+00:00"),
+            ("New urgent", Priority::High, "2026-01-03T09:00:00+00:00"),
+        ] {
+            let mut task = Task::new(description.to_string(), TimeHorizon::ShortTerm, priority);
+            task.created_at = created_at.to_string();
+            context.add_task(task);
+        }
+        context.tasks[0].mark_complete();
+
+        let view = View::new("triage", "priority:high", Some("-created"), None).unwrap();
+        let selected: Vec<&str> = view
+            .select(&context, &UrgencyWeights::default())
+            .unwrap()
+            .iter()
+            .map(|task| task.description.as_str())
+            .collect();
+        // Completed tasks are only hidden if the filter says so
+        assert_eq!(selected, ["New urgent", "Old urgent"]);
+    }
+
+    #[test]
+    fn test_save_replaces_same_scope_only() {
+        let mut views = Views::default();
+        assert!(!views.save(View::new("v", "tag:a", None, None).unwrap()));
+        assert!(!views.save(View::new("v", "tag:b", None, Some("work")).unwrap()));
+        assert!(views.save(View::new("v", "tag:c", None, None).unwrap()));
+        assert_eq!(views.views.len(), 2);
+
+        assert_eq!(views.visible("home").len(), 1);
+        assert_eq!(views.visible("work").len(), 2);
+
+        // Removing from "work" takes the context's view, leaving the global one
+        assert_eq!(views.remove("v", "work").unwrap().filter, "tag:b");
+        assert_eq!(views.find("v", "work").unwrap().filter, "tag:c");
+        views.remove("v", "home").unwrap();
+        assert!(matches!(
+            views.remove("v", "home"),
+            Err(AppError::ViewNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_new_rejects_bad_views() {
+        assert!(matches!(
+            View::new("", "tag:a", None, None),
+            Err(AppError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            View::new("v", "tag:a", Some("deadline"), None),
+            Err(AppError::InvalidSortKey(_))
+        ));
+    }
+}